@@ -0,0 +1,308 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::{HttpClient, Season};
+use crate::config::AnimeConfig;
+use crate::error::ApiError;
+
+/// A single TMDB -> Kitsu/AniList mapping entry
+///
+/// Entries without a season map the whole show to one Kitsu entry that uses absolute
+/// episode numbering (e.g., long-running shows like One Piece). Entries with a season map
+/// that TMDB season to a Kitsu entry; `episode_offset` is the number of episodes of that
+/// season that belong to earlier entries (split-cour seasons use several entries).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnimeMappingEntry {
+    /// TMDB TV show ID
+    pub tmdb_id: i32,
+    /// TMDB season number (None = whole show with absolute numbering)
+    #[serde(default)]
+    pub season: Option<u32>,
+    /// Kitsu anime ID (used for Torrentio lookups)
+    pub kitsu_id: u32,
+    /// AniList anime ID (informational)
+    #[serde(default)]
+    pub anilist_id: Option<u32>,
+    /// Episodes to subtract before numbering within the Kitsu entry
+    #[serde(default)]
+    pub episode_offset: u32,
+}
+
+/// A resolved Kitsu episode, as understood by Torrentio's `kitsu:{id}:{ep}` form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KitsuEpisode {
+    pub kitsu_id: u32,
+    pub episode: u32,
+}
+
+/// ID mapping from TMDB shows to Kitsu/AniList entries
+#[derive(Debug, Clone, Default)]
+pub struct AnimeMapping {
+    entries: Vec<AnimeMappingEntry>,
+}
+
+impl AnimeMapping {
+    /// Parse a mapping from its JSON representation (an array of entries)
+    pub fn from_json(json: &str) -> Result<Self, ApiError> {
+        let entries: Vec<AnimeMappingEntry> = serde_json::from_str(json)
+            .map_err(|e| ApiError::AnimeMapping(format!("Invalid mapping file: {}", e)))?;
+        Ok(Self { entries })
+    }
+
+    /// Load the mapping using the configured sources
+    ///
+    /// The mapping service (if configured) is tried first, falling back to the local file.
+    /// A missing mapping is not an error: lookups simply fall back to IMDb IDs.
+    pub async fn load(config: &AnimeConfig, http: &HttpClient) -> Result<Self, ApiError> {
+        if !config.mapping_url.is_empty() {
+            match Self::fetch(&config.mapping_url, http).await {
+                Ok(mapping) => return Ok(mapping),
+                Err(e) => tracing::warn!("Failed to fetch anime mapping: {}", e),
+            }
+        }
+
+        let path = config
+            .mapping_file
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(default_mapping_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::load_file(&path)
+    }
+
+    /// Load the mapping from a local JSON file
+    pub fn load_file(path: &Path) -> Result<Self, ApiError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ApiError::AnimeMapping(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_json(&content)
+    }
+
+    /// Fetch the mapping from a mapping service
+    async fn fetch(url: &str, http: &HttpClient) -> Result<Self, ApiError> {
+        let response = http.send(http.get(url)).await?;
+
+        if !response.status().is_success() {
+            return Err(ApiError::AnimeMapping(format!(
+                "HTTP {}",
+                response.status()
+            )));
+        }
+
+        let body = response.text().await?;
+        Self::from_json(&body)
+    }
+
+    /// Check if the mapping has any entry for a TMDB show
+    pub fn contains(&self, tmdb_id: i32) -> bool {
+        self.entries.iter().any(|e| e.tmdb_id == tmdb_id)
+    }
+
+    /// Resolve a TMDB season/episode to a Kitsu episode
+    ///
+    /// `seasons` is the show's season list, needed to compute absolute numbers for
+    /// whole-show entries.
+    pub fn resolve(
+        &self,
+        tmdb_id: i32,
+        seasons: &[Season],
+        season: u32,
+        episode: u32,
+    ) -> Option<KitsuEpisode> {
+        // Prefer a season-specific entry: the one with the largest offset below this episode
+        let season_entry = self
+            .entries
+            .iter()
+            .filter(|e| e.tmdb_id == tmdb_id && e.season == Some(season))
+            .filter(|e| e.episode_offset < episode)
+            .max_by_key(|e| e.episode_offset);
+
+        if let Some(entry) = season_entry {
+            return Some(KitsuEpisode {
+                kitsu_id: entry.kitsu_id,
+                episode: episode - entry.episode_offset,
+            });
+        }
+
+        // Fall back to a whole-show entry with absolute numbering
+        let entry = self
+            .entries
+            .iter()
+            .find(|e| e.tmdb_id == tmdb_id && e.season.is_none())?;
+        let absolute = absolute_episode(seasons, season, episode)?;
+        absolute
+            .checked_sub(entry.episode_offset)
+            .filter(|ep| *ep > 0)
+            .map(|ep| KitsuEpisode {
+                kitsu_id: entry.kitsu_id,
+                episode: ep,
+            })
+    }
+}
+
+/// Default location of the local mapping file (~/.config/miru/anime-mapping.json)
+pub fn default_mapping_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("miru")
+        .join("anime-mapping.json")
+}
+
+/// Number of episodes aired before a season, across regular seasons
+///
+/// Returns None for specials, which have no place in absolute numbering.
+pub fn absolute_offset(seasons: &[Season], season: u32) -> Option<u32> {
+    if season == 0 {
+        return None;
+    }
+    Some(
+        seasons
+            .iter()
+            .filter(|s| !s.is_specials() && s.number < season)
+            .map(|s| s.episode_count)
+            .sum(),
+    )
+}
+
+/// Convert a seasonal episode number to an absolute one (e.g., S02E03 -> 27)
+pub fn absolute_episode(seasons: &[Season], season: u32, episode: u32) -> Option<u32> {
+    absolute_offset(seasons, season).map(|offset| offset + episode)
+}
+
+/// Convert an absolute episode number to a (season, episode) pair
+pub fn seasonal_episode(seasons: &[Season], absolute: u32) -> Option<(u32, u32)> {
+    let mut regular: Vec<&Season> = seasons.iter().filter(|s| !s.is_specials()).collect();
+    regular.sort_by_key(|s| s.number);
+
+    let mut remaining = absolute;
+    for season in regular {
+        if remaining == 0 {
+            return None;
+        }
+        if remaining <= season.episode_count {
+            return Some((season.number, remaining));
+        }
+        remaining -= season.episode_count;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_seasons() -> Vec<Season> {
+        vec![
+            Season {
+                number: 1,
+                episode_count: 24,
+            },
+            Season {
+                number: 2,
+                episode_count: 12,
+            },
+            Season {
+                number: 3,
+                episode_count: 22,
+            },
+            Season {
+                number: 0,
+                episode_count: 5,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_absolute_episode() {
+        let seasons = make_seasons();
+        assert_eq!(absolute_episode(&seasons, 1, 1), Some(1));
+        assert_eq!(absolute_episode(&seasons, 2, 3), Some(27));
+        assert_eq!(absolute_episode(&seasons, 3, 1), Some(37));
+        assert_eq!(absolute_episode(&seasons, 0, 1), None);
+    }
+
+    #[test]
+    fn test_seasonal_episode() {
+        let seasons = make_seasons();
+        assert_eq!(seasonal_episode(&seasons, 1), Some((1, 1)));
+        assert_eq!(seasonal_episode(&seasons, 24), Some((1, 24)));
+        assert_eq!(seasonal_episode(&seasons, 27), Some((2, 3)));
+        assert_eq!(seasonal_episode(&seasons, 58), Some((3, 22)));
+        assert_eq!(seasonal_episode(&seasons, 59), None);
+        assert_eq!(seasonal_episode(&seasons, 0), None);
+    }
+
+    #[test]
+    fn test_parse_mapping() {
+        let json = r#"[
+            {"tmdb_id": 37854, "kitsu_id": 12, "anilist_id": 21},
+            {"tmdb_id": 1429, "season": 3, "kitsu_id": 8671, "episode_offset": 0}
+        ]"#;
+        let mapping = AnimeMapping::from_json(json).unwrap();
+        assert!(mapping.contains(37854));
+        assert!(mapping.contains(1429));
+        assert!(!mapping.contains(1));
+        assert!(AnimeMapping::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_resolve_whole_show_uses_absolute_numbering() {
+        let mapping = AnimeMapping {
+            entries: vec![AnimeMappingEntry {
+                tmdb_id: 100,
+                season: None,
+                kitsu_id: 12,
+                anilist_id: None,
+                episode_offset: 0,
+            }],
+        };
+        let seasons = make_seasons();
+
+        assert_eq!(
+            mapping.resolve(100, &seasons, 2, 3),
+            Some(KitsuEpisode {
+                kitsu_id: 12,
+                episode: 27
+            })
+        );
+        // Specials have no absolute number
+        assert_eq!(mapping.resolve(100, &seasons, 0, 1), None);
+        // Unknown show
+        assert_eq!(mapping.resolve(200, &seasons, 1, 1), None);
+    }
+
+    #[test]
+    fn test_resolve_split_cour_season() {
+        let entry = |kitsu_id, episode_offset| AnimeMappingEntry {
+            tmdb_id: 100,
+            season: Some(3),
+            kitsu_id,
+            anilist_id: None,
+            episode_offset,
+        };
+        let mapping = AnimeMapping {
+            entries: vec![entry(1, 0), entry(2, 12)],
+        };
+        let seasons = make_seasons();
+
+        assert_eq!(
+            mapping.resolve(100, &seasons, 3, 12),
+            Some(KitsuEpisode {
+                kitsu_id: 1,
+                episode: 12
+            })
+        );
+        assert_eq!(
+            mapping.resolve(100, &seasons, 3, 13),
+            Some(KitsuEpisode {
+                kitsu_id: 2,
+                episode: 1
+            })
+        );
+        // No entry for season 1 and no whole-show entry
+        assert_eq!(mapping.resolve(100, &seasons, 1, 1), None);
+    }
+}
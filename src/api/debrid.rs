@@ -0,0 +1,754 @@
+//! Debrid services other than Real-Debrid
+//!
+//! Torrentio resolves streams through these services itself, so miru only needs
+//! their API keys; the clients here check a key by fetching the account it belongs
+//! to, and look up which torrents a service has cached. Real-Debrid has its own,
+//! fuller client ([`RealDebridClient`]).
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::api::torrentio::resolve_url;
+use crate::api::{HttpClient, RealDebridClient, Stream};
+use crate::config::DebridKind;
+use crate::error::ApiError;
+
+const ALLDEBRID_URL: &str = "https://api.alldebrid.com/v4";
+const PREMIUMIZE_URL: &str = "https://www.premiumize.me/api";
+const TORBOX_URL: &str = "https://api.torbox.app/v1/api";
+const DEBRIDLINK_URL: &str = "https://debrid-link.com/api/v2";
+
+/// App name sent to AllDebrid, which requires one
+const ALLDEBRID_AGENT: &str = "miru";
+
+/// Info hashes per availability request
+const AVAILABILITY_BATCH_SIZE: usize = 50;
+
+/// A debrid service that Torrentio resolves streams through
+#[async_trait]
+pub trait DebridService: Send + Sync {
+    fn kind(&self) -> DebridKind;
+
+    /// Current API key (or access token) for Torrentio's config string
+    fn api_key(&self) -> String;
+
+    /// Refresh the access token if it has expired (services with OAuth logins)
+    async fn ensure_token(&self) -> Result<(), ApiError> {
+        Ok(())
+    }
+
+    /// Check the API key by fetching the account's user name
+    async fn username(&self) -> Result<String, ApiError>;
+
+    /// Which of these info hashes (lowercase) the service has cached
+    ///
+    /// Only asked of services that [check caches by hash](DebridKind::checks_cache_by_hash).
+    async fn cached_hashes(&self, _hashes: &[String]) -> Result<HashSet<String>, ApiError> {
+        Ok(HashSet::new())
+    }
+}
+
+/// Look up P2P-only streams in the debrid services' caches
+///
+/// Torrentio only marks streams cached on services in its config string. This asks
+/// the services directly, in batches and in priority order (each service only about
+/// hashes the ones before it don't have), and gives each cached stream a Torrentio
+/// link through the service that has it. A service that fails is skipped, as are
+/// Real-Debrid and AllDebrid, which no longer answer such queries.
+pub async fn check_availability(services: &[Arc<dyn DebridService>], streams: &mut [Stream]) {
+    let mut pending: Vec<String> = Vec::new();
+    for stream in streams.iter().filter(|stream| stream.debrid.is_none()) {
+        if let Some(hash) = &stream.info_hash {
+            let hash = hash.to_lowercase();
+            if !pending.contains(&hash) {
+                pending.push(hash);
+            }
+        }
+    }
+
+    let mut cached_on: HashMap<String, &dyn DebridService> = HashMap::new();
+    let services = services
+        .iter()
+        .filter(|service| service.kind().checks_cache_by_hash());
+    for service in services {
+        if pending.is_empty() {
+            break;
+        }
+        for batch in pending.chunks(AVAILABILITY_BATCH_SIZE) {
+            match service.cached_hashes(batch).await {
+                Ok(cached) => {
+                    for hash in cached {
+                        cached_on.entry(hash).or_insert(service.as_ref());
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("{} availability check failed: {}", service.kind().name(), e);
+                    break;
+                }
+            }
+        }
+        pending.retain(|hash| !cached_on.contains_key(hash));
+    }
+
+    for stream in streams.iter_mut().filter(|stream| stream.debrid.is_none()) {
+        let Some(hash) = stream.info_hash.as_ref().map(|hash| hash.to_lowercase()) else {
+            continue;
+        };
+        if let Some(service) = cached_on.get(&hash) {
+            stream.url = Some(resolve_url(
+                service.kind(),
+                &service.api_key(),
+                &hash,
+                stream.file_idx,
+            ));
+            stream.debrid = Some(service.kind());
+            stream.is_cached = true;
+        }
+    }
+}
+
+/// Client for a debrid service, authenticated with an API key
+pub fn debrid_service(
+    kind: DebridKind,
+    api_key: String,
+    http: HttpClient,
+) -> Arc<dyn DebridService> {
+    match kind {
+        DebridKind::RealDebrid => Arc::new(RealDebridClient::new(api_key, http)),
+        DebridKind::AllDebrid => Arc::new(AllDebridClient::new(api_key, http)),
+        DebridKind::Premiumize => Arc::new(PremiumizeClient::new(api_key, http)),
+        DebridKind::TorBox => Arc::new(TorBoxClient::new(api_key, http)),
+        DebridKind::DebridLink => Arc::new(DebridLinkClient::new(api_key, http)),
+    }
+}
+
+#[async_trait]
+impl DebridService for RealDebridClient {
+    fn kind(&self) -> DebridKind {
+        DebridKind::RealDebrid
+    }
+
+    fn api_key(&self) -> String {
+        self.access_token()
+    }
+
+    async fn ensure_token(&self) -> Result<(), ApiError> {
+        RealDebridClient::ensure_token(self).await
+    }
+
+    async fn username(&self) -> Result<String, ApiError> {
+        Ok(self.validate_key().await?.username)
+    }
+}
+
+/// Send a request and parse the JSON response, mapping rejected keys to an auth error
+async fn get_json<T: DeserializeOwned>(
+    http: &HttpClient,
+    kind: DebridKind,
+    request: RequestBuilder,
+) -> Result<T, ApiError> {
+    let response = http.send(request).await?;
+    match response.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(ApiError::DebridAuth(kind.name())),
+        status if status.is_success() => Ok(response.json().await?),
+        status => Err(ApiError::Debrid(kind.name(), format!("HTTP {}", status))),
+    }
+}
+
+/// AllDebrid API client
+pub struct AllDebridClient {
+    http: HttpClient,
+    api_key: String,
+    base_url: String,
+}
+
+impl AllDebridClient {
+    pub fn new(api_key: String, http: HttpClient) -> Self {
+        Self {
+            http,
+            api_key,
+            base_url: ALLDEBRID_URL.to_string(),
+        }
+    }
+
+    /// Use another API URL (e.g., a local mock server)
+    #[cfg(test)]
+    fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AllDebridResponse<T> {
+    status: String,
+    data: Option<T>,
+    error: Option<AllDebridError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AllDebridError {
+    code: String,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AllDebridUserData {
+    user: AllDebridUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct AllDebridUser {
+    username: String,
+}
+
+impl<T> AllDebridResponse<T> {
+    /// The response data, or the error AllDebrid answered with
+    fn into_data(self, kind: DebridKind) -> Result<T, ApiError> {
+        match (self.status.as_str(), self.data, self.error) {
+            ("success", Some(data), _) => Ok(data),
+            (_, _, Some(error)) if error.code.starts_with("AUTH_") => {
+                Err(ApiError::DebridAuth(kind.name()))
+            }
+            (_, _, Some(error)) => Err(ApiError::Debrid(kind.name(), error.message)),
+            _ => Err(ApiError::Debrid(
+                kind.name(),
+                "Unexpected response".to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl DebridService for AllDebridClient {
+    fn kind(&self) -> DebridKind {
+        DebridKind::AllDebrid
+    }
+
+    fn api_key(&self) -> String {
+        self.api_key.clone()
+    }
+
+    async fn username(&self) -> Result<String, ApiError> {
+        let request = self
+            .http
+            .get(&format!("{}/user?agent={}", self.base_url, ALLDEBRID_AGENT))
+            .bearer_auth(&self.api_key);
+        let response: AllDebridResponse<AllDebridUserData> =
+            get_json(&self.http, self.kind(), request).await?;
+        Ok(response.into_data(self.kind())?.user.username)
+    }
+}
+
+/// Premiumize API client
+pub struct PremiumizeClient {
+    http: HttpClient,
+    api_key: String,
+    base_url: String,
+}
+
+impl PremiumizeClient {
+    pub fn new(api_key: String, http: HttpClient) -> Self {
+        Self {
+            http,
+            api_key,
+            base_url: PREMIUMIZE_URL.to_string(),
+        }
+    }
+
+    /// Use another API URL (e.g., a local mock server)
+    #[cfg(test)]
+    fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PremiumizeAccount {
+    status: String,
+    /// Premiumize has no user names; the customer ID (a number or string) stands in
+    customer_id: Option<serde_json::Value>,
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PremiumizeCacheCheck {
+    status: String,
+    /// Whether each requested item is cached, in request order
+    #[serde(default)]
+    response: Vec<bool>,
+    message: Option<String>,
+}
+
+#[async_trait]
+impl DebridService for PremiumizeClient {
+    fn kind(&self) -> DebridKind {
+        DebridKind::Premiumize
+    }
+
+    fn api_key(&self) -> String {
+        self.api_key.clone()
+    }
+
+    async fn username(&self) -> Result<String, ApiError> {
+        let request = self
+            .http
+            .get(&format!("{}/account/info", self.base_url))
+            .query(&[("apikey", &self.api_key)]);
+        let account: PremiumizeAccount = get_json(&self.http, self.kind(), request).await?;
+
+        match (account.status.as_str(), account.customer_id) {
+            ("success", Some(serde_json::Value::String(id))) => Ok(id),
+            ("success", Some(id)) => Ok(id.to_string()),
+            // Premiumize answers bad keys with "Not logged in." and HTTP 200
+            _ => {
+                if let Some(message) = account.message {
+                    tracing::debug!("Premiumize: {}", message);
+                }
+                Err(ApiError::DebridAuth(self.kind().name()))
+            }
+        }
+    }
+
+    async fn cached_hashes(&self, hashes: &[String]) -> Result<HashSet<String>, ApiError> {
+        let mut query = vec![("apikey", self.api_key.as_str())];
+        query.extend(hashes.iter().map(|hash| ("items[]", hash.as_str())));
+        let request = self
+            .http
+            .get(&format!("{}/cache/check", self.base_url))
+            .query(&query);
+        let check: PremiumizeCacheCheck = get_json(&self.http, self.kind(), request).await?;
+
+        if check.status != "success" {
+            return Err(ApiError::Debrid(
+                self.kind().name(),
+                check
+                    .message
+                    .unwrap_or_else(|| "Unexpected response".to_string()),
+            ));
+        }
+        Ok(hashes
+            .iter()
+            .zip(check.response)
+            .filter(|(_, cached)| *cached)
+            .map(|(hash, _)| hash.to_lowercase())
+            .collect())
+    }
+}
+
+/// TorBox API client
+pub struct TorBoxClient {
+    http: HttpClient,
+    api_key: String,
+    base_url: String,
+}
+
+impl TorBoxClient {
+    pub fn new(api_key: String, http: HttpClient) -> Self {
+        Self {
+            http,
+            api_key,
+            base_url: TORBOX_URL.to_string(),
+        }
+    }
+
+    /// Use another API URL (e.g., a local mock server)
+    #[cfg(test)]
+    fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TorBoxResponse<T> {
+    success: bool,
+    data: Option<T>,
+    detail: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TorBoxUser {
+    email: String,
+}
+
+impl<T> TorBoxResponse<T> {
+    /// The response data, or the reason TorBox gave
+    fn into_data(self, kind: DebridKind) -> Result<Option<T>, ApiError> {
+        if self.success {
+            return Ok(self.data);
+        }
+        Err(ApiError::Debrid(
+            kind.name(),
+            self.detail
+                .unwrap_or_else(|| "Unexpected response".to_string()),
+        ))
+    }
+}
+
+#[async_trait]
+impl DebridService for TorBoxClient {
+    fn kind(&self) -> DebridKind {
+        DebridKind::TorBox
+    }
+
+    fn api_key(&self) -> String {
+        self.api_key.clone()
+    }
+
+    async fn username(&self) -> Result<String, ApiError> {
+        let request = self
+            .http
+            .get(&format!("{}/user/me", self.base_url))
+            .bearer_auth(&self.api_key);
+        let response: TorBoxResponse<TorBoxUser> =
+            get_json(&self.http, self.kind(), request).await?;
+
+        response
+            .into_data(self.kind())?
+            .map(|user| user.email)
+            .ok_or_else(|| ApiError::Debrid(self.kind().name(), "No user data".to_string()))
+    }
+
+    async fn cached_hashes(&self, hashes: &[String]) -> Result<HashSet<String>, ApiError> {
+        let request = self
+            .http
+            .get(&format!("{}/torrents/checkcached", self.base_url))
+            .query(&[("hash", hashes.join(",").as_str()), ("format", "object")])
+            .bearer_auth(&self.api_key);
+        // Cached hashes map to their torrent details; others are left out
+        let response: TorBoxResponse<HashMap<String, serde_json::Value>> =
+            get_json(&self.http, self.kind(), request).await?;
+
+        Ok(response
+            .into_data(self.kind())?
+            .unwrap_or_default()
+            .into_keys()
+            .map(|hash| hash.to_lowercase())
+            .collect())
+    }
+}
+
+/// Debrid-Link API client
+pub struct DebridLinkClient {
+    http: HttpClient,
+    api_key: String,
+    base_url: String,
+}
+
+impl DebridLinkClient {
+    pub fn new(api_key: String, http: HttpClient) -> Self {
+        Self {
+            http,
+            api_key,
+            base_url: DEBRIDLINK_URL.to_string(),
+        }
+    }
+
+    /// Use another API URL (e.g., a local mock server)
+    #[cfg(test)]
+    fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DebridLinkResponse<T> {
+    success: bool,
+    value: Option<T>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DebridLinkAccount {
+    pseudo: String,
+}
+
+impl<T> DebridLinkResponse<T> {
+    /// The response value, or the error Debrid-Link answered with
+    fn into_value(self, kind: DebridKind) -> Result<Option<T>, ApiError> {
+        match (self.success, self.error) {
+            (true, _) => Ok(self.value),
+            (false, Some(error)) if error == "badToken" => Err(ApiError::DebridAuth(kind.name())),
+            (false, error) => Err(ApiError::Debrid(
+                kind.name(),
+                error.unwrap_or_else(|| "Unexpected response".to_string()),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl DebridService for DebridLinkClient {
+    fn kind(&self) -> DebridKind {
+        DebridKind::DebridLink
+    }
+
+    fn api_key(&self) -> String {
+        self.api_key.clone()
+    }
+
+    async fn username(&self) -> Result<String, ApiError> {
+        let request = self
+            .http
+            .get(&format!("{}/account/infos", self.base_url))
+            .bearer_auth(&self.api_key);
+        let response: DebridLinkResponse<DebridLinkAccount> =
+            get_json(&self.http, self.kind(), request).await?;
+
+        response
+            .into_value(self.kind())?
+            .map(|account| account.pseudo)
+            .ok_or_else(|| ApiError::Debrid(self.kind().name(), "No account data".to_string()))
+    }
+
+    async fn cached_hashes(&self, hashes: &[String]) -> Result<HashSet<String>, ApiError> {
+        let request = self
+            .http
+            .get(&format!("{}/seedbox/cached", self.base_url))
+            .query(&[("url", hashes.join(","))])
+            .bearer_auth(&self.api_key);
+        // Cached hashes map to their torrent details; others are left out
+        let response: DebridLinkResponse<HashMap<String, serde_json::Value>> =
+            get_json(&self.http, self.kind(), request).await?;
+
+        Ok(response
+            .into_value(self.kind())?
+            .unwrap_or_default()
+            .into_keys()
+            .map(|hash| hash.to_lowercase())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock_server::MockServer;
+
+    /// AllDebrid, Premiumize, TorBox and Debrid-Link clients, each under its key's path
+    /// on the mock server
+    fn mock_services(server: &MockServer) -> Vec<Box<dyn DebridService>> {
+        let http = HttpClient::default;
+        let url = |kind: DebridKind| format!("{}/{}", server.url, kind.key());
+        vec![
+            Box::new(
+                AllDebridClient::new("key".to_string(), http())
+                    .with_base_url(&url(DebridKind::AllDebrid)),
+            ),
+            Box::new(
+                PremiumizeClient::new("key".to_string(), http())
+                    .with_base_url(&url(DebridKind::Premiumize)),
+            ),
+            Box::new(
+                TorBoxClient::new("key".to_string(), http())
+                    .with_base_url(&url(DebridKind::TorBox)),
+            ),
+            Box::new(
+                DebridLinkClient::new("key".to_string(), http())
+                    .with_base_url(&url(DebridKind::DebridLink)),
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_usernames() {
+        let server = MockServer::start(vec![
+            (
+                "GET /alldebrid/user?agent=miru",
+                200,
+                r#"{"status":"success","data":{"user":{"username":"ad_user","isPremium":true}}}"#,
+            ),
+            (
+                "GET /premiumize/account/info?apikey=key",
+                200,
+                r#"{"status":"success","customer_id":1234,"premium_until":1900000000}"#,
+            ),
+            (
+                "GET /torbox/user/me",
+                200,
+                r#"{"success":true,"detail":"User found.","data":{"email":"tb@example.com"}}"#,
+            ),
+            (
+                "GET /debridlink/account/infos",
+                200,
+                r#"{"success":true,"value":{"pseudo":"dl_user","premiumLeft":86400}}"#,
+            ),
+        ])
+        .await;
+        let services = mock_services(&server);
+        let mut names = Vec::new();
+        for service in &services {
+            names.push(service.username().await.unwrap());
+        }
+        assert_eq!(names, vec!["ad_user", "1234", "tb@example.com", "dl_user"]);
+    }
+
+    #[tokio::test]
+    async fn test_bad_keys() {
+        let server = MockServer::start(vec![
+            (
+                "GET /alldebrid/user?agent=miru",
+                200,
+                r#"{"status":"error","error":{"code":"AUTH_BAD_APIKEY","message":"The auth apikey is invalid"}}"#,
+            ),
+            (
+                "GET /premiumize/account/info?apikey=key",
+                200,
+                r#"{"status":"error","message":"Not logged in."}"#,
+            ),
+            ("GET /torbox/user/me", 403, r#"{"success":false,"error":"BAD_TOKEN"}"#),
+            (
+                "GET /debridlink/account/infos",
+                200,
+                r#"{"success":false,"error":"badToken"}"#,
+            ),
+        ])
+        .await;
+        let services = mock_services(&server);
+        for service in &services {
+            let err = service.username().await.unwrap_err();
+            assert!(
+                matches!(err, ApiError::DebridAuth(name) if name == service.kind().name()),
+                "{}: {}",
+                service.kind().name(),
+                err
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_hashes() {
+        let server = MockServer::start(vec![
+            (
+                "GET /premiumize/cache/check?apikey=key&items%5B%5D=aaa&items%5B%5D=bbb",
+                200,
+                r#"{"status":"success","response":[false,true],"transcoded":[false,false]}"#,
+            ),
+            (
+                "GET /torbox/torrents/checkcached?hash=aaa%2Cbbb&format=object",
+                200,
+                r#"{"success":true,"detail":"Found cached torrent(s).","data":{"aaa":{"name":"A","size":1000}}}"#,
+            ),
+            (
+                "GET /debridlink/seedbox/cached?url=aaa%2Cbbb",
+                200,
+                r#"{"success":true,"value":{"bbb":{"name":"B","files":[]}}}"#,
+            ),
+        ])
+        .await;
+        let services: Vec<_> = mock_services(&server)
+            .into_iter()
+            .filter(|service| service.kind().checks_cache_by_hash())
+            .collect();
+        let hashes = vec!["aaa".to_string(), "bbb".to_string()];
+        let mut cached = Vec::new();
+        for service in &services {
+            let mut found: Vec<String> = service
+                .cached_hashes(&hashes)
+                .await
+                .unwrap()
+                .into_iter()
+                .collect();
+            found.sort();
+            cached.push(found);
+        }
+        assert_eq!(cached, vec![vec!["bbb"], vec!["aaa"], vec!["bbb"]]);
+    }
+
+    /// Service with a fixed set of cached hashes, recording what it was asked about
+    struct FakeService {
+        kind: DebridKind,
+        cached: Vec<&'static str>,
+        asked: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl FakeService {
+        fn new(kind: DebridKind, cached: Vec<&'static str>) -> Arc<Self> {
+            Arc::new(Self {
+                kind,
+                cached,
+                asked: Default::default(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl DebridService for FakeService {
+        fn kind(&self) -> DebridKind {
+            self.kind
+        }
+
+        fn api_key(&self) -> String {
+            format!("{}_key", self.kind.key())
+        }
+
+        async fn username(&self) -> Result<String, ApiError> {
+            Ok("fake".to_string())
+        }
+
+        async fn cached_hashes(&self, hashes: &[String]) -> Result<HashSet<String>, ApiError> {
+            self.asked.lock().unwrap().extend(hashes.iter().cloned());
+            Ok(hashes
+                .iter()
+                .filter(|hash| self.cached.contains(&hash.as_str()))
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_availability() {
+        let stream = |info_hash: &str, debrid: Option<DebridKind>| Stream {
+            info_hash: Some(info_hash.to_string()),
+            file_idx: Some(2),
+            debrid,
+            ..Default::default()
+        };
+        let mut streams = vec![
+            stream("AAA", None),
+            stream("bbb", None),
+            stream("ccc", None),
+            // Already resolved through Torrentio
+            stream("ddd", Some(DebridKind::RealDebrid)),
+        ];
+        let real_debrid = FakeService::new(DebridKind::RealDebrid, vec!["aaa", "bbb", "ccc"]);
+        let torbox = FakeService::new(DebridKind::TorBox, vec!["bbb"]);
+        let premiumize = FakeService::new(DebridKind::Premiumize, vec!["aaa", "bbb"]);
+        let services: Vec<Arc<dyn DebridService>> =
+            vec![real_debrid.clone(), torbox.clone(), premiumize.clone()];
+
+        check_availability(&services, &mut streams).await;
+
+        // Real-Debrid can't be asked; each other service is only asked about what the
+        // ones before it don't have
+        assert!(real_debrid.asked.lock().unwrap().is_empty());
+        assert_eq!(*torbox.asked.lock().unwrap(), vec!["aaa", "bbb", "ccc"]);
+        assert_eq!(*premiumize.asked.lock().unwrap(), vec!["aaa", "ccc"]);
+
+        assert_eq!(streams[0].debrid, Some(DebridKind::Premiumize));
+        assert!(streams[0].is_cached);
+        assert_eq!(
+            streams[0].url.as_deref(),
+            Some("https://torrentio.strem.fun/resolve/premiumize/premiumize_key/aaa/null/2")
+        );
+        assert_eq!(streams[1].debrid, Some(DebridKind::TorBox));
+        assert!(streams[1]
+            .url
+            .as_deref()
+            .unwrap()
+            .contains("/torbox/torbox_key/bbb/"));
+        // Uncached: still a P2P stream
+        assert_eq!(streams[2].debrid, None);
+        assert!(!streams[2].is_cached);
+        assert!(streams[2].url.is_none());
+        assert!(streams[3].url.is_none());
+    }
+}
@@ -0,0 +1,238 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::api::{HttpClient, Media, MediaType, TmdbClient};
+use crate::error::ApiError;
+
+lazy_static! {
+    // Bare IMDb ID like "tt0903747"
+    static ref IMDB_ID_RE: Regex = Regex::new(r"^tt\d{5,}$").unwrap();
+    // Prefixed TMDB ID like "tmdb:1396", "tmdb:tv:1396" or "tmdb:movie/603"
+    static ref TMDB_ID_RE: Regex = Regex::new(r"^(?i)tmdb:(?:(movie|tv)[:/])?(\d+)$").unwrap();
+    // IMDb title URL (also matched inside HTML pages)
+    static ref IMDB_URL_RE: Regex = Regex::new(r"imdb\.com/title/(tt\d+)").unwrap();
+    // TMDB movie/TV URL (also matched inside HTML pages)
+    static ref TMDB_URL_RE: Regex = Regex::new(r"themoviedb\.org/(movie|tv)/(\d+)").unwrap();
+    // Sites whose pages link to IMDb/TMDB but whose own IDs TMDB doesn't know
+    static ref PAGE_URL_RE: Regex = Regex::new(r"^https?://(?:www\.)?(trakt\.tv/(?:shows|movies)/|letterboxd\.com/film/|boxd\.it/)\S+$").unwrap();
+}
+
+/// A title reference typed or pasted in place of a search query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalId {
+    /// IMDb ID (e.g., "tt0903747")
+    Imdb(String),
+    /// TMDB ID, with the media type when known (TMDB movie and TV IDs overlap)
+    Tmdb {
+        media_type: Option<MediaType>,
+        id: i32,
+    },
+    /// Trakt or Letterboxd page that links to IMDb/TMDB
+    Page(String),
+}
+
+impl ExternalId {
+    /// Recognise an IMDb ID, TMDB ID, or IMDb/TMDB/Trakt/Letterboxd URL
+    ///
+    /// Returns None for anything else, which should be treated as a text search.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+
+        if IMDB_ID_RE.is_match(input) {
+            return Some(Self::Imdb(input.to_string()));
+        }
+
+        if let Some(caps) = TMDB_ID_RE.captures(input) {
+            return Some(Self::Tmdb {
+                media_type: caps.get(1).map(|m| media_type_from_path(m.as_str())),
+                id: caps[2].parse().ok()?,
+            });
+        }
+
+        if !input.starts_with("http://") && !input.starts_with("https://") {
+            return None;
+        }
+
+        if let Some(caps) = IMDB_URL_RE.captures(input) {
+            return Some(Self::Imdb(caps[1].to_string()));
+        }
+
+        if let Some(caps) = TMDB_URL_RE.captures(input) {
+            return Some(Self::Tmdb {
+                media_type: Some(media_type_from_path(&caps[1])),
+                id: caps[2].parse().ok()?,
+            });
+        }
+
+        if PAGE_URL_RE.is_match(input) {
+            return Some(Self::Page(input.to_string()));
+        }
+
+        None
+    }
+
+    /// Resolve to a TMDB title
+    pub async fn resolve(&self, tmdb: &TmdbClient, http: &HttpClient) -> Result<Media, ApiError> {
+        match self {
+            Self::Imdb(imdb_id) => tmdb
+                .find_by_imdb_id(imdb_id)
+                .await?
+                .ok_or_else(|| ApiError::NotFound(imdb_id.clone())),
+            Self::Tmdb {
+                media_type: Some(MediaType::Movie),
+                id,
+            } => tmdb.get_movie_details(*id).await,
+            Self::Tmdb {
+                media_type: Some(MediaType::TvShow),
+                id,
+            } => tmdb.get_tv_show_details(*id).await,
+            Self::Tmdb {
+                media_type: None,
+                id,
+            } => {
+                // Without a type hint, prefer the movie with this ID
+                match tmdb.get_movie_details(*id).await {
+                    Ok(media) => Ok(media),
+                    Err(_) => tmdb.get_tv_show_details(*id).await,
+                }
+            }
+            Self::Page(url) => {
+                let response = http.send(http.get(url)).await?;
+                if !response.status().is_success() {
+                    return Err(ApiError::NotFound(url.clone()));
+                }
+                let html = response.text().await?;
+
+                match extract_from_html(&html) {
+                    Some(id @ (Self::Imdb(_) | Self::Tmdb { .. })) => {
+                        Box::pin(id.resolve(tmdb, http)).await
+                    }
+                    _ => Err(ApiError::NotFound(url.clone())),
+                }
+            }
+        }
+    }
+}
+
+/// Find an IMDb or TMDB reference in a Trakt/Letterboxd page
+///
+/// TMDB links are preferred since they identify the media type.
+fn extract_from_html(html: &str) -> Option<ExternalId> {
+    if let Some(caps) = TMDB_URL_RE.captures(html) {
+        return Some(ExternalId::Tmdb {
+            media_type: Some(media_type_from_path(&caps[1])),
+            id: caps[2].parse().ok()?,
+        });
+    }
+
+    IMDB_URL_RE
+        .captures(html)
+        .map(|caps| ExternalId::Imdb(caps[1].to_string()))
+}
+
+fn media_type_from_path(path: &str) -> MediaType {
+    if path.eq_ignore_ascii_case("tv") {
+        MediaType::TvShow
+    } else {
+        MediaType::Movie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_imdb() {
+        assert_eq!(
+            ExternalId::parse("tt0903747"),
+            Some(ExternalId::Imdb("tt0903747".to_string()))
+        );
+        assert_eq!(
+            ExternalId::parse("  https://www.imdb.com/title/tt0903747/?ref_=nv_sr_srsg_0 "),
+            Some(ExternalId::Imdb("tt0903747".to_string()))
+        );
+        assert_eq!(
+            ExternalId::parse("https://m.imdb.com/title/tt0133093/"),
+            Some(ExternalId::Imdb("tt0133093".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_tmdb() {
+        assert_eq!(
+            ExternalId::parse("https://www.themoviedb.org/tv/1396"),
+            Some(ExternalId::Tmdb {
+                media_type: Some(MediaType::TvShow),
+                id: 1396
+            })
+        );
+        assert_eq!(
+            ExternalId::parse("https://www.themoviedb.org/movie/603-the-matrix"),
+            Some(ExternalId::Tmdb {
+                media_type: Some(MediaType::Movie),
+                id: 603
+            })
+        );
+        assert_eq!(
+            ExternalId::parse("tmdb:tv:1396"),
+            Some(ExternalId::Tmdb {
+                media_type: Some(MediaType::TvShow),
+                id: 1396
+            })
+        );
+        assert_eq!(
+            ExternalId::parse("tmdb:603"),
+            Some(ExternalId::Tmdb {
+                media_type: None,
+                id: 603
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_pages() {
+        for url in [
+            "https://trakt.tv/shows/breaking-bad",
+            "https://trakt.tv/movies/the-matrix-1999",
+            "https://letterboxd.com/film/the-matrix/",
+            "https://boxd.it/2a1m",
+        ] {
+            assert_eq!(
+                ExternalId::parse(url),
+                Some(ExternalId::Page(url.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_plain_queries() {
+        // Titles that look like numbers or mention sites are still text searches
+        assert_eq!(ExternalId::parse("1917"), None);
+        assert_eq!(ExternalId::parse("breaking bad"), None);
+        assert_eq!(ExternalId::parse("tt"), None);
+        assert_eq!(ExternalId::parse("imdb.com/title/tt0903747"), None);
+        assert_eq!(ExternalId::parse("https://example.com/tv/1396"), None);
+    }
+
+    #[test]
+    fn test_extract_from_html() {
+        let letterboxd = r#"<a href="http://www.imdb.com/title/tt0133093/maindetails" data-track-action="IMDb">IMDb</a>
+            <a href="https://www.themoviedb.org/movie/603/" data-track-action="TMDb">TMDb</a>"#;
+        assert_eq!(
+            extract_from_html(letterboxd),
+            Some(ExternalId::Tmdb {
+                media_type: Some(MediaType::Movie),
+                id: 603
+            })
+        );
+
+        let imdb_only = r#"<a href="https://www.imdb.com/title/tt0903747">IMDB</a>"#;
+        assert_eq!(
+            extract_from_html(imdb_only),
+            Some(ExternalId::Imdb("tt0903747".to_string()))
+        );
+
+        assert_eq!(extract_from_html("<html></html>"), None);
+    }
+}
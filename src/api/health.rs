@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use reqwest::header::{CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use reqwest::StatusCode;
+
+use crate::api::HttpClient;
+use crate::error::ApiError;
+
+/// Longest a debrid link probe may take, in total
+const URL_PROBE_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Smallest plausible video file; debrid services answer dead or still downloading
+/// links with short placeholder clips or error pages
+const MIN_VIDEO_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Result of a pre-flight check of a source
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamHealth {
+    /// The debrid link serves a video file (size in bytes, if reported)
+    Reachable { size: Option<u64> },
+    /// The torrent's metadata arrived from the swarm
+    Swarm { peers: usize },
+    /// The source would fail to play
+    Dead(String),
+    /// The check was inconclusive (e.g. it timed out); the source may still play
+    Unknown(String),
+}
+
+impl StreamHealth {
+    /// Whether the source looks playable
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Self::Reachable { .. } | Self::Swarm { .. })
+    }
+
+    /// Short label for the sources list
+    pub fn label(&self) -> String {
+        match self {
+            Self::Reachable { .. } => "link ok".to_string(),
+            Self::Swarm { peers } => format!("{} peers", peers),
+            Self::Dead(reason) | Self::Unknown(reason) => reason.clone(),
+        }
+    }
+}
+
+/// Probe a debrid link with a single ranged GET of its first byte
+pub async fn probe_url(http: &HttpClient, url: &str) -> StreamHealth {
+    let request = http.get(url).header(RANGE, "bytes=0-0");
+
+    // Bound the whole probe, including the wait for the host's rate limit
+    let Ok(result) = tokio::time::timeout(URL_PROBE_TIMEOUT, http.send_once(request)).await else {
+        return StreamHealth::Unknown("timeout".to_string());
+    };
+
+    match result {
+        Ok(response) => {
+            let header = |name| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            };
+            check_response(
+                response.status(),
+                header(CONTENT_TYPE),
+                header(CONTENT_RANGE),
+                header(CONTENT_LENGTH),
+            )
+        }
+        Err(ApiError::Network(e)) if e.is_timeout() => StreamHealth::Unknown("timeout".to_string()),
+        Err(_) => StreamHealth::Unknown("unreachable".to_string()),
+    }
+}
+
+/// Check a probe response's status, content type and length
+fn check_response(
+    status: StatusCode,
+    content_type: Option<&str>,
+    content_range: Option<&str>,
+    content_length: Option<&str>,
+) -> StreamHealth {
+    // A 4xx means the link is gone; a 5xx may be a passing server problem
+    if status.is_client_error() {
+        return StreamHealth::Dead(status.as_u16().to_string());
+    }
+    if !status.is_success() {
+        return StreamHealth::Unknown(status.as_u16().to_string());
+    }
+
+    let content_type = content_type.unwrap_or_default().to_lowercase();
+    let is_video = content_type.is_empty()
+        || content_type.starts_with("video/")
+        || content_type.starts_with("application/octet-stream")
+        || content_type.contains("matroska");
+    if !is_video {
+        return StreamHealth::Dead("not a video".to_string());
+    }
+
+    // A 206 reports the full size in Content-Range ("bytes 0-0/1234"), a 200 (range
+    // ignored) in Content-Length
+    let size = match status {
+        StatusCode::PARTIAL_CONTENT => content_range
+            .and_then(|range| range.rsplit('/').next())
+            .and_then(|total| total.trim().parse::<u64>().ok()),
+        _ => content_length.and_then(|length| length.trim().parse::<u64>().ok()),
+    };
+    match size {
+        Some(size) if size < MIN_VIDEO_BYTES => StreamHealth::Dead("placeholder".to_string()),
+        size => StreamHealth::Reachable { size },
+    }
+}
+
+/// Health of a torrent from the peers seen while fetching its metadata
+pub fn swarm_health(peers: usize) -> StreamHealth {
+    if peers == 0 {
+        StreamHealth::Dead("no peers".to_string())
+    } else {
+        StreamHealth::Swarm { peers }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::mock_server::MockServer;
+
+    #[test]
+    fn test_check_response() {
+        let ok = check_response(
+            StatusCode::PARTIAL_CONTENT,
+            Some("video/x-matroska"),
+            Some("bytes 0-0/2147483648"),
+            Some("1"),
+        );
+        assert_eq!(
+            ok,
+            StreamHealth::Reachable {
+                size: Some(2147483648)
+            }
+        );
+        assert!(ok.is_healthy());
+
+        // Range ignored, unknown size
+        assert_eq!(
+            check_response(StatusCode::OK, Some("application/octet-stream"), None, None),
+            StreamHealth::Reachable { size: None }
+        );
+
+        let not_found = check_response(StatusCode::NOT_FOUND, None, None, None);
+        assert_eq!(not_found, StreamHealth::Dead("404".to_string()));
+        assert!(!not_found.is_healthy());
+
+        let unavailable = check_response(StatusCode::SERVICE_UNAVAILABLE, None, None, None);
+        assert_eq!(unavailable, StreamHealth::Unknown("503".to_string()));
+        assert!(!unavailable.is_healthy());
+
+        assert_eq!(
+            check_response(StatusCode::OK, Some("text/html"), None, Some("512")),
+            StreamHealth::Dead("not a video".to_string())
+        );
+        assert_eq!(
+            check_response(
+                StatusCode::PARTIAL_CONTENT,
+                Some("video/mp4"),
+                Some("bytes 0-0/1048576"),
+                None
+            ),
+            StreamHealth::Dead("placeholder".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_probe_url_does_not_retry() {
+        let server = MockServer::start(vec![("GET /link", 503, "")]).await;
+        let health = probe_url(&HttpClient::default(), &format!("{}/link", server.url)).await;
+
+        assert_eq!(health, StreamHealth::Unknown("503".to_string()));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_swarm_health() {
+        assert_eq!(swarm_health(0), StreamHealth::Dead("no peers".to_string()));
+        assert_eq!(swarm_health(12).label(), "12 peers");
+    }
+}
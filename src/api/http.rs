@@ -0,0 +1,309 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Client, Method, Proxy, RequestBuilder, Response, StatusCode};
+
+use crate::config::HttpConfig;
+use crate::error::ApiError;
+
+/// Longest `Retry-After` we are willing to wait before retrying
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Shared HTTP client with timeouts, retries and per-host rate limiting
+///
+/// Cheap to clone: clones share the connection pool and rate limiter.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+    max_retries: u32,
+    retry_base_delay: Duration,
+    limiter: Arc<RateLimiter>,
+}
+
+impl HttpClient {
+    /// Build a client from the `[http]` config section
+    ///
+    /// Fails if the proxy URL is invalid.
+    pub fn new(config: &HttpConfig) -> Result<Self, ApiError> {
+        let mut builder = Client::builder()
+            .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+            .read_timeout(Duration::from_secs(config.read_timeout_secs))
+            .user_agent(config.user_agent.clone());
+
+        if let Some(proxy) = config.proxy.as_deref().filter(|p| !p.is_empty()) {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+
+        Ok(Self {
+            client: builder.build()?,
+            max_retries: config.max_retries,
+            retry_base_delay: Duration::from_millis(config.retry_base_delay_ms),
+            limiter: Arc::new(RateLimiter::new(
+                config.requests_per_second,
+                config.rate_limits.clone(),
+            )),
+        })
+    }
+
+    /// Start a GET request
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    /// Start a POST request
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// Send a request, waiting for the host's rate limit
+    ///
+    /// Idempotent requests (GET, HEAD, PUT, DELETE) are retried with jittered exponential
+    /// backoff on connection errors, timeouts, 429 and 5xx responses. A `Retry-After`
+    /// header is honoured when present. Once retries run out, the last response is
+    /// returned so callers can report the status.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, ApiError> {
+        let mut request = request.build()?;
+        let method = request.method().clone();
+        let host = request.url().host_str().unwrap_or_default().to_string();
+        let idempotent = matches!(
+            method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE
+        );
+
+        let mut attempt = 0;
+        loop {
+            // Keep a copy for the next attempt (requests with streaming bodies can't be copied)
+            let next = if idempotent && attempt < self.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            self.limiter.wait(&host).await;
+            let result = self.client.execute(request).await;
+
+            let delay = match (&next, &result) {
+                (None, _) => None,
+                (_, Ok(response)) if is_retryable_status(response.status()) => {
+                    match retry_after(response.headers()) {
+                        Some(wait) if wait > MAX_RETRY_AFTER => None,
+                        Some(wait) => Some(wait),
+                        None => Some(backoff_delay(self.retry_base_delay, attempt)),
+                    }
+                }
+                (_, Err(e)) if is_retryable_error(e) => {
+                    Some(backoff_delay(self.retry_base_delay, attempt))
+                }
+                _ => None,
+            };
+
+            match (delay, next) {
+                (Some(delay), Some(next)) => {
+                    tracing::debug!(
+                        "Retrying {} {} in {:?} (attempt {})",
+                        method,
+                        host,
+                        delay,
+                        attempt + 1
+                    );
+                    tokio::time::sleep(delay).await;
+                    request = next;
+                    attempt += 1;
+                }
+                _ => return Ok(result?),
+            }
+        }
+    }
+
+    /// Send a request once, waiting for the host's rate limit but never retrying
+    ///
+    /// For quick checks that would rather give up than keep the user waiting.
+    pub async fn send_once(&self, request: RequestBuilder) -> Result<Response, ApiError> {
+        let request = request.build()?;
+        let host = request.url().host_str().unwrap_or_default().to_string();
+        self.limiter.wait(&host).await;
+        Ok(self.client.execute(request).await?)
+    }
+}
+
+impl Default for HttpClient {
+    /// Client with default settings (no proxy)
+    fn default() -> Self {
+        Self::new(&HttpConfig::default()).expect("default HTTP client settings are valid")
+    }
+}
+
+/// Per-host request spacing
+struct RateLimiter {
+    /// Default requests per second for each host (0 = unlimited)
+    default_rate: f64,
+    /// Host-specific requests per second
+    host_rates: HashMap<String, f64>,
+    /// Earliest time the next request to each host may start
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    fn new(default_rate: f64, host_rates: HashMap<String, f64>) -> Self {
+        Self {
+            default_rate,
+            host_rates,
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Minimum interval between requests to a host
+    fn interval(&self, host: &str) -> Option<Duration> {
+        let rate = self
+            .host_rates
+            .iter()
+            .find(|(h, _)| host == h.as_str() || host.ends_with(&format!(".{}", h)))
+            .map(|(_, rate)| *rate)
+            .unwrap_or(self.default_rate);
+
+        (rate > 0.0).then(|| Duration::from_secs_f64(1.0 / rate))
+    }
+
+    /// Reserve the next slot for a host, returning how long to wait for it
+    fn reserve(&self, host: &str, now: Instant) -> Duration {
+        let Some(interval) = self.interval(host) else {
+            return Duration::ZERO;
+        };
+
+        let mut slots = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = slots
+            .get(host)
+            .copied()
+            .filter(|slot| *slot > now)
+            .unwrap_or(now);
+        slots.insert(host.to_string(), slot + interval);
+        slot - now
+    }
+
+    async fn wait(&self, host: &str) {
+        let wait = self.reserve(host, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Statuses worth retrying: timeouts, rate limiting and transient server errors
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Network failures worth retrying (the request may not have reached the server)
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect() || error.is_request()
+}
+
+/// Parse a `Retry-After` header (delay in seconds or an HTTP date)
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.signed_duration_since(chrono::Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Exponential backoff with jitter: a random delay between half and all of `base * 2^attempt`
+fn backoff_delay(base: Duration, attempt: u32) -> Duration {
+    let max = base.saturating_mul(2u32.saturating_pow(attempt));
+    let jitter = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+    max.mul_f64(0.5 + jitter * 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable_status(StatusCode::OK));
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("5"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(5)));
+
+        // Dates in the past mean "retry now"
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_backoff_delay_bounds() {
+        let base = Duration::from_millis(100);
+        for attempt in 0..4 {
+            let max = base * 2u32.pow(attempt);
+            let delay = backoff_delay(base, attempt);
+            assert!(delay >= max / 2 && delay <= max, "{:?}", delay);
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter_spacing() {
+        let limiter = RateLimiter::new(
+            10.0,
+            HashMap::from([
+                ("strem.fun".to_string(), 2.0),
+                ("example.com".to_string(), 0.0),
+            ]),
+        );
+        let now = Instant::now();
+
+        // Requests to the same host are spaced by the interval
+        assert_eq!(limiter.reserve("api.themoviedb.org", now), Duration::ZERO);
+        assert_eq!(
+            limiter.reserve("api.themoviedb.org", now),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            limiter.reserve("api.themoviedb.org", now),
+            Duration::from_millis(200)
+        );
+
+        // Hosts are independent, and subdomains use their parent's limit
+        assert_eq!(limiter.reserve("torrentio.strem.fun", now), Duration::ZERO);
+        assert_eq!(
+            limiter.reserve("torrentio.strem.fun", now),
+            Duration::from_millis(500)
+        );
+
+        // A rate of 0 disables limiting
+        assert_eq!(limiter.reserve("example.com", now), Duration::ZERO);
+        assert_eq!(limiter.reserve("example.com", now), Duration::ZERO);
+    }
+}
@@ -0,0 +1,160 @@
+/// A language with its ISO 639 codes
+#[derive(Debug, PartialEq, Eq)]
+pub struct Language {
+    /// English name, as used in `Stream::languages` (e.g., "Japanese")
+    pub name: &'static str,
+    /// ISO 639-1 code first, then ISO 639-2 codes (e.g., "ja", "jpn")
+    pub codes: &'static [&'static str],
+}
+
+/// Languages that Torrentio marks with flags
+const LANGUAGES: &[Language] = &[
+    Language {
+        name: "English",
+        codes: &["en", "eng"],
+    },
+    Language {
+        name: "German",
+        codes: &["de", "ger", "deu"],
+    },
+    Language {
+        name: "French",
+        codes: &["fr", "fre", "fra"],
+    },
+    Language {
+        name: "Italian",
+        codes: &["it", "ita"],
+    },
+    Language {
+        name: "Spanish",
+        codes: &["es", "spa"],
+    },
+    Language {
+        name: "Japanese",
+        codes: &["ja", "jpn"],
+    },
+    Language {
+        name: "Korean",
+        codes: &["ko", "kor"],
+    },
+    Language {
+        name: "Chinese",
+        codes: &["zh", "chi", "zho"],
+    },
+    Language {
+        name: "Portuguese",
+        codes: &["pt", "por"],
+    },
+    Language {
+        name: "Russian",
+        codes: &["ru", "rus"],
+    },
+    Language {
+        name: "Dutch",
+        codes: &["nl", "dut", "nld"],
+    },
+    Language {
+        name: "Polish",
+        codes: &["pl", "pol"],
+    },
+    Language {
+        name: "Swedish",
+        codes: &["sv", "swe"],
+    },
+    Language {
+        name: "Norwegian",
+        codes: &["no", "nor"],
+    },
+    Language {
+        name: "Danish",
+        codes: &["da", "dan"],
+    },
+    Language {
+        name: "Finnish",
+        codes: &["fi", "fin"],
+    },
+    Language {
+        name: "Greek",
+        codes: &["el", "gre", "ell"],
+    },
+    Language {
+        name: "Turkish",
+        codes: &["tr", "tur"],
+    },
+    Language {
+        name: "Hindi",
+        codes: &["hi", "hin"],
+    },
+    Language {
+        name: "Thai",
+        codes: &["th", "tha"],
+    },
+    Language {
+        name: "Vietnamese",
+        codes: &["vi", "vie"],
+    },
+    Language {
+        name: "Indonesian",
+        codes: &["id", "ind"],
+    },
+];
+
+/// Look up a language by English name or ISO 639 code (case-insensitive)
+pub fn find_language(name_or_code: &str) -> Option<&'static Language> {
+    let key = name_or_code.trim();
+    LANGUAGES.iter().find(|language| {
+        language.name.eq_ignore_ascii_case(key)
+            || language
+                .codes
+                .iter()
+                .any(|code| code.eq_ignore_ascii_case(key))
+    })
+}
+
+/// Language name for a configured preference (e.g., "jpn" -> "Japanese").
+///
+/// Unknown languages are kept as written.
+pub fn language_name(name_or_code: &str) -> String {
+    find_language(name_or_code)
+        .map(|language| language.name.to_string())
+        .unwrap_or_else(|| name_or_code.trim().to_string())
+}
+
+/// Comma-separated ISO 639 codes for player track selection
+/// (e.g., ["Japanese", "en"] -> "ja,jpn,en,eng")
+pub fn language_codes(preferences: &[String]) -> String {
+    preferences
+        .iter()
+        .map(|preference| match find_language(preference) {
+            Some(language) => language.codes.join(","),
+            None => preference.trim().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_language() {
+        assert_eq!(find_language("jpn").unwrap().name, "Japanese");
+        assert_eq!(find_language("EN").unwrap().name, "English");
+        assert_eq!(find_language("german").unwrap().name, "German");
+        assert!(find_language("Klingon").is_none());
+    }
+
+    #[test]
+    fn test_language_name() {
+        assert_eq!(language_name("fra"), "French");
+        assert_eq!(language_name(" Klingon "), "Klingon");
+    }
+
+    #[test]
+    fn test_language_codes() {
+        let preferences = vec!["Japanese".to_string(), "en".to_string(), "tlh".to_string()];
+        assert_eq!(language_codes(&preferences), "ja,jpn,en,eng,tlh");
+        assert_eq!(language_codes(&[]), "");
+    }
+}
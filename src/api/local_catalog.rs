@@ -0,0 +1,258 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::api::{Episode, Media, MediaSource, MediaType, MetadataProvider, Season};
+use crate::error::ApiError;
+
+/// A title in a local catalog file
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogEntry {
+    /// Catalog-unique ID (e.g., "big-buck-bunny")
+    pub id: String,
+    pub media_type: CatalogMediaType,
+    pub title: String,
+    /// IMDb ID, needed to look up sources
+    #[serde(default)]
+    pub imdb_id: Option<String>,
+    #[serde(default)]
+    pub year: Option<i32>,
+    #[serde(default)]
+    pub overview: Option<String>,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    /// Seasons of a TV show
+    #[serde(default)]
+    pub seasons: Vec<CatalogSeason>,
+}
+
+/// Media type as written in the catalog file ("movie" or "tv")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogMediaType {
+    Movie,
+    Tv,
+}
+
+impl From<CatalogMediaType> for MediaType {
+    fn from(media_type: CatalogMediaType) -> Self {
+        match media_type {
+            CatalogMediaType::Movie => MediaType::Movie,
+            CatalogMediaType::Tv => MediaType::TvShow,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogSeason {
+    pub number: u32,
+    #[serde(default)]
+    pub episodes: Vec<CatalogEpisode>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogEpisode {
+    pub number: u32,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub air_date: Option<String>,
+    #[serde(default)]
+    pub overview: Option<String>,
+}
+
+/// Metadata provider backed by a local JSON file (an array of [`CatalogEntry`])
+///
+/// Useful for personal media that TMDB doesn't know about, and offline.
+#[derive(Debug, Clone, Default)]
+pub struct LocalCatalog {
+    entries: Vec<CatalogEntry>,
+}
+
+impl LocalCatalog {
+    /// Parse a catalog from its JSON representation
+    pub fn from_json(json: &str) -> Result<Self, ApiError> {
+        let entries: Vec<CatalogEntry> = serde_json::from_str(json)
+            .map_err(|e| ApiError::Metadata(format!("Invalid catalog file: {}", e)))?;
+        Ok(Self { entries })
+    }
+
+    /// Load a catalog from a JSON file
+    pub fn load_file(path: &Path) -> Result<Self, ApiError> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| ApiError::Metadata(format!("Failed to read {}: {}", path.display(), e)))?;
+        Self::from_json(&content)
+    }
+
+    fn entry(&self, source: &MediaSource) -> Result<&CatalogEntry, ApiError> {
+        self.entries
+            .iter()
+            .find(|e| matches!(source, MediaSource::Local { id } if *id == e.id))
+            .ok_or_else(|| ApiError::NotFound(source.key()))
+    }
+
+    fn to_media(entry: &CatalogEntry) -> Media {
+        let episodes: u32 = entry.seasons.iter().map(|s| s.episodes.len() as u32).sum();
+        Media {
+            media_type: entry.media_type.into(),
+            source: MediaSource::Local {
+                id: entry.id.clone(),
+            },
+            title: entry.title.clone(),
+            title_original: None,
+            imdb_id: entry.imdb_id.clone(),
+            year: entry.year,
+            score: None,
+            episodes: (episodes > 0).then_some(episodes as i32),
+            seasons: (!entry.seasons.is_empty()).then_some(entry.seasons.len() as i32),
+            cover_image: None,
+            episode_titles: Vec::new(),
+            description: entry.overview.clone(),
+            status: None,
+            format: None,
+            genres: entry.genres.clone(),
+            collection: None,
+        }
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for LocalCatalog {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<Media>, ApiError> {
+        let query = query.to_lowercase();
+        Ok(self
+            .entries
+            .iter()
+            .filter(|e| e.title.to_lowercase().contains(&query))
+            .map(Self::to_media)
+            .collect())
+    }
+
+    async fn details(
+        &self,
+        source: &MediaSource,
+        _media_type: MediaType,
+    ) -> Result<Media, ApiError> {
+        self.entry(source).map(Self::to_media)
+    }
+
+    async fn seasons(&self, media: &Media) -> Result<Vec<Season>, ApiError> {
+        let mut seasons: Vec<Season> = self
+            .entry(&media.source)?
+            .seasons
+            .iter()
+            .map(|s| Season {
+                number: s.number,
+                episode_count: s.episodes.len() as u32,
+            })
+            .collect();
+        // Same order as TMDB: regular seasons first, specials last
+        seasons.sort_by_key(|s| (s.is_specials(), s.number));
+        Ok(seasons)
+    }
+
+    async fn episodes(&self, media: &Media, season: u32) -> Result<Vec<Episode>, ApiError> {
+        let entry = self.entry(&media.source)?;
+        let season = entry
+            .seasons
+            .iter()
+            .find(|s| s.number == season)
+            .ok_or_else(|| ApiError::NotFound(format!("{} season {}", entry.title, season)))?;
+
+        Ok(season
+            .episodes
+            .iter()
+            .map(|ep| Episode {
+                number: ep.number,
+                title: ep
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| format!("Episode {}", ep.number)),
+                air_date: ep.air_date.clone(),
+                overview: ep.overview.clone(),
+                runtime: None,
+                vote_average: None,
+            })
+            .collect())
+    }
+
+    async fn imdb_id(&self, media: &Media) -> Result<String, ApiError> {
+        self.entry(&media.source)?
+            .imdb_id
+            .clone()
+            .ok_or(ApiError::MappingNotFound)
+    }
+}
+
+/// Default location of the local catalog (~/.config/miru/catalog.json)
+pub fn default_catalog_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("miru")
+        .join("catalog.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"[
+        {"id": "bbb", "media_type": "movie", "title": "Big Buck Bunny", "year": 2008, "imdb_id": "tt1254207"},
+        {"id": "home-videos", "media_type": "tv", "title": "Home Videos", "seasons": [
+            {"number": 0, "episodes": [{"number": 1, "title": "Bloopers"}]},
+            {"number": 1, "episodes": [{"number": 1, "title": "Pilot"}, {"number": 2}]}
+        ]}
+    ]"#;
+
+    #[tokio::test]
+    async fn test_search_and_details() {
+        let catalog = LocalCatalog::from_json(CATALOG).unwrap();
+        let provider: &dyn MetadataProvider = &catalog;
+
+        let results = provider.search("bunny").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].media_type, MediaType::Movie);
+        assert_eq!(results[0].source.key(), "local:bbb");
+        assert_eq!(provider.imdb_id(&results[0]).await.unwrap(), "tt1254207");
+
+        let show = provider
+            .details(
+                &MediaSource::Local {
+                    id: "home-videos".to_string(),
+                },
+                MediaType::TvShow,
+            )
+            .await
+            .unwrap();
+        assert_eq!(show.seasons, Some(2));
+        assert!(matches!(
+            provider.imdb_id(&show).await,
+            Err(ApiError::MappingNotFound)
+        ));
+
+        let missing = MediaSource::Local {
+            id: "nope".to_string(),
+        };
+        assert!(provider.details(&missing, MediaType::Movie).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_seasons_and_episodes() {
+        let catalog = LocalCatalog::from_json(CATALOG).unwrap();
+        let show = catalog.search("home").await.unwrap().remove(0);
+
+        let seasons = catalog.seasons(&show).await.unwrap();
+        let numbers: Vec<u32> = seasons.iter().map(|s| s.number).collect();
+        assert_eq!(numbers, vec![1, 0]);
+
+        let episodes = catalog.episodes(&show, 1).await.unwrap();
+        assert_eq!(episodes[0].title, "Pilot");
+        assert_eq!(episodes[1].title, "Episode 2");
+        assert!(catalog.episodes(&show, 5).await.is_err());
+    }
+}
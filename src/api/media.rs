@@ -17,10 +17,52 @@ impl MediaType {
     }
 }
 
-/// Source of the media data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Source of the media data: the metadata provider and the ID it knows the media by
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MediaSource {
-    Tmdb { id: i32 },
+    Tmdb {
+        id: i32,
+    },
+    /// Entry in a local JSON catalog
+    Local {
+        id: String,
+    },
+}
+
+impl MediaSource {
+    /// Name of the metadata provider (the prefix of [`MediaSource::key`])
+    pub fn provider(&self) -> &'static str {
+        match self {
+            MediaSource::Tmdb { .. } => "tmdb",
+            MediaSource::Local { .. } => "local",
+        }
+    }
+
+    /// Provider-qualified ID (e.g., "tmdb:1396"), used as the watch history key
+    pub fn key(&self) -> String {
+        match self {
+            MediaSource::Tmdb { id } => format!("tmdb:{}", id),
+            MediaSource::Local { id } => format!("local:{}", id),
+        }
+    }
+
+    /// Parse a provider-qualified ID produced by [`MediaSource::key`]
+    pub fn from_key(key: &str) -> Option<Self> {
+        let (provider, id) = key.split_once(':')?;
+        match provider {
+            "tmdb" => id.parse().ok().map(|id| MediaSource::Tmdb { id }),
+            "local" if !id.is_empty() => Some(MediaSource::Local { id: id.to_string() }),
+            _ => None,
+        }
+    }
+
+    /// TMDB ID, for TMDB-only features (collections, calendar, anime mapping)
+    pub fn tmdb_id(&self) -> Option<i32> {
+        match self {
+            MediaSource::Tmdb { id } => Some(*id),
+            MediaSource::Local { .. } => None,
+        }
+    }
 }
 
 /// Unified media structure for all content types
@@ -56,6 +98,37 @@ pub struct Media {
     pub format: Option<String>,
     /// Genres
     pub genres: Vec<String>,
+    /// Collection (franchise) this movie belongs to, if known
+    #[serde(default)]
+    pub collection: Option<CollectionRef>,
+}
+
+/// Reference to a movie collection (e.g., "The Matrix Collection")
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionRef {
+    pub id: i32,
+    pub name: String,
+}
+
+/// A movie collection with its parts in release order
+#[derive(Debug, Clone)]
+pub struct Collection {
+    pub name: String,
+    pub overview: Option<String>,
+    /// Movies in the collection, in release order (unreleased parts last)
+    pub parts: Vec<Media>,
+}
+
+impl Collection {
+    /// Index of the first part not in the set of watched movies
+    pub fn next_unwatched(
+        &self,
+        watched: &std::collections::HashSet<MediaSource>,
+    ) -> Option<usize> {
+        self.parts
+            .iter()
+            .position(|part| part.is_released() && !watched.contains(&part.source))
+    }
 }
 
 impl Media {
@@ -64,11 +137,26 @@ impl Media {
         &self.title
     }
 
-    /// Get the TMDB ID
-    pub fn tmdb_id(&self) -> i32 {
-        match &self.source {
-            MediaSource::Tmdb { id } => *id,
-        }
+    /// Whether the title is out: false for movies that are upcoming, undated or still in
+    /// production
+    pub fn is_released(&self) -> bool {
+        self.media_type != MediaType::Movie
+            || self
+                .status
+                .as_deref()
+                .is_none_or(|status| status == "Released")
+    }
+
+    /// Get the TMDB ID (None for media from other providers)
+    pub fn tmdb_id(&self) -> Option<i32> {
+        self.source.tmdb_id()
+    }
+
+    /// Whether this looks like anime (TMDB has no anime type, so use the Animation genre)
+    pub fn is_anime(&self) -> bool {
+        self.genres
+            .iter()
+            .any(|g| g.eq_ignore_ascii_case("animation"))
     }
 
     /// Get episode list (generated from episode count)
@@ -118,6 +206,22 @@ pub struct Episode {
     pub vote_average: Option<f32>,
 }
 
+/// An episode referenced by a show's airing schedule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AiringEpisode {
+    pub season: u32,
+    pub episode: u32,
+    /// Air date (e.g., "2024-01-15")
+    pub air_date: Option<String>,
+}
+
+/// Airing schedule of a TV show: the latest aired episode and the next scheduled one
+#[derive(Debug, Clone, Default)]
+pub struct TvAiring {
+    pub last: Option<AiringEpisode>,
+    pub next: Option<AiringEpisode>,
+}
+
 /// Season data structure (for TV shows)
 #[derive(Debug, Clone)]
 pub struct Season {
@@ -126,6 +230,20 @@ pub struct Season {
 }
 
 impl Season {
+    /// Whether this is TMDB's "Specials" season (season 0: OVAs, specials, extras)
+    pub fn is_specials(&self) -> bool {
+        self.number == 0
+    }
+
+    /// Display label for the season (e.g., "Season 2" or "Specials")
+    pub fn label(&self) -> String {
+        if self.is_specials() {
+            "Specials".to_string()
+        } else {
+            format!("Season {}", self.number)
+        }
+    }
+
     /// Get episode list for this season (basic, without metadata)
     pub fn get_episodes(&self) -> Vec<Episode> {
         (1..=self.episode_count)
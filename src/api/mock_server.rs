@@ -0,0 +1,105 @@
+//! Local HTTP server for testing API clients
+
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Minimal HTTP server standing in for a debrid service API
+///
+/// Each route ("METHOD /path") answers with its responses in turn, repeating the
+/// last one. Requests are recorded as "METHOD /path body".
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    pub async fn start(routes: Vec<(&'static str, u16, &'static str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        tokio::spawn(async move {
+            let mut served = vec![0; routes.len()];
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let (method, path, body) = read_request(&mut socket).await;
+                let route = format!("{} {}", method, path);
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", route, body).trim_end().to_string());
+
+                let matching: Vec<usize> = (0..routes.len())
+                    .filter(|&i| routes[i].0 == route)
+                    .collect();
+                let index = matching
+                    .iter()
+                    .copied()
+                    .find(|&i| served[i] == 0)
+                    .or_else(|| matching.last().copied());
+                let (status, body) = match index {
+                    Some(i) => {
+                        served[i] += 1;
+                        (routes[i].1, routes[i].2)
+                    }
+                    None => (404, r#"{"error":"unknown_ressource","error_code":7}"#),
+                };
+
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Read a request's method, path and body
+async fn read_request(socket: &mut tokio::net::TcpStream) -> (String, String, String) {
+    let mut data = Vec::new();
+    let mut buf = [0; 4096];
+    let header_end = loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        data.extend_from_slice(&buf[..n]);
+        if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if n == 0 {
+            break data.len();
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..header_end]).to_string();
+    let content_length = head
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length")
+                .then(|| value.trim().parse::<usize>().ok())?
+        })
+        .unwrap_or(0);
+    while data.len() < header_end + content_length {
+        let n = socket.read(&mut buf).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        data.extend_from_slice(&buf[..n]);
+    }
+
+    let mut request_line = head.split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let body = String::from_utf8_lossy(&data[header_end..]).to_string();
+    (method, path, body)
+}
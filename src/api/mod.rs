@@ -1,11 +1,40 @@
+pub mod anime_mapping;
+mod debrid;
+pub mod external_id;
+mod health;
+mod http;
+pub mod language;
+pub mod local_catalog;
 pub mod media;
-pub mod source_scoring;
+#[cfg(test)]
+mod mock_server;
+mod provider;
 mod realdebrid;
+mod realdebrid_oauth;
+pub mod release_filter;
+pub mod release_name;
+pub mod source_scoring;
 mod tmdb;
 pub mod torrentio;
 
-pub use media::{Episode, Media, MediaType, Season};
-pub use realdebrid::RealDebridClient;
-pub use source_scoring::{ScoringOptions, sort_streams_by_score, get_recommended_indices, pin_recommended_to_top, calculate_source_score};
+pub use anime_mapping::{AnimeMapping, KitsuEpisode};
+pub use debrid::{check_availability, debrid_service, DebridService};
+pub use health::{probe_url, swarm_health, StreamHealth};
+pub use http::HttpClient;
+pub use local_catalog::LocalCatalog;
+pub use media::{
+    Collection, CollectionRef, Episode, Media, MediaSource, MediaType, Season, TvAiring,
+};
+pub use provider::{MetadataProvider, Providers};
+pub use realdebrid::{
+    AccountDownload, AccountStatus, AccountTorrent, RealDebridClient, TorrentFileInfo, TorrentInfo,
+    TorrentStep,
+};
+pub use realdebrid_oauth::{DeviceCode, OAuthClient, OAuthLogin};
+pub use release_filter::{FilteredStreams, ReleaseFilter};
+pub use source_scoring::{
+    auto_select_index, calculate_source_score, fallback_streams, profile_names, rank_streams,
+    unsupported_features, ScoringOptions,
+};
 pub use tmdb::TmdbClient;
-pub use torrentio::{Stream, TorrentioClient};
+pub use torrentio::{Stream, StreamChoice, TorrentioClient};
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::api::{Episode, Media, MediaSource, MediaType, Season};
+use crate::error::ApiError;
+
+/// A source of movie and TV show metadata (TMDB, a local catalog, ...)
+///
+/// Media returned by a provider carries a [`MediaSource`] with the provider's own ID,
+/// so later lookups can be routed back to the same provider.
+#[async_trait]
+pub trait MetadataProvider: Send + Sync {
+    /// Provider name, matching [`MediaSource::provider`] for its media
+    fn name(&self) -> &'static str;
+
+    /// Search for movies and TV shows
+    async fn search(&self, query: &str) -> Result<Vec<Media>, ApiError>;
+
+    /// Get full details of a movie or TV show
+    async fn details(&self, source: &MediaSource, media_type: MediaType)
+        -> Result<Media, ApiError>;
+
+    /// Get the seasons of a TV show
+    async fn seasons(&self, media: &Media) -> Result<Vec<Season>, ApiError>;
+
+    /// Get the episodes of a TV show season
+    async fn episodes(&self, media: &Media, season: u32) -> Result<Vec<Episode>, ApiError>;
+
+    /// Get the IMDb ID of a movie or TV show (needed for source lookups)
+    async fn imdb_id(&self, media: &Media) -> Result<String, ApiError>;
+}
+
+/// The configured metadata providers
+///
+/// Searches go to the primary provider; lookups for existing media (history, watchlist)
+/// go to whichever provider the media came from.
+#[derive(Clone)]
+pub struct Providers {
+    providers: Vec<Arc<dyn MetadataProvider>>,
+}
+
+impl Providers {
+    /// Create the provider set; the first provider is the primary one
+    pub fn new(primary: Arc<dyn MetadataProvider>) -> Self {
+        Self {
+            providers: vec![primary],
+        }
+    }
+
+    /// Add a provider used for lookups of its own media
+    pub fn with(mut self, provider: Arc<dyn MetadataProvider>) -> Self {
+        self.providers.push(provider);
+        self
+    }
+
+    /// The provider used for searches
+    pub fn primary(&self) -> &dyn MetadataProvider {
+        self.providers[0].as_ref()
+    }
+
+    /// The provider that a media item came from
+    pub fn for_source(&self, source: &MediaSource) -> Result<&dyn MetadataProvider, ApiError> {
+        self.providers
+            .iter()
+            .find(|p| p.name() == source.provider())
+            .map(|p| p.as_ref())
+            .ok_or_else(|| {
+                ApiError::Metadata(format!(
+                    "No {} metadata provider configured for {}",
+                    source.provider(),
+                    source.key()
+                ))
+            })
+    }
+}
@@ -1,54 +1,830 @@
-use reqwest::Client;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use reqwest::{RequestBuilder, Response, StatusCode};
 use serde::Deserialize;
 
+use crate::api::realdebrid_oauth::{OAuthClient, OAuthLogin};
+use crate::api::HttpClient;
+use crate::config::RealDebridOAuth;
 use crate::error::ApiError;
 
 const RD_API_URL: &str = "https://api.real-debrid.com/rest/1.0";
 
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Refresh OAuth access tokens this long before they expire
+const TOKEN_REFRESH_MARGIN_SECS: i64 = 60;
+
+/// Called with the new tokens after a refresh (e.g., to save them)
+type RefreshCallback = Arc<dyn Fn(&OAuthLogin) + Send + Sync>;
+
 /// Real-Debrid API client
+///
+/// Cheap to clone: clones share the tokens, so a refresh by one is seen by all.
+#[derive(Clone)]
 pub struct RealDebridClient {
-    client: Client,
-    api_key: String,
+    http: HttpClient,
+    base_url: String,
+    tokens: Arc<Mutex<Tokens>>,
+    /// Held while refreshing so concurrent requests refresh only once
+    refreshing: Arc<tokio::sync::Mutex<()>>,
+    oauth_client: OAuthClient,
+    on_refresh: Option<RefreshCallback>,
+}
+
+/// Current access token (private API token or OAuth access token)
+struct Tokens {
+    access_token: String,
+    /// Set when logged in with OAuth, to refresh the access token
+    oauth: Option<RealDebridOAuth>,
 }
 
 impl RealDebridClient {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(api_key: String, http: HttpClient) -> Self {
         Self {
-            client: Client::new(),
-            api_key,
+            oauth_client: OAuthClient::new(http.clone()),
+            http,
+            base_url: RD_API_URL.to_string(),
+            tokens: Arc::new(Mutex::new(Tokens {
+                access_token: api_key,
+                oauth: None,
+            })),
+            refreshing: Arc::new(tokio::sync::Mutex::new(())),
+            on_refresh: None,
+        }
+    }
+
+    /// Refresh the access token with these OAuth credentials when it expires
+    pub fn with_oauth(
+        self,
+        oauth: RealDebridOAuth,
+        on_refresh: impl Fn(&OAuthLogin) + Send + Sync + 'static,
+    ) -> Self {
+        self.lock_tokens().oauth = Some(oauth);
+        Self {
+            on_refresh: Some(Arc::new(on_refresh)),
+            ..self
+        }
+    }
+
+    /// Use another API URL (e.g., a local mock server), with OAuth under `/oauth/v2`
+    #[cfg(test)]
+    fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self.oauth_client = self
+            .oauth_client
+            .with_base_url(&format!("{}/oauth/v2", self.base_url));
+        self
+    }
+
+    fn lock_tokens(&self) -> std::sync::MutexGuard<'_, Tokens> {
+        self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Current access token
+    pub fn access_token(&self) -> String {
+        self.lock_tokens().access_token.clone()
+    }
+
+    /// Current OAuth credentials, if logged in with OAuth
+    pub fn oauth(&self) -> Option<RealDebridOAuth> {
+        self.lock_tokens().oauth.clone()
+    }
+
+    /// Refresh the OAuth access token if it has expired or is about to
+    pub async fn ensure_token(&self) -> Result<(), ApiError> {
+        let expires_at = match &self.lock_tokens().oauth {
+            Some(oauth) => oauth.expires_at,
+            None => return Ok(()),
+        };
+        if expires_at <= chrono::Utc::now().timestamp() + TOKEN_REFRESH_MARGIN_SECS {
+            self.refresh(&self.access_token()).await?;
+        }
+        Ok(())
+    }
+
+    /// Replace a rejected access token, returning the new one
+    ///
+    /// If another request already replaced `stale_token`, its new token is used.
+    async fn refresh(&self, stale_token: &str) -> Result<String, ApiError> {
+        let _refreshing = self.refreshing.lock().await;
+
+        let oauth = {
+            let tokens = self.lock_tokens();
+            if tokens.access_token != stale_token {
+                return Ok(tokens.access_token.clone());
+            }
+            tokens.oauth.clone().ok_or(ApiError::RealDebridAuth)?
+        };
+
+        tracing::debug!("Refreshing Real-Debrid access token");
+        let login = self.oauth_client.refresh(&oauth).await?;
+        {
+            let mut tokens = self.lock_tokens();
+            tokens.access_token = login.access_token.clone();
+            tokens.oauth = Some(login.oauth.clone());
         }
+        if let Some(on_refresh) = &self.on_refresh {
+            on_refresh(&login);
+        }
+        Ok(login.access_token)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Send a request built with the access token
+    ///
+    /// With OAuth, an expired token is refreshed first, and a rejected one is
+    /// refreshed and the request retried once.
+    async fn send(&self, build: impl Fn(&str) -> RequestBuilder) -> Result<Response, ApiError> {
+        self.ensure_token().await?;
+        let token = self.access_token();
+        let response = self.http.send(build(&token)).await?;
+
+        if response.status() == StatusCode::UNAUTHORIZED && self.oauth().is_some() {
+            let token = self.refresh(&token).await?;
+            return check_status(self.http.send(build(&token)).await?).await;
+        }
+        check_status(response).await
+    }
+
+    /// Send an authenticated GET request
+    async fn get(&self, path: &str) -> Result<Response, ApiError> {
+        let url = self.url(path);
+        self.send(|token| self.http.get(&url).bearer_auth(token))
+            .await
     }
 
-    /// Make an authenticated request
-    fn auth_header(&self) -> String {
-        format!("Bearer {}", self.api_key)
+    /// Send an authenticated form POST request
+    async fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<Response, ApiError> {
+        let url = self.url(path);
+        self.send(|token| self.http.post(&url).bearer_auth(token).form(form))
+            .await
     }
 
     /// Validate the API key by fetching user info
     pub async fn validate_key(&self) -> Result<RealDebridUser, ApiError> {
-        let url = format!("{}/user", RD_API_URL);
+        let user: RealDebridUser = self.get("/user").await?.json().await?;
+        Ok(user)
+    }
+
+    /// Get the remaining traffic of each hoster with a limit
+    pub async fn traffic(&self) -> Result<BTreeMap<String, HostTraffic>, ApiError> {
+        let traffic = self.get("/traffic").await?.json().await?;
+        Ok(traffic)
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", self.auth_header())
-            .send()
+    /// Get the account's user info and traffic
+    pub async fn account(&self) -> Result<AccountStatus, ApiError> {
+        let (user, traffic) = tokio::join!(self.validate_key(), self.traffic());
+        let user = user?;
+        // Traffic is informational, so the user info is enough
+        let traffic = traffic.unwrap_or_else(|e| {
+            tracing::warn!("Failed to get Real-Debrid traffic: {}", e);
+            BTreeMap::new()
+        });
+        Ok(AccountStatus { user, traffic })
+    }
+
+    /// Add a magnet link to the account's torrents
+    pub async fn add_magnet(&self, magnet: &str) -> Result<AddedTorrent, ApiError> {
+        let added: AddedTorrent = self
+            .post("/torrents/addMagnet", &[("magnet", magnet)])
+            .await?
+            .json()
             .await?;
+        Ok(added)
+    }
+
+    /// Choose which files of an added torrent to download (RD file IDs)
+    pub async fn select_files(&self, torrent_id: &str, file_ids: &[u64]) -> Result<(), ApiError> {
+        let files = file_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.post(
+            &format!("/torrents/selectFiles/{}", torrent_id),
+            &[("files", &files)],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Get the status, progress, files and links of an added torrent
+    pub async fn torrent_info(&self, torrent_id: &str) -> Result<TorrentInfo, ApiError> {
+        let info: TorrentInfo = self
+            .get(&format!("/torrents/info/{}", torrent_id))
+            .await?
+            .json()
+            .await?;
+        Ok(info)
+    }
+
+    /// Turn a torrent's hoster link into a direct download link
+    pub async fn unrestrict_link(&self, link: &str) -> Result<UnrestrictedLink, ApiError> {
+        let unrestricted: UnrestrictedLink = self
+            .post("/unrestrict/link", &[("link", link)])
+            .await?
+            .json()
+            .await?;
+        Ok(unrestricted)
+    }
+
+    /// List the account's torrents, most recently added first
+    pub async fn torrents(&self, limit: usize) -> Result<Vec<AccountTorrent>, ApiError> {
+        let response = self.get(&format!("/torrents?limit={}", limit)).await?;
+        // An account without torrents gets an empty 204 response
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(Vec::new());
+        }
+        Ok(response.json().await?)
+    }
 
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(ApiError::RealDebridAuth);
+    /// List the account's unrestricted links (download history), most recent first
+    pub async fn downloads(&self, limit: usize) -> Result<Vec<AccountDownload>, ApiError> {
+        let response = self.get(&format!("/downloads?limit={}", limit)).await?;
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(Vec::new());
         }
+        Ok(response.json().await?)
+    }
 
-        if !response.status().is_success() {
-            return Err(ApiError::RealDebrid(format!("HTTP {}", response.status())));
+    /// Move an added torrent one step towards playback
+    ///
+    /// Selects the file picked by `choose_file` once the torrent's files are known, and
+    /// unrestricts the link once Real-Debrid has downloaded it. Poll until this returns
+    /// [`TorrentStep::Ready`].
+    pub async fn advance_torrent(
+        &self,
+        torrent_id: &str,
+        choose_file: impl Fn(&[TorrentFileInfo]) -> Option<u64>,
+    ) -> Result<TorrentStep, ApiError> {
+        let info = self.torrent_info(torrent_id).await?;
+
+        match info.status.as_str() {
+            "waiting_files_selection" => {
+                let file_id = choose_file(&info.files).ok_or_else(|| {
+                    ApiError::RealDebrid("No video file for this title in the torrent".to_string())
+                })?;
+                self.select_files(torrent_id, &[file_id]).await?;
+                Ok(TorrentStep::Waiting(info))
+            }
+            "downloaded" => {
+                // One file is selected, so there is one link
+                let link = info.links.first().ok_or_else(|| {
+                    ApiError::RealDebrid("Downloaded torrent has no links".to_string())
+                })?;
+                Ok(TorrentStep::Ready(self.unrestrict_link(link).await?))
+            }
+            "magnet_error" | "error" | "virus" | "dead" => Err(ApiError::RealDebrid(format!(
+                "Torrent failed on Real-Debrid ({})",
+                info.status.replace('_', " ")
+            ))),
+            _ => Ok(TorrentStep::Waiting(info)),
         }
+    }
+}
 
-        let user: RealDebridUser = response.json().await?;
-        Ok(user)
+/// Map error statuses to errors, using Real-Debrid's error message when there is one
+async fn check_status(response: Response) -> Result<Response, ApiError> {
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED {
+        return Err(ApiError::RealDebridAuth);
     }
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let message = response
+        .json::<ErrorResponse>()
+        .await
+        .map(|body| body.error.replace('_', " "))
+        .unwrap_or_else(|_| format!("HTTP {}", status));
+    Err(ApiError::RealDebrid(message))
 }
 
 #[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct RealDebridUser {
     pub username: String,
+    /// Fidelity points
+    #[serde(default)]
+    pub points: u64,
+    /// "premium" or "free"
+    #[serde(rename = "type", default)]
+    pub account_type: String,
+    /// Seconds of premium left
+    #[serde(default)]
+    pub premium: u64,
+    /// End of premium (ISO 8601)
+    #[serde(default)]
+    pub expiration: Option<String>,
+}
+
+impl RealDebridUser {
+    /// Whether the account has premium left (needed for cached sources)
+    pub fn is_premium(&self) -> bool {
+        self.account_type == "premium" && self.premium > 0
+    }
+
+    /// Whole days of premium left
+    pub fn premium_days(&self) -> u64 {
+        self.premium / SECONDS_PER_DAY
+    }
+}
+
+/// Traffic of a hoster with a limit
+#[derive(Debug, Clone, Deserialize)]
+pub struct HostTraffic {
+    /// Traffic left, in the unit of `kind`
+    #[serde(default)]
+    pub left: i64,
+    /// Traffic limit, in the unit of `kind`
+    #[serde(default)]
+    pub limit: i64,
+    /// Unit of `left` and `limit`: "links", "gigabytes" or "bytes"
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    /// When the limit resets (e.g., "daily")
+    #[serde(default)]
+    pub reset: String,
+}
+
+/// Account user info and traffic
+#[derive(Debug, Clone)]
+pub struct AccountStatus {
+    pub user: RealDebridUser,
+    /// Traffic by hoster
+    pub traffic: BTreeMap<String, HostTraffic>,
+}
+
+impl AccountStatus {
+    /// One-line summary ("alice · premium, 42 days left · 1200 points")
+    pub fn summary(&self) -> String {
+        let premium = if self.user.is_premium() {
+            format!("premium, {} days left", self.user.premium_days())
+        } else {
+            "no premium".to_string()
+        };
+        format!(
+            "{} · {} · {} points",
+            self.user.username, premium, self.user.points
+        )
+    }
+
+    /// Warning when premium has ended, or ends within `warning_days`
+    pub fn expiry_warning(&self, warning_days: u64) -> Option<String> {
+        if !self.user.is_premium() {
+            return Some(
+                "Real-Debrid premium has expired: cached sources are unavailable until it's renewed"
+                    .to_string(),
+            );
+        }
+        match self.user.premium_days() {
+            0 => Some("Real-Debrid premium expires today".to_string()),
+            1 => Some("Real-Debrid premium expires tomorrow".to_string()),
+            days if days <= warning_days => {
+                Some(format!("Real-Debrid premium expires in {} days", days))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Torrent added to the account
+#[derive(Debug, Deserialize)]
+pub struct AddedTorrent {
+    pub id: String,
+}
+
+/// Status of an added torrent
+#[derive(Debug, Clone, Deserialize)]
+pub struct TorrentInfo {
+    /// Torrent name
+    #[serde(default)]
+    pub filename: String,
+    /// "magnet_conversion", "waiting_files_selection", "queued", "downloading",
+    /// "downloaded", "compressing", "uploading", or an error status
+    pub status: String,
+    /// Download progress (0-100)
+    #[serde(default)]
+    pub progress: f64,
+    /// Download speed in bytes/s, while downloading
+    #[serde(default)]
+    pub speed: Option<u64>,
+    /// Connected seeders, while downloading
+    #[serde(default)]
+    pub seeders: Option<u32>,
+    /// Size of the selected files
+    #[serde(default)]
+    pub bytes: u64,
+    #[serde(default)]
+    pub files: Vec<TorrentFileInfo>,
+    /// Hoster links of the selected files, once downloaded
+    #[serde(default)]
+    pub links: Vec<String>,
+}
+
+/// A file of an added torrent
+#[derive(Debug, Clone, Deserialize)]
+pub struct TorrentFileInfo {
+    /// Real-Debrid file ID (from 1, in torrent order)
+    pub id: u64,
+    /// Path within the torrent (starts with "/")
+    pub path: String,
+    pub bytes: u64,
+    /// 1 if the file was selected for download
+    #[serde(default)]
+    pub selected: u8,
+}
+
+impl TorrentInfo {
+    /// Selected files with their hoster links (one link per selected file, in order)
+    pub fn selected_links(&self) -> Vec<(&TorrentFileInfo, &str)> {
+        self.files
+            .iter()
+            .filter(|file| file.selected == 1)
+            .zip(self.links.iter().map(String::as_str))
+            .collect()
+    }
+}
+
+/// A torrent in the account
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountTorrent {
+    pub id: String,
+    /// Torrent name
+    pub filename: String,
+    /// Size of the selected files
+    pub bytes: u64,
+    /// Same statuses as [`TorrentInfo::status`]
+    pub status: String,
+    /// Download progress (0-100)
+    #[serde(default)]
+    pub progress: f64,
+}
+
+/// An unrestricted link in the account's download history
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountDownload {
+    pub filename: String,
+    #[serde(default)]
+    pub filesize: u64,
+    /// Direct download link
+    pub download: String,
+}
+
+/// Direct download link for a hoster link
+#[derive(Debug, Clone, Deserialize)]
+pub struct UnrestrictedLink {
+    pub filename: String,
+    pub download: String,
+}
+
+/// Result of [`RealDebridClient::advance_torrent`]
+#[derive(Debug)]
+pub enum TorrentStep {
+    /// Real-Debrid is still working on the torrent
+    Waiting(TorrentInfo),
+    /// The file can be streamed
+    Ready(UnrestrictedLink),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::api::mock_server::MockServer;
+
+    fn mock_client(server: &MockServer) -> RealDebridClient {
+        RealDebridClient::new("key".to_string(), HttpClient::default()).with_base_url(&server.url)
+    }
+
+    const INFO_WAITING: &str = r#"{"id":"T1","status":"waiting_files_selection","progress":0,"bytes":0,
+        "files":[{"id":1,"path":"/Show/Show.S01E01.mkv","bytes":1000,"selected":0},
+                 {"id":2,"path":"/Show/Show.S01E02.mkv","bytes":1000,"selected":0}],"links":[]}"#;
+    const INFO_DOWNLOADING: &str = r#"{"id":"T1","status":"downloading","progress":42.5,
+        "speed":1048576,"seeders":12,"bytes":1000,"files":[],"links":[]}"#;
+    const INFO_DOWNLOADED: &str = r#"{"id":"T1","status":"downloaded","progress":100,"bytes":1000,
+        "files":[],"links":["https://real-debrid.com/d/ABC"]}"#;
+
+    #[tokio::test]
+    async fn test_account() {
+        let server = MockServer::start(vec![
+            (
+                "GET /user",
+                200,
+                r#"{"id":1,"username":"alice","email":"a@example.com","points":1200,
+                    "locale":"en","type":"premium","premium":259200,
+                    "expiration":"2026-10-21T12:00:00.000Z"}"#,
+            ),
+            (
+                "GET /traffic",
+                200,
+                r#"{"rapidgator.net":{"left":48,"bytes":0,"links":2,"limit":50,
+                    "type":"links","extra":0,"reset":"daily"}}"#,
+            ),
+        ])
+        .await;
+
+        let account = mock_client(&server).account().await.unwrap();
+        assert_eq!(account.user.premium_days(), 3);
+        assert_eq!(
+            account.summary(),
+            "alice · premium, 3 days left · 1200 points"
+        );
+        assert_eq!(account.traffic["rapidgator.net"].left, 48);
+        assert_eq!(account.traffic["rapidgator.net"].kind, "links");
+        assert_eq!(
+            account.expiry_warning(7).as_deref(),
+            Some("Real-Debrid premium expires in 3 days")
+        );
+        assert_eq!(account.expiry_warning(2), None);
+    }
+
+    #[test]
+    fn test_expired_account() {
+        let user: RealDebridUser =
+            serde_json::from_str(r#"{"username":"bob","type":"free","premium":0}"#).unwrap();
+        let account = AccountStatus {
+            user,
+            traffic: BTreeMap::new(),
+        };
+        assert!(!account.user.is_premium());
+        assert!(account.expiry_warning(7).unwrap().contains("expired"));
+        assert_eq!(account.summary(), "bob · no premium · 0 points");
+    }
+
+    #[tokio::test]
+    async fn test_library() {
+        let server = MockServer::start(vec![
+            (
+                "GET /torrents?limit=50",
+                200,
+                r#"[{"id":"T1","filename":"Show.S01.1080p","hash":"abc","bytes":2000,
+                     "status":"downloaded","progress":100,"links":["l1","l2"]},
+                    {"id":"T2","filename":"Movie.2024.2160p","bytes":5000,
+                     "status":"downloading","progress":12.5}]"#,
+            ),
+            ("GET /downloads?limit=50", 204, ""),
+            (
+                "GET /torrents/info/T1",
+                200,
+                r#"{"id":"T1","filename":"Show.S01.1080p","status":"downloaded","progress":100,
+                    "bytes":2000,"files":[{"id":1,"path":"/Show.S01E01.mkv","bytes":1000,"selected":1},
+                    {"id":2,"path":"/sample.mkv","bytes":10,"selected":0},
+                    {"id":3,"path":"/Show.S01E02.mkv","bytes":1000,"selected":1}],
+                    "links":["https://real-debrid.com/d/E1","https://real-debrid.com/d/E2"]}"#,
+            ),
+        ])
+        .await;
+        let client = mock_client(&server);
+
+        let torrents = client.torrents(50).await.unwrap();
+        assert_eq!(torrents.len(), 2);
+        assert_eq!(torrents[1].filename, "Movie.2024.2160p");
+        assert_eq!(torrents[1].progress, 12.5);
+        assert!(client.downloads(50).await.unwrap().is_empty());
+
+        let info = client.torrent_info("T1").await.unwrap();
+        let links: Vec<(&str, &str)> = info
+            .selected_links()
+            .into_iter()
+            .map(|(file, link)| (file.path.as_str(), link))
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                ("/Show.S01E01.mkv", "https://real-debrid.com/d/E1"),
+                ("/Show.S01E02.mkv", "https://real-debrid.com/d/E2"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_torrent_workflow() {
+        let server = MockServer::start(vec![
+            (
+                "POST /torrents/addMagnet",
+                201,
+                r#"{"id":"T1","uri":"https://x/T1"}"#,
+            ),
+            ("GET /torrents/info/T1", 200, INFO_WAITING),
+            ("POST /torrents/selectFiles/T1", 204, ""),
+            ("GET /torrents/info/T1", 200, INFO_DOWNLOADING),
+            ("GET /torrents/info/T1", 200, INFO_DOWNLOADED),
+            (
+                "POST /unrestrict/link",
+                200,
+                r#"{"id":"U1","filename":"Show.S01E02.mkv","filesize":1000,
+                    "download":"https://cdn.real-debrid.com/d/Show.S01E02.mkv"}"#,
+            ),
+        ])
+        .await;
+        let client = mock_client(&server);
+
+        let added = client.add_magnet("magnet:?xt=urn:btih:abc").await.unwrap();
+        assert_eq!(added.id, "T1");
+
+        let choose = |files: &[TorrentFileInfo]| {
+            files
+                .iter()
+                .find(|file| file.path.contains("S01E02"))
+                .map(|file| file.id)
+        };
+        let step = client.advance_torrent("T1", choose).await.unwrap();
+        assert!(
+            matches!(step, TorrentStep::Waiting(ref info) if info.status == "waiting_files_selection")
+        );
+
+        match client.advance_torrent("T1", choose).await.unwrap() {
+            TorrentStep::Waiting(info) => {
+                assert_eq!(info.status, "downloading");
+                assert_eq!(info.progress, 42.5);
+                assert_eq!(info.seeders, Some(12));
+            }
+            step => panic!("unexpected step {:?}", step),
+        }
+
+        match client.advance_torrent("T1", choose).await.unwrap() {
+            TorrentStep::Ready(link) => {
+                assert_eq!(
+                    link.download,
+                    "https://cdn.real-debrid.com/d/Show.S01E02.mkv"
+                );
+            }
+            step => panic!("unexpected step {:?}", step),
+        }
+
+        assert_eq!(
+            server.requests(),
+            vec![
+                "POST /torrents/addMagnet magnet=magnet%3A%3Fxt%3Durn%3Abtih%3Aabc",
+                "GET /torrents/info/T1",
+                "POST /torrents/selectFiles/T1 files=2",
+                "GET /torrents/info/T1",
+                "GET /torrents/info/T1",
+                "POST /unrestrict/link link=https%3A%2F%2Freal-debrid.com%2Fd%2FABC",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_torrent_errors() {
+        let server = MockServer::start(vec![
+            (
+                "GET /torrents/info/DEAD",
+                200,
+                r#"{"status":"magnet_error"}"#,
+            ),
+            ("GET /torrents/info/NOVIDEO", 200, INFO_WAITING),
+            (
+                "POST /torrents/addMagnet",
+                503,
+                r#"{"error":"service_unavailable","error_code":25}"#,
+            ),
+            ("GET /user", 401, r#"{"error":"bad_token","error_code":8}"#),
+        ])
+        .await;
+        let client = mock_client(&server);
+
+        let err = client.advance_torrent("DEAD", |_| None).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Real-Debrid API error: Torrent failed on Real-Debrid (magnet error)"
+        );
+
+        let err = client
+            .advance_torrent("NOVIDEO", |_| None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No video file"));
+
+        let err = client
+            .add_magnet("magnet:?xt=urn:btih:abc")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Real-Debrid API error: service unavailable"
+        );
+
+        assert!(matches!(
+            client.validate_key().await,
+            Err(ApiError::RealDebridAuth)
+        ));
+    }
+
+    const USER: &str = r#"{"id":1,"username":"alice","email":"a@example.com","points":10,
+        "locale":"en","avatar":"","type":"premium","premium":864000,
+        "expiration":"2030-01-01T00:00:00.000Z"}"#;
+
+    #[tokio::test]
+    async fn test_device_login() {
+        let server = MockServer::start(vec![
+            (
+                "GET /oauth/v2/device/code?client_id=X245A4XAIBGVM&new_credentials=yes",
+                200,
+                r#"{"device_code":"DEV","user_code":"ABCD1234","interval":0,"expires_in":60,
+                    "verification_url":"https://real-debrid.com/device"}"#,
+            ),
+            (
+                "GET /oauth/v2/device/credentials?client_id=X245A4XAIBGVM&code=DEV",
+                403,
+                r#"{"error":"authorization_pending"}"#,
+            ),
+            (
+                "GET /oauth/v2/device/credentials?client_id=X245A4XAIBGVM&code=DEV",
+                200,
+                r#"{"client_id":"CID","client_secret":"SECRET"}"#,
+            ),
+            (
+                "POST /oauth/v2/token",
+                200,
+                r#"{"access_token":"ACCESS","expires_in":3600,"token_type":"Bearer",
+                    "refresh_token":"REFRESH"}"#,
+            ),
+        ])
+        .await;
+        let oauth = OAuthClient::new(HttpClient::default())
+            .with_base_url(&format!("{}/oauth/v2", server.url));
+
+        let device = oauth.device_code().await.unwrap();
+        assert_eq!(device.user_code, "ABCD1234");
+        assert_eq!(device.verification_url, "https://real-debrid.com/device");
+
+        let login = oauth.wait_for_login(&device).await.unwrap();
+        assert_eq!(login.access_token, "ACCESS");
+        assert_eq!(login.oauth.client_id, "CID");
+        assert_eq!(login.oauth.client_secret, "SECRET");
+        assert_eq!(login.oauth.refresh_token, "REFRESH");
+        assert!(login.oauth.expires_at > chrono::Utc::now().timestamp() + 3000);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(
+            requests[3],
+            "POST /oauth/v2/token client_id=CID&client_secret=SECRET&code=DEV\
+             &grant_type=http%3A%2F%2Foauth.net%2Fgrant_type%2Fdevice%2F1.0"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_refresh() {
+        let server = MockServer::start(vec![
+            ("GET /user", 401, r#"{"error":"bad_token","error_code":8}"#),
+            ("GET /user", 200, USER),
+            (
+                "POST /oauth/v2/token",
+                200,
+                r#"{"access_token":"NEW","expires_in":3600,"token_type":"Bearer",
+                    "refresh_token":"REFRESH"}"#,
+            ),
+        ])
+        .await;
+        let oauth = RealDebridOAuth {
+            client_id: "CID".to_string(),
+            client_secret: "SECRET".to_string(),
+            refresh_token: "REFRESH".to_string(),
+            expires_at: chrono::Utc::now().timestamp() + 3600,
+        };
+        let saved = Arc::new(Mutex::new(Vec::new()));
+        let on_refresh = {
+            let saved = saved.clone();
+            move |login: &OAuthLogin| saved.lock().unwrap().push(login.access_token.clone())
+        };
+        let client = mock_client(&server).with_oauth(oauth, on_refresh);
+
+        // A rejected token is refreshed and the request retried
+        let user = client.validate_key().await.unwrap();
+        assert_eq!(user.username, "alice");
+        assert_eq!(client.access_token(), "NEW");
+        assert_eq!(*saved.lock().unwrap(), vec!["NEW"]);
+        assert_eq!(
+            server.requests(),
+            vec![
+                "GET /user",
+                "POST /oauth/v2/token client_id=CID&client_secret=SECRET&code=REFRESH\
+                 &grant_type=http%3A%2F%2Foauth.net%2Fgrant_type%2Fdevice%2F1.0",
+                "GET /user",
+            ]
+        );
+
+        // An expired token is refreshed before the request
+        let mut expired = client.oauth().unwrap();
+        expired.expires_at = 0;
+        let client = mock_client(&server).with_oauth(expired, |_| {});
+        client.validate_key().await.unwrap();
+        assert_eq!(client.access_token(), "NEW");
+        assert!(server.requests()[3].starts_with("POST /oauth/v2/token"));
+    }
 }
@@ -0,0 +1,172 @@
+//! Real-Debrid OAuth device-code login.
+//!
+//! Instead of pasting a private API token, the user enters a short code on
+//! real-debrid.com. The app polls until the code is approved, receives client
+//! credentials for this device, and trades them for an access token that expires and
+//! is refreshed with the refresh token.
+
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::api::HttpClient;
+use crate::config::RealDebridOAuth;
+use crate::error::ApiError;
+
+const OAUTH_URL: &str = "https://api.real-debrid.com/oauth/v2";
+
+/// Real-Debrid's client ID for open source apps
+const OPEN_SOURCE_CLIENT_ID: &str = "X245A4XAIBGVM";
+
+const DEVICE_GRANT_TYPE: &str = "http://oauth.net/grant_type/device/1.0";
+
+/// Code for the user to enter on the verification page
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceCode {
+    /// Code the app polls with
+    pub device_code: String,
+    /// Code the user enters
+    pub user_code: String,
+    /// Seconds between polls
+    pub interval: u64,
+    /// Seconds until the code expires
+    pub expires_in: u64,
+    pub verification_url: String,
+}
+
+/// Access token with the credentials to refresh it
+#[derive(Debug, Clone)]
+pub struct OAuthLogin {
+    pub access_token: String,
+    pub oauth: RealDebridOAuth,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceCredentials {
+    client_id: String,
+    client_secret: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    /// Seconds until the access token expires
+    expires_in: i64,
+}
+
+/// Client for Real-Debrid's OAuth endpoints
+#[derive(Clone)]
+pub struct OAuthClient {
+    http: HttpClient,
+    base_url: String,
+}
+
+impl OAuthClient {
+    pub fn new(http: HttpClient) -> Self {
+        Self {
+            http,
+            base_url: OAUTH_URL.to_string(),
+        }
+    }
+
+    /// Use another OAuth URL (e.g., a local mock server)
+    #[cfg(test)]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Request a code for the user to enter on the verification page
+    pub async fn device_code(&self) -> Result<DeviceCode, ApiError> {
+        let request = self.http.get(&format!(
+            "{}/device/code?client_id={}&new_credentials=yes",
+            self.base_url, OPEN_SOURCE_CLIENT_ID
+        ));
+        let response = self.http.send(request).await?;
+        if !response.status().is_success() {
+            return Err(ApiError::RealDebrid(format!(
+                "Failed to get a login code (HTTP {})",
+                response.status()
+            )));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Poll until the user approves the code, then get the first access token
+    pub async fn wait_for_login(&self, device: &DeviceCode) -> Result<OAuthLogin, ApiError> {
+        let deadline = Instant::now() + Duration::from_secs(device.expires_in);
+        let interval = Duration::from_secs(device.interval);
+
+        while Instant::now() < deadline {
+            if let Some(credentials) = self.credentials(&device.device_code).await? {
+                return self
+                    .token(
+                        &credentials.client_id,
+                        &credentials.client_secret,
+                        &device.device_code,
+                    )
+                    .await;
+            }
+            tokio::time::sleep(interval).await;
+        }
+        Err(ApiError::RealDebrid(
+            "The login code expired before it was entered".to_string(),
+        ))
+    }
+
+    /// Get a new access token with the refresh token
+    pub async fn refresh(&self, oauth: &RealDebridOAuth) -> Result<OAuthLogin, ApiError> {
+        self.token(&oauth.client_id, &oauth.client_secret, &oauth.refresh_token)
+            .await
+    }
+
+    /// Client credentials for the device, once the user has approved the code
+    async fn credentials(&self, device_code: &str) -> Result<Option<DeviceCredentials>, ApiError> {
+        let request = self.http.get(&format!(
+            "{}/device/credentials?client_id={}&code={}",
+            self.base_url, OPEN_SOURCE_CLIENT_ID, device_code
+        ));
+        let response = self.http.send(request).await?;
+        match response.status() {
+            status if status.is_success() => Ok(Some(response.json().await?)),
+            // Not approved yet
+            StatusCode::FORBIDDEN | StatusCode::BAD_REQUEST => Ok(None),
+            status => Err(ApiError::RealDebrid(format!(
+                "Failed to check the login code (HTTP {})",
+                status
+            ))),
+        }
+    }
+
+    /// Trade a device code or refresh token for an access token
+    async fn token(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+        code: &str,
+    ) -> Result<OAuthLogin, ApiError> {
+        let request = self.http.post(&format!("{}/token", self.base_url)).form(&[
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("code", code),
+            ("grant_type", DEVICE_GRANT_TYPE),
+        ]);
+        let response = self.http.send(request).await?;
+        if !response.status().is_success() {
+            return Err(ApiError::RealDebridAuth);
+        }
+
+        let token: TokenResponse = response.json().await?;
+        Ok(OAuthLogin {
+            access_token: token.access_token,
+            oauth: RealDebridOAuth {
+                client_id: client_id.to_string(),
+                client_secret: client_secret.to_string(),
+                refresh_token: token.refresh_token,
+                expires_at: chrono::Utc::now().timestamp() + token.expires_in,
+            },
+        })
+    }
+}
@@ -0,0 +1,281 @@
+use regex::Regex;
+
+use crate::api::release_name;
+use crate::api::torrentio::Stream;
+use crate::config::{FilterAction, FilterConfig, FilterRule};
+use crate::error::ConfigError;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Sources left after filtering
+#[derive(Debug, Default)]
+pub struct FilteredStreams {
+    /// Sources matching no rule (or an allow rule)
+    pub shown: Vec<Stream>,
+    /// Sources matching a demote rule, listed after the others
+    pub demoted: Vec<Stream>,
+    /// Number of sources matching a hide rule
+    pub hidden: usize,
+}
+
+impl FilteredStreams {
+    /// Hide the shown and demoted streams matching a predicate
+    pub fn hide(&mut self, predicate: impl Fn(&Stream) -> bool) {
+        let before = self.shown.len() + self.demoted.len();
+        self.shown.retain(|stream| !predicate(stream));
+        self.demoted.retain(|stream| !predicate(stream));
+        self.hidden += before - self.shown.len() - self.demoted.len();
+    }
+}
+
+/// A rule with its patterns compiled
+struct CompiledRule {
+    action: FilterAction,
+    group: Option<String>,
+    keyword: Option<Regex>,
+    regex: Option<Regex>,
+    provider: Option<String>,
+    /// Normalized like parsed codecs ("x265" -> "HEVC")
+    codec: Option<String>,
+    min_size_gb: Option<f64>,
+    max_size_gb: Option<f64>,
+}
+
+impl CompiledRule {
+    fn new(rule: &FilterRule) -> Result<Self, ConfigError> {
+        let compile = |pattern: &str| {
+            Regex::new(pattern)
+                .map_err(|e| ConfigError::Invalid(format!("filter regex \"{}\": {}", pattern, e)))
+        };
+
+        Ok(Self {
+            action: rule.action,
+            group: rule.group.clone(),
+            keyword: rule
+                .keyword
+                .as_deref()
+                .map(|keyword| compile(&format!(r"(?i)\b{}\b", regex::escape(keyword))))
+                .transpose()?,
+            regex: rule.regex.as_deref().map(compile).transpose()?,
+            provider: rule.provider.clone(),
+            codec: rule.codec.as_deref().map(|codec| {
+                release_name::parse(codec)
+                    .video_codec
+                    .unwrap_or_else(|| codec.to_string())
+            }),
+            min_size_gb: rule.min_size_gb,
+            max_size_gb: rule.max_size_gb,
+        })
+    }
+
+    /// Whether all of the rule's criteria match the stream
+    fn matches(&self, stream: &Stream) -> bool {
+        let size_gb =
+            (stream.size_bytes < u64::MAX).then(|| stream.size_bytes as f64 / BYTES_PER_GB);
+
+        let criteria = [
+            self.group.as_ref().map(|group| {
+                stream
+                    .release_group
+                    .as_ref()
+                    .is_some_and(|g| g.eq_ignore_ascii_case(group))
+            }),
+            self.keyword.as_ref().map(|re| re.is_match(&stream.title)),
+            self.regex.as_ref().map(|re| re.is_match(&stream.title)),
+            self.provider
+                .as_ref()
+                .map(|provider| stream.provider.eq_ignore_ascii_case(provider)),
+            self.codec.as_ref().map(|codec| {
+                stream.video_codec.as_ref().is_some_and(|codecs| {
+                    codecs
+                        .split_whitespace()
+                        .any(|c| c.eq_ignore_ascii_case(codec))
+                })
+            }),
+            self.min_size_gb
+                .map(|min| size_gb.is_some_and(|size| size < min)),
+            self.max_size_gb
+                .map(|max| size_gb.is_some_and(|size| size > max)),
+        ];
+
+        let mut set = criteria.iter().flatten().peekable();
+        set.peek().is_some() && set.all(|matched| *matched)
+    }
+}
+
+/// Hides and demotes sources according to the configured rules
+pub struct ReleaseFilter {
+    rules: Vec<CompiledRule>,
+}
+
+impl ReleaseFilter {
+    /// Compile the configured rules, failing on an invalid regex
+    pub fn new(config: &FilterConfig) -> Result<Self, ConfigError> {
+        let rules = config
+            .rules
+            .iter()
+            .map(CompiledRule::new)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Action for a stream: allow rules win over hide rules, which win over demote rules
+    fn action(&self, stream: &Stream) -> Option<FilterAction> {
+        let matched: Vec<FilterAction> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches(stream))
+            .map(|rule| rule.action)
+            .collect();
+
+        [
+            FilterAction::Allow,
+            FilterAction::Hide,
+            FilterAction::Demote,
+        ]
+        .into_iter()
+        .find(|action| matched.contains(action))
+    }
+
+    /// Split streams into shown and demoted ones, dropping hidden ones
+    pub fn apply(&self, streams: Vec<Stream>) -> FilteredStreams {
+        let mut filtered = FilteredStreams::default();
+        for stream in streams {
+            match self.action(&stream) {
+                Some(FilterAction::Hide) => filtered.hidden += 1,
+                Some(FilterAction::Demote) => filtered.demoted.push(stream),
+                Some(FilterAction::Allow) | None => filtered.shown.push(stream),
+            }
+        }
+        filtered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_stream(title: &str, provider: &str, size_gb: f64) -> Stream {
+        let release = release_name::parse(title);
+        Stream {
+            provider: provider.to_string(),
+            title: title.to_string(),
+            size_bytes: (size_gb * BYTES_PER_GB) as u64,
+            video_codec: release.video_codec,
+            release_group: release.release_group,
+            ..Default::default()
+        }
+    }
+
+    fn filter(rules: Vec<FilterRule>) -> ReleaseFilter {
+        ReleaseFilter::new(&FilterConfig { rules }).unwrap()
+    }
+
+    fn titles(streams: &[Stream]) -> Vec<&str> {
+        streams.iter().map(|s| s.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_hide_by_group_and_keyword() {
+        let filter = filter(vec![
+            FilterRule {
+                group: Some("yify".to_string()),
+                ..Default::default()
+            },
+            FilterRule {
+                keyword: Some("CAM".to_string()),
+                ..Default::default()
+            },
+        ]);
+        let result = filter.apply(vec![
+            make_stream("Dune.2021.1080p.WEBRip.x264-YIFY", "YTS", 2.0),
+            make_stream("Dune.2021.CAM.x264-XYZ", "1337x", 1.0),
+            make_stream("Dune.2021.1080p.BluRay.x264-SPARKS", "1337x", 10.0),
+            // "CAM" only as a whole word
+            make_stream("Dune.2021.1080p.CAMELOT.x264-ABC", "1337x", 10.0),
+        ]);
+        assert_eq!(result.hidden, 2);
+        assert_eq!(
+            titles(&result.shown),
+            vec![
+                "Dune.2021.1080p.BluRay.x264-SPARKS",
+                "Dune.2021.1080p.CAMELOT.x264-ABC"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_demote_codec_and_size() {
+        let filter = filter(vec![
+            FilterRule {
+                action: FilterAction::Demote,
+                codec: Some("av1".to_string()),
+                ..Default::default()
+            },
+            FilterRule {
+                max_size_gb: Some(30.0),
+                ..Default::default()
+            },
+        ]);
+        let result = filter.apply(vec![
+            make_stream("Show.S01E01.1080p.WEB.AV1-GRP", "eztv", 1.0),
+            make_stream("Movie.2160p.REMUX.HEVC-FGT", "1337x", 60.0),
+            make_stream("Movie.1080p.x265-GRP", "1337x", 3.0),
+        ]);
+        assert_eq!(titles(&result.shown), vec!["Movie.1080p.x265-GRP"]);
+        assert_eq!(
+            titles(&result.demoted),
+            vec!["Show.S01E01.1080p.WEB.AV1-GRP"]
+        );
+        assert_eq!(result.hidden, 1);
+    }
+
+    #[test]
+    fn test_criteria_combine_and_allow_wins() {
+        let filter = filter(vec![
+            // YTS releases larger than 1 GB
+            FilterRule {
+                provider: Some("yts".to_string()),
+                max_size_gb: Some(1.0),
+                ..Default::default()
+            },
+            FilterRule {
+                regex: Some(r"(?i)\bHDTS\b".to_string()),
+                ..Default::default()
+            },
+            FilterRule {
+                action: FilterAction::Allow,
+                group: Some("Trusted".to_string()),
+                ..Default::default()
+            },
+        ]);
+        let result = filter.apply(vec![
+            make_stream("Movie.720p.x264-YTS.MX", "YTS", 0.5),
+            make_stream("Movie.1080p.x264-YTS.MX", "YTS", 2.0),
+            make_stream("Movie.HDTS.x264-Trusted", "1337x", 2.0),
+        ]);
+        assert_eq!(result.hidden, 1);
+        assert_eq!(
+            titles(&result.shown),
+            vec!["Movie.720p.x264-YTS.MX", "Movie.HDTS.x264-Trusted"]
+        );
+    }
+
+    #[test]
+    fn test_empty_rule_and_invalid_regex() {
+        let result = filter(vec![FilterRule::default()]).apply(vec![make_stream(
+            "Movie.1080p-GRP",
+            "yts",
+            1.0,
+        )]);
+        assert_eq!(result.shown.len(), 1);
+
+        let config = FilterConfig {
+            rules: vec![FilterRule {
+                regex: Some("(unclosed".to_string()),
+                ..Default::default()
+            }],
+        };
+        assert!(ReleaseFilter::new(&config).is_err());
+    }
+}
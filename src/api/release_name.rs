@@ -0,0 +1,646 @@
+//! Release-name parsing for torrent titles.
+//!
+//! Torrent names follow loose scene and fansub conventions, e.g.
+//! `Show.S01E03.1080p.WEB-DL.DDP5.1.H.264-GROUP` or
+//! `[SubsPlease] Frieren - 12 (1080p) [ABCD1234].mkv`. [`parse`] extracts what it can
+//! and leaves the rest unset.
+
+use std::ops::RangeInclusive;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // Match quality patterns like "1080p", "720p", "480p", "2160p", "4K"
+    static ref QUALITY_RE: Regex = Regex::new(r"\b(2160p|4K|1080p|720p|480p|360p)\b").unwrap();
+    // Match HDR patterns
+    static ref HDR_RE: Regex = Regex::new(r"(?i)\b(HDR10\+|HDR10|DoVi|DV|Dolby[\s.]?Vision|HDR)\b").unwrap();
+    // Match video codec patterns
+    static ref VIDEO_CODEC_RE: Regex = Regex::new(r"(?i)\b(HEVC|x265|x264|AVC|AV1|H\.?265|H\.?264|VC-1|10bit|10-bit)\b").unwrap();
+    // Match audio codec patterns (simplified)
+    static ref AUDIO_RE: Regex = Regex::new(r"(?i)(DTS-HD[\s.]?MA|TrueHD|Atmos|DTS|AAC|FLAC|EAC3|E-AC-3|AC3|DD|DD\+|LPCM)[\s.]?(\d\.\d)?").unwrap();
+    // Match audio channels
+    static ref AUDIO_CHANNELS_RE: Regex = Regex::new(r"\b([257]\.[01])\b").unwrap();
+    // Match source type patterns
+    static ref SOURCE_RE: Regex = Regex::new(r"(?i)\b(UHD[\s.]?BluRay|BluRay|Blu-Ray|BDRip|BRRip|WEB-DL|WEBDL|WEBRip|REMUX|HDTV|DVDRip)\b").unwrap();
+    // Match bit depth ("10bit", "10-bit", "Hi10")
+    static ref BIT_DEPTH_RE: Regex = Regex::new(r"(?i)\b(?:(8|10|12)[\s.-]?bits?|Hi(10)P?)\b").unwrap();
+    // Match editions; "DC" only as a tag before a year or quality, not in titles like
+    // "DC League of Super-Pets"
+    static ref EDITION_RE: Regex = Regex::new(r"(?i)\b(Director'?s[\s.]Cut|Extended(?:[\s.](?:Cut|Edition))?|IMAX|Unrated|Uncut|Theatrical(?:[\s.]Cut)?|Remastered|Criterion|Special[\s.]Edition|Ultimate[\s.](?:Cut|Edition))\b|(?:^|[\s.])(?-i:(DC))[\s.](?:\d{4}|\d{3,4}p)\b").unwrap();
+    // Match repack/proper markers
+    static ref REPACK_RE: Regex = Regex::new(r"(?i)\b(REPACK\d?|PROPER|RERIP)\b").unwrap();
+    // Match dual audio
+    static ref DUAL_AUDIO_RE: Regex = Regex::new(r"(?i)\b(Dual[\s.-]?Audio|DUAL)\b").unwrap();
+    // Match multi audio ("MULTi", "Multi-Audio"); "Multi-Subs" is checked separately
+    static ref MULTI_AUDIO_RE: Regex = Regex::new(r"(?i)\bMulti(?:[\s.-]?(?:Audio|Lang))?\b").unwrap();
+    // Match subtitle hints; "HC" (hardcoded) only next to a quality or rip tag, not in
+    // names like "HC Davos"
+    static ref SUBTITLES_RE: Regex = Regex::new(r"(?i)\b(Multi[\s.-]?Subs?|E[\s.-]?Subs?|Eng[\s.-]?Subs?|Hard[\s.-]?Subs?|Soft[\s.-]?Subs?|Subbed)\b|\d{3,4}p[\s.](?-i:(HC))\b|[\s.](?-i:(HC))[\s.](?:\d{3,4}p|HD-?Rip|WEB(?:-?DL|Rip)?|HDTS|CAM)\b").unwrap();
+    // Match "S01E03", "S01E03-E05", "S01E03-05", "S01E03E04"
+    static ref SEASON_EPISODE_RE: Regex = Regex::new(r"(?i)\bS(\d{1,2})[\s._-]?E(\d{1,4})(?:[\s._]?-[\s._]?E?(\d{1,4})|E(\d{1,4}))?\b").unwrap();
+    // Match "1x03"
+    static ref CROSS_EPISODE_RE: Regex = Regex::new(r"(?i)\b(\d{1,2})x(\d{2,3})\b").unwrap();
+    // Match "S01-S03", "S01-03"
+    static ref SEASON_RANGE_RE: Regex = Regex::new(r"(?i)\bS(\d{1,2})[\s._]?-[\s._]?S?(\d{1,2})\b").unwrap();
+    // Match "Season 2", "Seasons 1-3", "Season 1 to 3"
+    static ref SEASON_WORD_RE: Regex = Regex::new(r"(?i)\bSeasons?[\s._]?(\d{1,2})(?:[\s._]?(?:-|to)[\s._]?(\d{1,2}))?\b").unwrap();
+    // Match a bare season ("S01")
+    static ref SEASON_RE: Regex = Regex::new(r"(?i)\bS(\d{1,2})\b").unwrap();
+    // Match absolute anime episodes after a dash: "Frieren - 12", "Frieren - 01-28", "Frieren - 01 ~ 28"
+    static ref ANIME_EPISODE_RE: Regex = Regex::new(r"\s-\s(\d{1,4})(?:\s?[-~]\s?(\d{1,4}))?(?:v\d)?\b").unwrap();
+    // Match complete-series and batch markers
+    static ref COMPLETE_RE: Regex = Regex::new(r"(?i)\b(Complete|Batch)\b").unwrap();
+    // Match a fansub group at the start ("[SubsPlease] ...")
+    static ref GROUP_PREFIX_RE: Regex = Regex::new(r"^\[([^\]]+)\]").unwrap();
+    // Match a scene group at the end ("...x264-GROUP")
+    static ref GROUP_SUFFIX_RE: Regex = Regex::new(r"-([A-Za-z0-9][A-Za-z0-9_]*)$").unwrap();
+    // Match trailing tags to strip before looking for a scene group ("[eztv]", ".mkv")
+    static ref TRAILING_TAG_RE: Regex = Regex::new(r"(?i)(\s*\[[^\]]*\]|\s*\([^)]*\)|\.(mkv|mp4|avi|m4v|ts))$").unwrap();
+    // Match a release year, possibly in parentheses ("2023", "(2023)")
+    static ref YEAR_RE: Regex = Regex::new(r"[(\[]?\b(19\d{2}|20\d{2})\b[)\]]?").unwrap();
+    // Match the start of bracketed tags ("[1080p]", "(Batch)")
+    static ref TAG_START_RE: Regex = Regex::new(r"[\[(]").unwrap();
+}
+
+/// Information parsed from a release name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReleaseInfo {
+    /// Quality (e.g., "1080p", "2160p")
+    pub quality: Option<String>,
+    /// HDR type (e.g., "HDR", "DV / HDR10")
+    pub hdr: Option<String>,
+    /// Video codec (e.g., "HEVC", "AVC 10bit")
+    pub video_codec: Option<String>,
+    /// Bit depth of the video (e.g., 10)
+    pub bit_depth: Option<u8>,
+    /// Audio format (e.g., "DTS-HD MA 7.1")
+    pub audio: Option<String>,
+    /// Source type (e.g., "BluRay", "WEB-DL")
+    pub source_type: Option<String>,
+    /// Release group (e.g., "NTb", "SubsPlease")
+    pub release_group: Option<String>,
+    /// Repack marker ("REPACK", "PROPER", "RERIP")
+    pub repack: Option<String>,
+    /// Edition (e.g., "Director's Cut", "Extended", "IMAX")
+    pub edition: Option<String>,
+    /// Two audio tracks (usually original language and English dub)
+    pub dual_audio: bool,
+    /// Several audio languages
+    pub multi_audio: bool,
+    /// Subtitle hint ("Multi-Subs", "English subs", "Hardcoded", "Subbed")
+    pub subtitles: Option<String>,
+    /// Season(s) covered
+    pub seasons: Option<RangeInclusive<u32>>,
+    /// Episode(s) covered
+    pub episodes: Option<RangeInclusive<u32>>,
+    /// Whether the release holds whole seasons rather than single episodes
+    pub season_pack: bool,
+}
+
+/// Parse a release name
+///
+/// The first line of `text` is the release (torrent) name; group, edition and episode
+/// information is only taken from it. Further lines (file names, addon labels) are
+/// searched for quality, codec, audio and source hints too.
+pub fn parse(text: &str) -> ReleaseInfo {
+    let name = text.lines().next().unwrap_or_default().trim();
+    let (seasons, episodes) = parse_episodes(name);
+    let season_pack = (seasons.is_some() && episodes.is_none())
+        || (COMPLETE_RE.is_match(name) && episodes.as_ref().is_none_or(|e| e.start() != e.end()));
+
+    ReleaseInfo {
+        quality: QUALITY_RE.find(text).map(|m| m.as_str().to_string()),
+        hdr: parse_hdr(text),
+        video_codec: parse_video_codec(text),
+        bit_depth: parse_bit_depth(text),
+        audio: parse_audio(text),
+        source_type: SOURCE_RE.find(text).map(|m| normalize_source(m.as_str())),
+        release_group: parse_release_group(name),
+        repack: REPACK_RE.find(name).map(|m| m.as_str().to_uppercase()),
+        edition: parse_edition(name),
+        dual_audio: DUAL_AUDIO_RE.is_match(name),
+        multi_audio: has_multi_audio(name),
+        subtitles: parse_subtitles(name),
+        seasons,
+        episodes,
+        season_pack,
+    }
+}
+
+/// Parse the title and year from a release name
+/// (e.g., "Blade.Runner.1982.The.Final.Cut.2160p..." -> ("Blade Runner", Some(1982)))
+///
+/// The title is everything before the year, episode, quality or bracketed tags.
+pub fn parse_title(name: &str) -> (String, Option<i32>) {
+    let name = name.lines().next().unwrap_or_default().trim();
+    let name = GROUP_PREFIX_RE.replace(name, "").replace(['.', '_'], " ");
+
+    // A year at the very start is part of the title ("1917 2019 1080p")
+    let year = YEAR_RE
+        .captures_iter(&name)
+        .find(|caps| caps.get(0).unwrap().start() > 0);
+    let year_start = year.as_ref().map(|caps| caps.get(0).unwrap().start());
+
+    let end = [
+        year_start,
+        SEASON_EPISODE_RE.find(&name).map(|m| m.start()),
+        CROSS_EPISODE_RE.find(&name).map(|m| m.start()),
+        SEASON_RANGE_RE.find(&name).map(|m| m.start()),
+        SEASON_WORD_RE.find(&name).map(|m| m.start()),
+        SEASON_RE.find(&name).map(|m| m.start()),
+        ANIME_EPISODE_RE.find(&name).map(|m| m.start()),
+        QUALITY_RE.find(&name).map(|m| m.start()),
+        TAG_START_RE.find(&name).map(|m| m.start()),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|&start| start > 0)
+    .min()
+    .unwrap_or(name.len());
+
+    let title = name[..end]
+        .trim_end_matches(|c: char| c.is_whitespace() || c == '-')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    // Only the year right after the title is the release year
+    let year = year
+        .filter(|_| year_start == Some(end))
+        .and_then(|caps| caps[1].parse().ok());
+    (title, year)
+}
+
+/// Short label for the seasons and episodes of a release ("S01E03-E05", "S01-S03", "E01-E28")
+pub fn episode_label(
+    seasons: Option<&RangeInclusive<u32>>,
+    episodes: Option<&RangeInclusive<u32>>,
+) -> Option<String> {
+    let range = |prefix: &str, r: &RangeInclusive<u32>| {
+        if r.start() == r.end() {
+            format!("{}{:02}", prefix, r.start())
+        } else {
+            format!("{}{:02}-{}{:02}", prefix, r.start(), prefix, r.end())
+        }
+    };
+
+    match (seasons, episodes) {
+        (Some(s), Some(e)) => Some(format!("{}{}", range("S", s), range("E", e))),
+        (Some(s), None) => Some(range("S", s)),
+        (None, Some(e)) => Some(range("E", e)),
+        (None, None) => None,
+    }
+}
+
+/// Parse the seasons and episodes named in a release name
+fn parse_episodes(name: &str) -> (Option<RangeInclusive<u32>>, Option<RangeInclusive<u32>>) {
+    let number = |m: Option<regex::Match>| m.and_then(|m| m.as_str().parse::<u32>().ok());
+    let range = |start: u32, end: Option<u32>| {
+        let end = end.filter(|&end| end >= start).unwrap_or(start);
+        start..=end
+    };
+
+    if let Some(caps) = SEASON_EPISODE_RE.captures(name) {
+        let season = number(caps.get(1)).unwrap_or(1);
+        let first = number(caps.get(2)).unwrap_or(1);
+        let last = number(caps.get(3)).or_else(|| number(caps.get(4)));
+        return (Some(season..=season), Some(range(first, last)));
+    }
+
+    if let Some(caps) = CROSS_EPISODE_RE.captures(name) {
+        let season = number(caps.get(1)).unwrap_or(1);
+        let episode = number(caps.get(2)).unwrap_or(1);
+        return (Some(season..=season), Some(episode..=episode));
+    }
+
+    let seasons = SEASON_RANGE_RE
+        .captures(name)
+        .or_else(|| SEASON_WORD_RE.captures(name))
+        .and_then(|caps| Some(range(number(caps.get(1))?, number(caps.get(2)))))
+        .or_else(|| {
+            let season = number(SEASON_RE.captures(name)?.get(1))?;
+            Some(season..=season)
+        });
+    if seasons.is_some() {
+        return (seasons, None);
+    }
+
+    // Absolute episode numbers (anime), skipping years
+    let episodes = ANIME_EPISODE_RE
+        .captures(name)
+        .and_then(|caps| Some(range(number(caps.get(1))?, number(caps.get(2)))))
+        .filter(|episodes| !(1900..=2099).contains(episodes.start()));
+    (None, episodes)
+}
+
+/// Parse the release group from a release name
+fn parse_release_group(name: &str) -> Option<String> {
+    // Fansub groups lead with their name in brackets
+    if let Some(caps) = GROUP_PREFIX_RE.captures(name) {
+        let group = caps[1].trim();
+        if !group.is_empty() && !QUALITY_RE.is_match(group) {
+            return Some(group.to_string());
+        }
+    }
+
+    // YTS tags its releases at the end
+    if name.to_uppercase().contains("[YTS") {
+        return Some("YTS".to_string());
+    }
+
+    // Scene groups trail after a dash, possibly followed by site tags or an extension
+    let mut stripped = name.trim();
+    while let Some(m) = TRAILING_TAG_RE.find(stripped) {
+        stripped = stripped[..m.start()].trim_end();
+    }
+    let group = GROUP_SUFFIX_RE.captures(stripped)?.get(1)?.as_str();
+    let is_source_suffix = matches!(group.to_uppercase().as_str(), "DL" | "RIP" | "RAY");
+    (!is_source_suffix).then(|| group.to_string())
+}
+
+/// Parse HDR type from title, normalizing variants
+fn parse_hdr(text: &str) -> Option<String> {
+    let mut hdr_types = Vec::new();
+
+    for cap in HDR_RE.find_iter(text) {
+        let hdr = match cap.as_str().to_uppercase().as_str() {
+            "DOVI" | "DV" | "DOLBYVISION" | "DOLBY VISION" | "DOLBY.VISION" => "DV",
+            "HDR10+" => "HDR10+",
+            "HDR10" => "HDR10",
+            "HDR" => "HDR",
+            _ => continue,
+        };
+        if !hdr_types.contains(&hdr.to_string()) {
+            hdr_types.push(hdr.to_string());
+        }
+    }
+
+    if hdr_types.is_empty() {
+        None
+    } else {
+        Some(hdr_types.join(" / "))
+    }
+}
+
+/// Parse video codec from title, normalizing variants
+fn parse_video_codec(text: &str) -> Option<String> {
+    let mut codecs = Vec::new();
+
+    for cap in VIDEO_CODEC_RE.find_iter(text) {
+        let codec = match cap.as_str().to_uppercase().replace('.', "").as_str() {
+            "HEVC" | "H265" | "X265" => "HEVC",
+            "AVC" | "H264" | "X264" => "AVC",
+            "AV1" => "AV1",
+            "VC-1" => "VC-1",
+            "10BIT" | "10-BIT" => "10bit",
+            _ => continue,
+        };
+        if !codecs.contains(&codec.to_string()) {
+            codecs.push(codec.to_string());
+        }
+    }
+
+    if codecs.is_empty() {
+        None
+    } else {
+        Some(codecs.join(" "))
+    }
+}
+
+/// Parse video bit depth from title
+fn parse_bit_depth(text: &str) -> Option<u8> {
+    let caps = BIT_DEPTH_RE.captures(text)?;
+    caps.get(1).or_else(|| caps.get(2))?.as_str().parse().ok()
+}
+
+/// Parse audio format from title
+fn parse_audio(text: &str) -> Option<String> {
+    let mut audio_parts = Vec::new();
+
+    // Find audio codec
+    if let Some(cap) = AUDIO_RE.find(text) {
+        let codec = cap.as_str().to_uppercase();
+        let normalized = if codec.contains("DTS-HD") || codec.contains("DTS HD") {
+            "DTS-HD MA"
+        } else if codec.contains("TRUEHD") {
+            "TrueHD"
+        } else if codec.contains("ATMOS") {
+            "Atmos"
+        } else if codec.contains("EAC3") || codec.contains("E-AC-3") || codec.contains("DD+") {
+            "EAC3"
+        } else if codec.contains("AC3") || codec.contains("DD") {
+            "AC3"
+        } else if codec.contains("AAC") {
+            "AAC"
+        } else if codec.contains("FLAC") {
+            "FLAC"
+        } else if codec.contains("DTS") {
+            "DTS"
+        } else if codec.contains("LPCM") {
+            "LPCM"
+        } else {
+            return None;
+        };
+        audio_parts.push(normalized.to_string());
+    }
+
+    // Check for Atmos separately (can appear with other codecs like TrueHD Atmos)
+    if text.to_uppercase().contains("ATMOS") && !audio_parts.contains(&"Atmos".to_string()) {
+        audio_parts.push("Atmos".to_string());
+    }
+
+    // Find channel configuration
+    if let Some(cap) = AUDIO_CHANNELS_RE.find(text) {
+        audio_parts.push(cap.as_str().to_string());
+    }
+
+    if audio_parts.is_empty() {
+        None
+    } else {
+        Some(audio_parts.join(" "))
+    }
+}
+
+/// Parse the edition from a release name
+fn parse_edition(name: &str) -> Option<String> {
+    let caps = EDITION_RE.captures(name)?;
+    let edition = caps.get(1).or_else(|| caps.get(2))?;
+    Some(normalize_edition(edition.as_str()))
+}
+
+/// Parse the subtitle hint from a release name
+fn parse_subtitles(name: &str) -> Option<String> {
+    let caps = SUBTITLES_RE.captures(name)?;
+    let hint = (1..=3).find_map(|i| caps.get(i))?;
+    Some(normalize_subtitles(hint.as_str()))
+}
+
+/// Whether a release name has multiple audio languages ("MULTi" but not "Multi-Subs")
+fn has_multi_audio(name: &str) -> bool {
+    MULTI_AUDIO_RE.find_iter(name).any(|m| {
+        let rest = name[m.end()..].trim_start_matches([' ', '.', '-', '_']);
+        !rest.to_lowercase().starts_with("sub")
+    })
+}
+
+/// Normalize source type string
+fn normalize_source(source: &str) -> String {
+    match source.to_uppercase().replace(['-', ' ', '.'], "").as_str() {
+        "UHDBLURAY" => "UHD BluRay".to_string(),
+        "BLURAY" => "BluRay".to_string(),
+        "BDRIP" | "BRRIP" => "BDRip".to_string(),
+        "WEBDL" => "WEB-DL".to_string(),
+        "WEBRIP" => "WEBRip".to_string(),
+        "REMUX" => "REMUX".to_string(),
+        "HDTV" => "HDTV".to_string(),
+        "DVDRIP" => "DVDRip".to_string(),
+        _ => source.to_string(),
+    }
+}
+
+/// Normalize edition string
+fn normalize_edition(edition: &str) -> String {
+    let key = edition.to_uppercase().replace(['.', '\''], " ");
+    let key: Vec<&str> = key.split_whitespace().collect();
+    match key.as_slice() {
+        ["DIRECTOR", "S", "CUT"] | ["DIRECTORS", "CUT"] | ["DC"] => "Director's Cut",
+        ["EXTENDED", ..] => "Extended",
+        ["IMAX"] => "IMAX",
+        ["UNRATED"] => "Unrated",
+        ["UNCUT"] => "Uncut",
+        ["THEATRICAL", ..] => "Theatrical",
+        ["REMASTERED"] => "Remastered",
+        ["CRITERION"] => "Criterion",
+        ["SPECIAL", ..] => "Special Edition",
+        ["ULTIMATE", ..] => "Ultimate Edition",
+        _ => return edition.to_string(),
+    }
+    .to_string()
+}
+
+/// Normalize subtitle hint
+fn normalize_subtitles(hint: &str) -> String {
+    let key = hint.to_uppercase().replace([' ', '.', '-'], "");
+    if key.starts_with("MULTI") {
+        "Multi-Subs"
+    } else if key.starts_with("ESUB") || key.starts_with("ENG") {
+        "English subs"
+    } else if key.starts_with("HARD") || key == "HC" {
+        "Hardcoded"
+    } else {
+        "Subbed"
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Release names as Torrentio returns them (first line of the stream title)
+    const FIXTURES: &[&str] = &[
+        "The.Last.of.Us.S01E03.Long.Long.Time.2160p.MAX.WEB-DL.DDP5.1.Atmos.DV.HDR.H.265-FLUX",
+        "[SubsPlease] Sousou no Frieren - 12 (1080p) [7F3A1B2C].mkv",
+        "[Judas] Sousou no Frieren (Season 1) [1080p][HEVC x265 10bit][Dual-Audio][Multi-Subs] (Batch)",
+        "Blade.Runner.1982.The.Final.Cut.REMASTERED.2160p.UHD.BluRay.REMUX.HDR.HEVC.TrueHD.Atmos.7.1-FGT",
+        "The Lord of the Rings The Fellowship of the Ring 2001 EXTENDED 1080p BluRay x265 10bit AAC 7.1-Tigole",
+        "Oppenheimer (2023) [2160p] [4K] [WEB] [5.1] [YTS.MX]",
+        "Breaking.Bad.S01-S05.COMPLETE.1080p.BluRay.x264-ROVERS[rartv]",
+        "Shogun.2024.S01E01E02.REPACK.1080p.WEB.h264-ETHEL[EZTVx.to]",
+        "Avatar.The.Way.of.Water.2022.IMAX.1080p.WEB-DL.DDP5.1.Atmos.H.264-CMRG",
+        "Kingdom.of.Heaven.2005.Directors.Cut.1080p.BluRay.DTS-HD.MA.5.1.x264-HDMaNiAcS",
+        "Dune.Part.Two.2024.MULTi.1080p.WEB.H264-FW",
+        "Doctor Who 2005 S13E01-E06 720p HDTV x264 ESubs",
+        "Friends - 2x03 - The One Where Heckles Dies.avi",
+        "Fargo.Season.2.1080p.BluRay.x265-PSA",
+        "Cowboy Bebop - 01 ~ 26 [BD 1080p Hi10P FLAC] [Dual Audio]",
+        "DC's Legends of Tomorrow S03E05 1080p WEB-DL DD5.1 H.264-KiNGS",
+        "DC.League.of.Super-Pets.2022.1080p.WEBRip.x264.AAC5.1-YTS",
+        "Daredevil.2003.DC.1080p.BluRay.x264-HD1080",
+        "Spengler.Cup.2023.HC.Davos.vs.HC.Lugano.720p.HDTV.x264-GROUP",
+        "Send.in.the.Subs.2019.1080p.WEBRip.x264-GROUP",
+        "The.Marvels.2023.HC.1080p.HDRip.x264-GROUP",
+    ];
+
+    fn fixture(index: usize) -> ReleaseInfo {
+        parse(FIXTURES[index])
+    }
+
+    #[test]
+    fn test_fixture_corpus_parses() {
+        // Every fixture yields at least a release group or episode information
+        for name in FIXTURES {
+            let info = parse(name);
+            assert!(
+                info.release_group.is_some() || info.episodes.is_some(),
+                "{}: {:?}",
+                name,
+                info
+            );
+        }
+    }
+
+    #[test]
+    fn test_scene_episode() {
+        let info = fixture(0);
+        assert_eq!(info.quality.as_deref(), Some("2160p"));
+        assert_eq!(info.hdr.as_deref(), Some("DV / HDR"));
+        assert_eq!(info.video_codec.as_deref(), Some("HEVC"));
+        assert_eq!(info.source_type.as_deref(), Some("WEB-DL"));
+        assert_eq!(info.release_group.as_deref(), Some("FLUX"));
+        assert_eq!(info.seasons, Some(1..=1));
+        assert_eq!(info.episodes, Some(3..=3));
+        assert!(!info.season_pack);
+        assert_eq!(info.repack, None);
+        assert_eq!(info.edition, None);
+    }
+
+    #[test]
+    fn test_fansub_releases() {
+        let info = fixture(1);
+        assert_eq!(info.release_group.as_deref(), Some("SubsPlease"));
+        assert_eq!(info.episodes, Some(12..=12));
+        assert_eq!(info.seasons, None);
+        assert!(!info.season_pack);
+
+        let info = fixture(2);
+        assert_eq!(info.release_group.as_deref(), Some("Judas"));
+        assert_eq!(info.seasons, Some(1..=1));
+        assert!(info.season_pack);
+        assert_eq!(info.bit_depth, Some(10));
+        assert!(info.dual_audio);
+        assert!(!info.multi_audio);
+        assert_eq!(info.subtitles.as_deref(), Some("Multi-Subs"));
+
+        let info = fixture(14);
+        assert_eq!(info.episodes, Some(1..=26));
+        assert_eq!(info.bit_depth, Some(10));
+        assert!(info.dual_audio);
+        assert_eq!(info.audio.as_deref(), Some("FLAC"));
+    }
+
+    #[test]
+    fn test_editions() {
+        assert_eq!(fixture(3).edition.as_deref(), Some("Remastered"));
+        assert_eq!(fixture(3).release_group.as_deref(), Some("FGT"));
+        assert_eq!(fixture(4).edition.as_deref(), Some("Extended"));
+        assert_eq!(fixture(4).bit_depth, Some(10));
+        assert_eq!(fixture(4).release_group.as_deref(), Some("Tigole"));
+        assert_eq!(fixture(8).edition.as_deref(), Some("IMAX"));
+        assert_eq!(fixture(9).edition.as_deref(), Some("Director's Cut"));
+        assert_eq!(fixture(9).audio.as_deref(), Some("DTS-HD MA 5.1"));
+
+        // "DC" in a title is not Director's Cut
+        assert_eq!(fixture(15).edition, None);
+        assert_eq!(fixture(15).release_group.as_deref(), Some("KiNGS"));
+        assert_eq!(fixture(16).edition, None);
+        assert_eq!(fixture(17).edition.as_deref(), Some("Director's Cut"));
+    }
+
+    #[test]
+    fn test_groups_and_repacks() {
+        assert_eq!(fixture(5).release_group.as_deref(), Some("YTS"));
+        assert_eq!(fixture(5).episodes, None);
+
+        let info = fixture(6);
+        assert_eq!(info.release_group.as_deref(), Some("ROVERS"));
+        assert_eq!(info.seasons, Some(1..=5));
+        assert!(info.season_pack);
+
+        let info = fixture(7);
+        assert_eq!(info.release_group.as_deref(), Some("ETHEL"));
+        assert_eq!(info.repack.as_deref(), Some("REPACK"));
+        assert_eq!(info.episodes, Some(1..=2));
+        assert!(!info.season_pack);
+    }
+
+    #[test]
+    fn test_audio_and_subtitle_hints() {
+        let info = fixture(10);
+        assert!(info.multi_audio);
+        assert_eq!(info.subtitles, None);
+
+        let info = fixture(11);
+        assert_eq!(info.seasons, Some(13..=13));
+        assert_eq!(info.episodes, Some(1..=6));
+        assert_eq!(info.subtitles.as_deref(), Some("English subs"));
+        assert!(!info.multi_audio);
+
+        // Multi-Subs is not multi audio
+        assert!(!parse("Show S01E01 1080p Multi-Subs").multi_audio);
+
+        // "HC" and "Subs" in names are not subtitle hints
+        assert_eq!(fixture(18).subtitles, None);
+        assert_eq!(fixture(19).subtitles, None);
+        assert_eq!(fixture(20).subtitles.as_deref(), Some("Hardcoded"));
+        assert_eq!(
+            parse("Movie 2023 1080p HC WEB x264").subtitles.as_deref(),
+            Some("Hardcoded")
+        );
+    }
+
+    #[test]
+    fn test_other_episode_formats() {
+        let info = fixture(12);
+        assert_eq!(info.seasons, Some(2..=2));
+        assert_eq!(info.episodes, Some(3..=3));
+
+        let info = fixture(13);
+        assert_eq!(info.seasons, Some(2..=2));
+        assert!(info.season_pack);
+        assert_eq!(info.release_group.as_deref(), Some("PSA"));
+
+        // Years after a dash are not episodes
+        assert_eq!(parse("Heat - 1995 - 1080p").episodes, None);
+    }
+
+    #[test]
+    fn test_season_pack_detection() {
+        let pack = |name: &str| parse(name).season_pack;
+        assert!(pack(
+            "Show.S01.1080p.WEB-DL.x264\nShow.S01E03.1080p.mkv\n👤 40 💾 1.1 GB"
+        ));
+        assert!(pack("Show Season 2 Complete 720p"));
+        assert!(pack("Show S01-S03 1080p BluRay"));
+        assert!(!pack("Show.S01E03.1080p.WEB-DL\n👤 40 💾 1.1 GB"));
+        assert!(!pack("Show 1x03 720p HDTV"));
+        assert!(!pack("Movie.2024.1080p.BluRay.x265"));
+    }
+
+    #[test]
+    fn test_parse_title() {
+        let title = |index: usize| parse_title(FIXTURES[index]);
+        assert_eq!(title(0), ("The Last of Us".to_string(), None));
+        assert_eq!(title(1), ("Sousou no Frieren".to_string(), None));
+        assert_eq!(title(3), ("Blade Runner".to_string(), Some(1982)));
+        assert_eq!(title(5), ("Oppenheimer".to_string(), Some(2023)));
+        assert_eq!(title(11), ("Doctor Who".to_string(), Some(2005)));
+        assert_eq!(title(12), ("Friends".to_string(), None));
+        assert_eq!(title(13), ("Fargo".to_string(), None));
+        assert_eq!(
+            parse_title("1917.2019.1080p.BluRay.x264-SPARKS.mkv"),
+            ("1917".to_string(), Some(2019))
+        );
+    }
+
+    #[test]
+    fn test_episode_label() {
+        assert_eq!(
+            episode_label(Some(&(1..=1)), Some(&(3..=5))).as_deref(),
+            Some("S01E03-E05")
+        );
+        assert_eq!(
+            episode_label(Some(&(1..=1)), Some(&(3..=3))).as_deref(),
+            Some("S01E03")
+        );
+        assert_eq!(
+            episode_label(Some(&(1..=3)), None).as_deref(),
+            Some("S01-S03")
+        );
+        assert_eq!(
+            episode_label(None, Some(&(1..=28))).as_deref(),
+            Some("E01-E28")
+        );
+        assert_eq!(episode_label(None, None), None);
+    }
+}
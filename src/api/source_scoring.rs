@@ -1,8 +1,13 @@
+use std::collections::{HashMap, HashSet};
+
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use crate::api::language::language_name;
 use crate::api::media::MediaType;
-use crate::api::torrentio::Stream;
+use crate::api::release_name;
+use crate::api::torrentio::{Stream, StreamChoice};
+use crate::config::{PlaybackCapabilities, PlayerConfig, ScoringConfig, ScoringProfile};
 
 lazy_static! {
     /// Keywords that strongly suggest a source is not the actual main content
@@ -10,11 +15,163 @@ lazy_static! {
         Regex::new(r"(?i)\b(trailer|promo|sample|preview|clip|extra|bonus|teaser|opening|ending|op|ed)\b").unwrap();
 }
 
+/// Penalty for sources the playback device can't handle, ranking them below playable ones
+const UNSUPPORTED_PENALTY: f64 = -5000.0;
+
+/// Penalty for sources that recently failed to play, keeping them out of the
+/// recommendations
+const FAILED_PENALTY: f64 = -3000.0;
+
+/// Name of the profile with the stock weights
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Built-in profile names, in the order they are cycled through
+const BUILTIN_PROFILES: [&str; 4] = [DEFAULT_PROFILE, "bandwidth-saver", "home-theater", "anime"];
+
 /// Options for scoring a stream
 #[derive(Debug, Clone)]
 pub struct ScoringOptions {
     pub media_type: MediaType,
     pub is_anime: bool,
+    /// Name of the scoring profile (shown on the sources screen)
+    pub profile_name: String,
+    pub profile: ScoringProfile,
+    /// Preferred audio languages by name, most preferred first
+    pub preferred_audio: Vec<String>,
+    /// Preferred subtitle languages by name, most preferred first
+    pub preferred_subtitles: Vec<String>,
+    /// What the playback device can handle
+    pub capabilities: PlaybackCapabilities,
+    /// Stream last played for the same movie or show
+    pub previous_choice: Option<StreamChoice>,
+    /// Keys (`Stream::source_key`) of sources that recently failed to play
+    pub failed_sources: HashSet<String>,
+}
+
+impl ScoringOptions {
+    /// Options for a media item using the named profile, or the configured profile
+    /// for its media type if `profile_name` is None.
+    ///
+    /// Unknown profile names fall back to the default profile.
+    pub fn new(
+        config: &ScoringConfig,
+        media_type: MediaType,
+        is_anime: bool,
+        profile_name: Option<&str>,
+    ) -> Self {
+        let name = profile_name.unwrap_or_else(|| match (&config.anime_profile, media_type) {
+            (Some(anime), _) if is_anime => anime,
+            (_, MediaType::Movie) => &config.movie_profile,
+            (_, MediaType::TvShow) => &config.tv_profile,
+        });
+
+        let (profile_name, profile) = match find_profile(config, name) {
+            Some(profile) => (name.to_string(), profile),
+            None => (DEFAULT_PROFILE.to_string(), ScoringProfile::default()),
+        };
+
+        Self {
+            media_type,
+            is_anime,
+            profile_name,
+            profile,
+            preferred_audio: Vec::new(),
+            preferred_subtitles: Vec::new(),
+            capabilities: PlaybackCapabilities::default(),
+            previous_choice: None,
+            failed_sources: HashSet::new(),
+        }
+    }
+
+    /// Favor the player's preferred audio and subtitle languages
+    pub fn with_languages(mut self, player: &PlayerConfig) -> Self {
+        let names = |languages: &[String]| languages.iter().map(|l| language_name(l)).collect();
+        self.preferred_audio = names(&player.preferred_audio_languages);
+        self.preferred_subtitles = names(&player.preferred_subtitle_languages);
+        self
+    }
+
+    /// Penalize sources beyond the playback device's capabilities
+    pub fn with_capabilities(mut self, capabilities: &PlaybackCapabilities) -> Self {
+        self.capabilities = capabilities.clone();
+        self
+    }
+
+    /// Favor the release last played for the same movie or show
+    pub fn with_previous_choice(mut self, choice: Option<StreamChoice>) -> Self {
+        self.previous_choice = choice;
+        self
+    }
+
+    /// Demote sources that recently failed to play
+    pub fn with_failed_sources(mut self, failed_sources: HashSet<String>) -> Self {
+        self.failed_sources = failed_sources;
+        self
+    }
+}
+
+/// Built-in scoring profile by name
+fn builtin_profile(name: &str) -> Option<ScoringProfile> {
+    let mut profile = ScoringProfile::default();
+    match name {
+        DEFAULT_PROFILE => {}
+        "bandwidth-saver" => {
+            // Small files first: 720p wins, and size is punished hard
+            profile.quality.uhd = 200.0;
+            profile.quality.full_hd = 800.0;
+            profile.quality.hd = 1000.0;
+            profile.quality.other = 600.0;
+            profile.size_weight = 150.0;
+            profile.size_exponent = 2.0;
+            profile.codecs =
+                HashMap::from([("HEVC".to_string(), 150.0), ("AV1".to_string(), 150.0)]);
+        }
+        "home-theater" => {
+            // Best picture and sound, size barely matters
+            profile.quality.uhd = 1400.0;
+            profile.quality.full_hd = 1000.0;
+            profile.quality.hd = 500.0;
+            profile.quality.other = 200.0;
+            profile.size_weight = 5.0;
+            profile.size_exponent = 1.0;
+            profile.hdr_bonus = 250.0;
+            profile.atmos_bonus = 150.0;
+            profile.codecs = HashMap::from([("HEVC".to_string(), 50.0)]);
+        }
+        "anime" => {
+            // Dedicated anime trackers and efficient 10-bit HEVC encodes
+            profile.quality.uhd = 700.0;
+            profile.codecs = HashMap::from([("HEVC".to_string(), 100.0)]);
+            profile.providers = HashMap::from([("nyaasi".to_string(), 200.0)]);
+        }
+        _ => return None,
+    }
+    Some(profile)
+}
+
+/// Look up a profile by name; profiles from the config override built-in ones
+pub fn find_profile(config: &ScoringConfig, name: &str) -> Option<ScoringProfile> {
+    config
+        .profiles
+        .get(name)
+        .cloned()
+        .or_else(|| builtin_profile(name))
+}
+
+/// All profile names: the built-in ones, then custom profiles alphabetically
+pub fn profile_names(config: &ScoringConfig) -> Vec<String> {
+    let mut custom: Vec<&String> = config
+        .profiles
+        .keys()
+        .filter(|name| !BUILTIN_PROFILES.contains(&name.as_str()))
+        .collect();
+    custom.sort();
+
+    BUILTIN_PROFILES
+        .iter()
+        .map(|name| name.to_string())
+        .chain(custom.into_iter().cloned())
+        .collect()
 }
 
 /// Filter out potential trailers based on size, quality, and keywords
@@ -62,55 +219,219 @@ pub fn is_likely_trailer(stream: &Stream, media_type: MediaType) -> bool {
     }
 }
 
-/// Calculate a recommendation score for a stream.
+/// Resolution rank of a quality string (4 for 4K, 1 for 480p and below, 0 if unknown)
+fn resolution_rank(quality: &str) -> u8 {
+    let quality = quality.to_lowercase();
+    if quality.contains("2160p") || quality.contains("4k") {
+        4
+    } else if quality.contains("1080p") {
+        3
+    } else if quality.contains("720p") {
+        2
+    } else if quality.contains("480p") || quality.contains("360p") {
+        1
+    } else {
+        0
+    }
+}
+
+/// Typical runtime in minutes, for estimating a source's bitrate from its size
+fn typical_runtime_minutes(media_type: MediaType) -> f64 {
+    match media_type {
+        MediaType::Movie => 120.0,
+        MediaType::TvShow => 45.0,
+    }
+}
+
+/// Features of a stream the playback device can't handle (e.g. "2160p", "HDR", "HEVC").
 ///
-/// Higher scores are better. Mirrors the scoring algorithm from Mira:
+/// Unknown properties are assumed to be playable.
+pub fn unsupported_features(
+    stream: &Stream,
+    capabilities: &PlaybackCapabilities,
+    media_type: MediaType,
+) -> Vec<String> {
+    let mut features = Vec::new();
+
+    // Resolution
+    if let (Some(max), Some(quality)) = (&capabilities.max_resolution, &stream.quality) {
+        if resolution_rank(quality) > resolution_rank(max) {
+            features.push(quality.clone());
+        }
+    }
+
+    // HDR and Dolby Vision
+    if !capabilities.hdr {
+        if let Some(hdr) = &stream.hdr {
+            features.push(hdr.clone());
+        }
+    }
+
+    // Codecs, compared in their normalized form ("x265" -> "HEVC")
+    if !capabilities.codecs.is_empty() {
+        let decodable: Vec<String> = capabilities
+            .codecs
+            .iter()
+            .map(|codec| {
+                release_name::parse(codec)
+                    .video_codec
+                    .unwrap_or(codec.clone())
+            })
+            .collect();
+        for codec in stream.video_codec.iter().flat_map(|c| c.split_whitespace()) {
+            if codec != "10bit" && !decodable.iter().any(|d| d.eq_ignore_ascii_case(codec)) {
+                features.push(codec.to_string());
+            }
+        }
+    }
+
+    // Bit depth
+    if let (Some(max), Some(depth)) = (capabilities.max_bit_depth, stream.bit_depth) {
+        if depth > max {
+            features.push(format!("{}-bit", depth));
+        }
+    }
+
+    // Average bitrate over a typical runtime
+    if let Some(max) = capabilities.max_bitrate_mbps {
+        if stream.size_bytes < u64::MAX {
+            let seconds = typical_runtime_minutes(media_type) * 60.0;
+            let mbps = stream.size_bytes as f64 * 8.0 / seconds / 1_000_000.0;
+            if mbps > max {
+                features.push(format!("~{:.0} Mbit/s", mbps));
+            }
+        }
+    }
+
+    features
+}
+
+/// Contribution of each scoring factor to a source's score
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScoreBreakdown {
+    /// -10000 for likely trailers and samples (the other factors are then zero)
+    pub trailer_penalty: f64,
+    /// -5000 for sources the playback device can't handle
+    pub device_penalty: f64,
+    /// -3000 for sources that recently failed to play
+    pub failure_penalty: f64,
+    /// What the playback device can't handle (e.g. "HDR", "10-bit")
+    pub unsupported: Vec<String>,
+    pub quality: f64,
+    pub provider: f64,
+    pub cached: f64,
+    pub languages: f64,
+    pub seeders: f64,
+    /// Size penalty (zero or negative)
+    pub size: f64,
+    pub codec: f64,
+    pub hdr: f64,
+    pub atmos: f64,
+    /// Bonus for a preferred audio language
+    pub audio_language: f64,
+    /// Bonus for preferred subtitles
+    pub subtitle_language: f64,
+    /// Bonus for the release last played for the same movie or show
+    pub continuity: f64,
+}
+
+impl ScoreBreakdown {
+    /// Total score
+    pub fn total(&self) -> f64 {
+        self.factors().iter().map(|(_, points)| points).sum()
+    }
+
+    /// Factors with their labels, in scoring order
+    pub fn factors(&self) -> [(&'static str, f64); 15] {
+        [
+            ("trailer", self.trailer_penalty),
+            ("device", self.device_penalty),
+            ("failed", self.failure_penalty),
+            ("quality", self.quality),
+            ("provider", self.provider),
+            ("cached", self.cached),
+            ("languages", self.languages),
+            ("seeders", self.seeders),
+            ("size", self.size),
+            ("codec", self.codec),
+            ("HDR", self.hdr),
+            ("Atmos", self.atmos),
+            ("audio lang", self.audio_language),
+            ("subs lang", self.subtitle_language),
+            ("same release", self.continuity),
+        ]
+    }
+}
+
+/// Calculate a recommendation score for a stream, broken down by factor.
+///
+/// Higher scores are better. The weights come from the scoring profile; the
+/// default profile mirrors the scoring algorithm from Mira:
 /// - Trailer penalty: -10000
 /// - Quality: 1080p +1000, 720p +800, 4K +600, other +400
 /// - Cache bonus: +1000 if cached on Real-Debrid
 /// - Language: +30 per language detected
 /// - Seeder bonus: log2(seeders + 1) * 50 (logarithmic)
 /// - Size penalty: -(sizeGB ^ 1.5) * 80 (exponential)
-pub fn calculate_source_score(stream: &Stream, options: &ScoringOptions) -> f64 {
+///
+/// Profiles can also reward codecs, HDR, Atmos audio and providers. Sources the
+/// playback device can't handle get -5000, and sources that recently failed to
+/// play get -3000.
+pub fn calculate_source_score(stream: &Stream, options: &ScoringOptions) -> ScoreBreakdown {
     // 1. Trailer penalty (massive penalty)
     if is_likely_trailer(stream, options.media_type) {
-        return -10000.0;
+        return ScoreBreakdown {
+            trailer_penalty: -10000.0,
+            ..Default::default()
+        };
     }
 
-    let mut score: f64 = 0.0;
+    let profile = &options.profile;
+    let mut score = ScoreBreakdown {
+        unsupported: unsupported_features(stream, &options.capabilities, options.media_type),
+        ..Default::default()
+    };
+    if !score.unsupported.is_empty() {
+        score.device_penalty = UNSUPPORTED_PENALTY;
+    }
+    if options.failed_sources.contains(&stream.source_key()) {
+        score.failure_penalty = FAILED_PENALTY;
+    }
 
-    // 2. Quality points - Prefer 1080p
+    // 2. Quality points
     let quality = stream.quality.as_deref().unwrap_or("").to_lowercase();
 
-    if quality.contains("1080p") {
-        score += 1000.0;
+    score.quality = if quality.contains("1080p") {
+        profile.quality.full_hd
     } else if quality.contains("720p") {
-        score += 800.0;
+        profile.quality.hd
     } else if quality.contains("2160p") || quality.contains("4k") {
-        score += 600.0; // Lower score for 4K to favor 1080p/720p
+        profile.quality.uhd
     } else {
-        score += 400.0;
-    }
+        profile.quality.other
+    };
 
-    // 3. Provider bonus (nyaasi for anime -- currently +0 in Mira, reserved for future)
-    if options.is_anime && stream.provider.to_lowercase().contains("nyaa") {
-        // Reserved for future use
-    }
+    // 3. Provider bonus
+    let provider = stream.provider.to_lowercase();
+    score.provider = profile
+        .providers
+        .iter()
+        .filter(|(name, _)| provider.contains(&name.to_lowercase()))
+        .map(|(_, bonus)| bonus)
+        .sum();
 
     // 4. Caching bonus (essential for smooth experience)
     if stream.is_cached {
-        score += 1000.0;
+        score.cached = profile.cached_bonus;
     }
 
     // 5. Language points
-    if !stream.languages.is_empty() {
-        score += stream.languages.len() as f64 * 30.0;
-    }
+    score.languages = stream.languages.len() as f64 * profile.language_bonus;
 
     // 6. Seeder bonus (logarithmic)
     if let Some(seeders) = stream.seeders {
         if seeders > 0 {
-            score += (seeders as f64 + 1.0).log2() * 50.0;
+            score.seeders = (seeders as f64 + 1.0).log2() * profile.seeder_weight;
         }
     }
 
@@ -118,12 +439,115 @@ pub fn calculate_source_score(stream: &Stream, options: &ScoringOptions) -> f64
     // Penalty grows exponentially to avoid excessively large files
     if stream.size_bytes < u64::MAX {
         let size_gb = stream.size_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
-        score -= size_gb.powf(1.5) * 80.0;
+        score.size = -size_gb.powf(profile.size_exponent) * profile.size_weight;
+    }
+
+    // 8. Codec, HDR and Atmos preferences
+    if let Some(codecs) = &stream.video_codec {
+        score.codec = codecs
+            .split_whitespace()
+            .filter_map(|codec| {
+                profile
+                    .codecs
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(codec))
+                    .map(|(_, bonus)| bonus)
+            })
+            .sum();
+    }
+    if stream.hdr.is_some() {
+        score.hdr = profile.hdr_bonus;
+    }
+    if stream
+        .audio
+        .as_deref()
+        .is_some_and(|audio| audio.to_lowercase().contains("atmos"))
+    {
+        score.atmos = profile.atmos_bonus;
+    }
+
+    // 9. Preferred languages: full bonus for the first choice, less for later ones
+    let audio = audio_languages(stream, options.is_anime);
+    if let Some(rank) = options
+        .preferred_audio
+        .iter()
+        .position(|preferred| audio.iter().any(|l| l.eq_ignore_ascii_case(preferred)))
+    {
+        score.audio_language = profile.preferred_audio_bonus / (rank + 1) as f64;
+    }
+    if let Some(rank) = subtitle_preference_rank(stream, options) {
+        score.subtitle_language = profile.preferred_subtitle_bonus / (rank + 1) as f64;
+    }
+
+    // 10. Continuity with the release played last time
+    if let Some(previous) = &options.previous_choice {
+        score.continuity = profile.continuity_bonus * continuity(stream, previous);
     }
 
     score
 }
 
+/// How closely a stream continues a previous choice: 1.0 for the same torrent
+/// (e.g. a season pack) or the same release group and quality, 0.5 for the same
+/// group in another quality
+fn continuity(stream: &Stream, previous: &StreamChoice) -> f64 {
+    let same = |a: &Option<String>, b: &Option<String>| match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        _ => false,
+    };
+
+    if same(&stream.info_hash, &previous.info_hash) {
+        1.0
+    } else if same(&stream.release_group, &previous.release_group) {
+        if same(&stream.quality, &previous.quality) {
+            1.0
+        } else {
+            0.5
+        }
+    } else {
+        0.0
+    }
+}
+
+/// Audio languages of a stream.
+///
+/// Torrentio rarely flags English releases, so a release without flags is taken to
+/// be English, or Japanese for anime. Dual-audio anime carry the Japanese track and
+/// an English dub.
+fn audio_languages(stream: &Stream, is_anime: bool) -> Vec<&str> {
+    let mut languages: Vec<&str> = stream.languages.iter().map(String::as_str).collect();
+    if is_anime && (stream.dual_audio || languages.is_empty()) {
+        languages.push("Japanese");
+    }
+    if (is_anime && stream.dual_audio) || (!is_anime && languages.is_empty()) {
+        languages.push("English");
+    }
+    languages
+}
+
+/// Position of the first preferred subtitle language a stream has.
+///
+/// Multi-sub releases match any preference. Anime fansubs are English unless
+/// they say otherwise.
+fn subtitle_preference_rank(stream: &Stream, options: &ScoringOptions) -> Option<usize> {
+    if options.preferred_subtitles.is_empty() {
+        return None;
+    }
+    let english = match stream.subtitles.as_deref() {
+        Some("Multi-Subs") => return Some(0),
+        Some("English subs") => true,
+        Some("Subbed") | None => options.is_anime,
+        Some(_) => false,
+    };
+    if !english {
+        return None;
+    }
+    options
+        .preferred_subtitles
+        .iter()
+        .position(|preferred| preferred == "English")
+}
+
 /// Get recommended sources from a list of streams.
 ///
 /// Returns the indices of the top `limit` streams by score (filtering out score <= 0).
@@ -140,7 +564,7 @@ pub fn get_recommended_indices(
     let mut scored: Vec<(usize, f64)> = streams
         .iter()
         .enumerate()
-        .map(|(i, stream)| (i, calculate_source_score(stream, options)))
+        .map(|(i, stream)| (i, calculate_source_score(stream, options).total()))
         .filter(|(_, score)| *score > 0.0)
         .collect();
 
@@ -149,14 +573,46 @@ pub fn get_recommended_indices(
     scored.into_iter().take(limit).map(|(i, _)| i).collect()
 }
 
+/// Source to play without asking: the top recommended stream, cached ones only
+/// if `cached_only`.
+///
+/// Returns `None` if no candidate scores above zero.
+pub fn auto_select_index(
+    streams: &[Stream],
+    options: &ScoringOptions,
+    cached_only: bool,
+) -> Option<usize> {
+    get_recommended_indices(streams, options, streams.len())
+        .into_iter()
+        .find(|&i| !cached_only || streams[i].is_cached)
+}
+
+/// Streams to try after `chosen` fails to play: the other recommended streams,
+/// best first, at most `limit`
+pub fn fallback_streams(
+    streams: &[Stream],
+    options: &ScoringOptions,
+    chosen: &Stream,
+    limit: usize,
+) -> Vec<Stream> {
+    let chosen_key = chosen.source_key();
+    get_recommended_indices(streams, options, streams.len())
+        .into_iter()
+        .map(|i| &streams[i])
+        .filter(|stream| stream.source_key() != chosen_key)
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
 /// Sort streams by score (descending) with tie-breaking on quality rank then size.
 ///
 /// This replaces the previous hardcoded quality-then-size sort with a comprehensive
 /// scoring algorithm that considers quality, cache status, seeders, size, and languages.
-pub fn sort_streams_by_score(streams: &mut Vec<Stream>, options: &ScoringOptions) {
+pub fn sort_streams_by_score(streams: &mut [Stream], options: &ScoringOptions) {
     streams.sort_by(|a, b| {
-        let score_a = calculate_source_score(a, options);
-        let score_b = calculate_source_score(b, options);
+        let score_a = calculate_source_score(a, options).total();
+        let score_b = calculate_source_score(b, options).total();
 
         // Primary sort: score descending
         match score_b.partial_cmp(&score_a) {
@@ -203,6 +659,22 @@ pub fn pin_recommended_to_top(streams: Vec<Stream>, recommended_indices: &[usize
     result
 }
 
+/// Sort streams by score and pin the top `limit` recommended sources to the top.
+///
+/// Returns the ranked streams and the number of recommended sources pinned.
+pub fn rank_streams(
+    mut streams: Vec<Stream>,
+    options: &ScoringOptions,
+    limit: usize,
+) -> (Vec<Stream>, usize) {
+    sort_streams_by_score(&mut streams, options);
+    let recommended = get_recommended_indices(&streams, options, limit);
+    (
+        pin_recommended_to_top(streams, &recommended),
+        recommended.len(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,30 +692,26 @@ mod tests {
             size: Some("1.0 GB".to_string()),
             size_bytes,
             seeders,
-            url: None,
-            info_hash: None,
-            file_idx: None,
-            video_codec: None,
-            audio: None,
-            hdr: None,
-            source_type: None,
-            languages: vec![],
             is_cached,
+            ..Default::default()
         }
     }
 
     fn movie_options() -> ScoringOptions {
-        ScoringOptions {
-            media_type: MediaType::Movie,
-            is_anime: false,
-        }
+        ScoringOptions::new(&ScoringConfig::default(), MediaType::Movie, false, None)
     }
 
     fn tv_options() -> ScoringOptions {
-        ScoringOptions {
-            media_type: MediaType::TvShow,
-            is_anime: false,
-        }
+        ScoringOptions::new(&ScoringConfig::default(), MediaType::TvShow, false, None)
+    }
+
+    fn profile_options(name: &str) -> ScoringOptions {
+        ScoringOptions::new(
+            &ScoringConfig::default(),
+            MediaType::Movie,
+            false,
+            Some(name),
+        )
     }
 
     #[test]
@@ -286,8 +754,8 @@ mod tests {
         let mut stream = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true);
         stream.title = "Movie Trailer HD".to_string();
         let score = calculate_source_score(&stream, &movie_options());
-        assert!(score < 0.0);
-        assert_eq!(score, -10000.0);
+        assert_eq!(score.total(), -10000.0);
+        assert_eq!(score.quality, 0.0);
     }
 
     #[test]
@@ -295,7 +763,10 @@ mod tests {
         let cached = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true);
         let uncached = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), false);
         let opts = movie_options();
-        assert!(calculate_source_score(&cached, &opts) > calculate_source_score(&uncached, &opts));
+        assert!(
+            calculate_source_score(&cached, &opts).total()
+                > calculate_source_score(&uncached, &opts).total()
+        );
     }
 
     #[test]
@@ -303,7 +774,10 @@ mod tests {
         let hd = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true);
         let uhd = make_stream(Some("2160p"), 2 * 1024 * 1024 * 1024, Some(100), true);
         let opts = movie_options();
-        assert!(calculate_source_score(&hd, &opts) > calculate_source_score(&uhd, &opts));
+        assert!(
+            calculate_source_score(&hd, &opts).total()
+                > calculate_source_score(&uhd, &opts).total()
+        );
     }
 
     #[test]
@@ -312,17 +786,20 @@ mod tests {
         let few_seeders = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(5), true);
         let opts = movie_options();
         assert!(
-            calculate_source_score(&many_seeders, &opts)
-                > calculate_source_score(&few_seeders, &opts)
+            calculate_source_score(&many_seeders, &opts).total()
+                > calculate_source_score(&few_seeders, &opts).total()
         );
     }
 
     #[test]
     fn test_smaller_size_scores_higher() {
-        let small = make_stream(Some("1080p"), 1 * 1024 * 1024 * 1024, Some(100), true);
+        let small = make_stream(Some("1080p"), 1024 * 1024 * 1024, Some(100), true);
         let large = make_stream(Some("1080p"), 20 * 1024 * 1024 * 1024, Some(100), true);
         let opts = movie_options();
-        assert!(calculate_source_score(&small, &opts) > calculate_source_score(&large, &opts));
+        assert!(
+            calculate_source_score(&small, &opts).total()
+                > calculate_source_score(&large, &opts).total()
+        );
     }
 
     #[test]
@@ -332,8 +809,8 @@ mod tests {
         let without_langs = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true);
         let opts = movie_options();
         assert!(
-            calculate_source_score(&with_langs, &opts)
-                > calculate_source_score(&without_langs, &opts)
+            calculate_source_score(&with_langs, &opts).total()
+                > calculate_source_score(&without_langs, &opts).total()
         );
     }
 
@@ -351,12 +828,60 @@ mod tests {
         assert!(recommended.contains(&0) || recommended.contains(&2));
     }
 
+    #[test]
+    fn test_auto_select_index() {
+        let opts = movie_options();
+        let streams = vec![
+            make_stream(Some("720p"), 2 * 1024 * 1024 * 1024, Some(50), true),
+            make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(200), true),
+        ];
+        assert_eq!(auto_select_index(&streams, &opts, true), Some(1));
+
+        // Nothing cached: only "best" picks a source
+        let streams = vec![
+            make_stream(Some("480p"), 2 * 1024 * 1024 * 1024, Some(5), false),
+            make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(200), false),
+        ];
+        assert_eq!(auto_select_index(&streams, &opts, false), Some(1));
+        assert_eq!(auto_select_index(&streams, &opts, true), None);
+        assert_eq!(auto_select_index(&[], &opts, false), None);
+    }
+
+    #[test]
+    fn test_failed_sources_and_fallbacks() {
+        let mut streams = vec![
+            make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(200), true),
+            make_stream(Some("720p"), 2 * 1024 * 1024 * 1024, Some(100), true),
+            make_stream(Some("480p"), 2 * 1024 * 1024 * 1024, Some(50), true),
+        ];
+        for (i, stream) in streams.iter_mut().enumerate() {
+            stream.info_hash = Some(format!("HASH{}", i));
+        }
+
+        let keys = |streams: Vec<Stream>| -> Vec<String> {
+            streams.iter().map(|s| s.source_key()).collect()
+        };
+
+        let opts = movie_options();
+        let fallbacks = fallback_streams(&streams, &opts, &streams[0], 5);
+        assert_eq!(keys(fallbacks), vec!["hash1", "hash2"]);
+        assert_eq!(fallback_streams(&streams, &opts, &streams[0], 1).len(), 1);
+
+        // A failed source is penalized and not retried
+        let opts = movie_options().with_failed_sources(HashSet::from(["hash1".to_string()]));
+        let score = calculate_source_score(&streams[1], &opts);
+        assert_eq!(score.failure_penalty, -3000.0);
+        assert!(score.total() <= 0.0);
+        let fallbacks = fallback_streams(&streams, &opts, &streams[0], 5);
+        assert_eq!(keys(fallbacks), vec!["hash2"]);
+    }
+
     #[test]
     fn test_sort_streams_by_score() {
         let mut streams = vec![
             make_stream(Some("480p"), 500 * 1024 * 1024, Some(5), false), // Worst
             make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true), // Best
-            make_stream(Some("720p"), 1 * 1024 * 1024 * 1024, Some(50), true), // Middle
+            make_stream(Some("720p"), 1024 * 1024 * 1024, Some(50), true), // Middle
         ];
         let opts = movie_options();
         sort_streams_by_score(&mut streams, &opts);
@@ -372,7 +897,7 @@ mod tests {
         let streams = vec![
             make_stream(Some("480p"), 500 * 1024 * 1024, Some(5), false), // index 0
             make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true), // index 1
-            make_stream(Some("720p"), 1 * 1024 * 1024 * 1024, Some(50), true), // index 2
+            make_stream(Some("720p"), 1024 * 1024 * 1024, Some(50), true), // index 2
         ];
 
         // Recommend index 1 and 2
@@ -396,7 +921,281 @@ mod tests {
     fn test_tv_options() {
         let stream = make_stream(Some("1080p"), 500 * 1024 * 1024, Some(50), true);
         let opts = tv_options();
-        let score = calculate_source_score(&stream, &opts);
+        let score = calculate_source_score(&stream, &opts).total();
         assert!(score > 0.0);
     }
+
+    #[test]
+    fn test_home_theater_prefers_4k_hdr() {
+        let hd = make_stream(Some("1080p"), 4 * 1024 * 1024 * 1024, Some(100), true);
+        let mut uhd = make_stream(Some("2160p"), 20 * 1024 * 1024 * 1024, Some(100), true);
+        uhd.hdr = Some("DV".to_string());
+        uhd.audio = Some("TrueHD Atmos 7.1".to_string());
+
+        let opts = profile_options("home-theater");
+        assert_eq!(opts.profile_name, "home-theater");
+        assert!(
+            calculate_source_score(&uhd, &opts).total()
+                > calculate_source_score(&hd, &opts).total()
+        );
+
+        // The default profile still favors the smaller 1080p file
+        let opts = movie_options();
+        assert!(
+            calculate_source_score(&hd, &opts).total()
+                > calculate_source_score(&uhd, &opts).total()
+        );
+    }
+
+    #[test]
+    fn test_bandwidth_saver_prefers_small_720p() {
+        let hd = make_stream(Some("720p"), 1024 * 1024 * 1024, Some(50), true);
+        let full_hd = make_stream(Some("1080p"), 3 * 1024 * 1024 * 1024, Some(100), true);
+        let opts = profile_options("bandwidth-saver");
+        assert!(
+            calculate_source_score(&hd, &opts).total()
+                > calculate_source_score(&full_hd, &opts).total()
+        );
+    }
+
+    #[test]
+    fn test_codec_and_provider_bonuses() {
+        let plain = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true);
+        let mut preferred = plain.clone();
+        preferred.provider = "NyaaSi".to_string();
+        preferred.video_codec = Some("HEVC 10bit".to_string());
+
+        let opts = profile_options("anime");
+        let difference = calculate_source_score(&preferred, &opts).total()
+            - calculate_source_score(&plain, &opts).total();
+        assert_eq!(difference, 300.0);
+    }
+
+    #[test]
+    fn test_profile_selection_per_media_type() {
+        let mut config = ScoringConfig {
+            movie_profile: "home-theater".to_string(),
+            anime_profile: Some("anime".to_string()),
+            ..Default::default()
+        };
+        let movie = ScoringOptions::new(&config, MediaType::Movie, false, None);
+        assert_eq!(movie.profile_name, "home-theater");
+        let tv = ScoringOptions::new(&config, MediaType::TvShow, false, None);
+        assert_eq!(tv.profile_name, "default");
+        let anime = ScoringOptions::new(&config, MediaType::TvShow, true, None);
+        assert_eq!(anime.profile_name, "anime");
+
+        // An explicit name wins, unknown names fall back to the default profile
+        let chosen = ScoringOptions::new(&config, MediaType::Movie, false, Some("anime"));
+        assert_eq!(chosen.profile_name, "anime");
+        config.tv_profile = "missing".to_string();
+        let tv = ScoringOptions::new(&config, MediaType::TvShow, false, None);
+        assert_eq!(tv.profile_name, "default");
+        assert_eq!(tv.profile, ScoringProfile::default());
+    }
+
+    #[test]
+    fn test_custom_profiles_override_builtins() {
+        let mut config = ScoringConfig::default();
+        let custom = ScoringProfile {
+            hdr_bonus: 999.0,
+            ..Default::default()
+        };
+        config
+            .profiles
+            .insert("home-theater".to_string(), custom.clone());
+        config
+            .profiles
+            .insert("living-room".to_string(), custom.clone());
+
+        assert_eq!(find_profile(&config, "home-theater"), Some(custom));
+        assert!(find_profile(&config, "nope").is_none());
+        assert_eq!(
+            profile_names(&config),
+            vec![
+                "default",
+                "bandwidth-saver",
+                "home-theater",
+                "anime",
+                "living-room"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rank_streams_pins_recommended() {
+        let streams = vec![
+            make_stream(Some("480p"), 500 * 1024 * 1024, Some(5), false),
+            make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true),
+        ];
+        let (ranked, recommended) = rank_streams(streams, &movie_options(), 1);
+        assert_eq!(ranked[0].quality.as_deref(), Some("1080p"));
+        assert_eq!(recommended, 1);
+    }
+
+    fn language_options(audio: &[&str], subtitles: &[&str], is_anime: bool) -> ScoringOptions {
+        let player = PlayerConfig {
+            preferred_audio_languages: audio.iter().map(|l| l.to_string()).collect(),
+            preferred_subtitle_languages: subtitles.iter().map(|l| l.to_string()).collect(),
+            ..Default::default()
+        };
+        ScoringOptions::new(&ScoringConfig::default(), MediaType::TvShow, is_anime, None)
+            .with_languages(&player)
+    }
+
+    #[test]
+    fn test_preferred_audio_language_favored() {
+        let mut italian = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true);
+        italian.languages = vec!["Italian".to_string()];
+        let mut german = italian.clone();
+        german.languages = vec!["German".to_string()];
+        let unflagged = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(100), true);
+
+        let opts = language_options(&["deu", "en"], &[], false);
+        let german_score = calculate_source_score(&german, &opts);
+        assert_eq!(german_score.audio_language, 800.0);
+        // Unflagged releases are English, the second choice
+        assert_eq!(
+            calculate_source_score(&unflagged, &opts).audio_language,
+            400.0
+        );
+        assert_eq!(calculate_source_score(&italian, &opts).audio_language, 0.0);
+    }
+
+    #[test]
+    fn test_anime_dual_audio() {
+        let mut dual = make_stream(Some("1080p"), 1024 * 1024 * 1024, Some(100), true);
+        dual.dual_audio = true;
+        let japanese = make_stream(Some("1080p"), 1024 * 1024 * 1024, Some(100), true);
+
+        // Dub watchers want the dual-audio release
+        let opts = language_options(&["English"], &[], true);
+        assert_eq!(calculate_source_score(&dual, &opts).audio_language, 800.0);
+        assert_eq!(calculate_source_score(&japanese, &opts).audio_language, 0.0);
+
+        // Both releases have the Japanese track
+        let opts = language_options(&["Japanese"], &["English"], true);
+        assert_eq!(calculate_source_score(&dual, &opts).audio_language, 800.0);
+        assert_eq!(
+            calculate_source_score(&japanese, &opts).audio_language,
+            800.0
+        );
+        assert_eq!(
+            calculate_source_score(&japanese, &opts).subtitle_language,
+            300.0
+        );
+    }
+
+    #[test]
+    fn test_preferred_subtitles() {
+        let mut multi = make_stream(Some("1080p"), 1024 * 1024 * 1024, Some(100), true);
+        multi.subtitles = Some("Multi-Subs".to_string());
+        let plain = make_stream(Some("1080p"), 1024 * 1024 * 1024, Some(100), true);
+
+        let opts = language_options(&[], &["French"], false);
+        assert_eq!(
+            calculate_source_score(&multi, &opts).subtitle_language,
+            300.0
+        );
+        assert_eq!(calculate_source_score(&plain, &opts).subtitle_language, 0.0);
+
+        // No preferences, no language bonus
+        let opts = movie_options();
+        assert_eq!(calculate_source_score(&multi, &opts).subtitle_language, 0.0);
+        assert_eq!(calculate_source_score(&plain, &opts).audio_language, 0.0);
+    }
+
+    fn capability_options(capabilities: PlaybackCapabilities) -> ScoringOptions {
+        movie_options().with_capabilities(&capabilities)
+    }
+
+    #[test]
+    fn test_unsupported_features() {
+        let mut stream = make_stream(Some("2160p"), 20 * 1024 * 1024 * 1024, Some(100), true);
+        stream.hdr = Some("DV".to_string());
+        stream.video_codec = Some("HEVC 10bit".to_string());
+        stream.bit_depth = Some(10);
+
+        let capabilities = PlaybackCapabilities {
+            max_resolution: Some("1080p".to_string()),
+            hdr: false,
+            codecs: vec!["x264".to_string()],
+            max_bit_depth: Some(8),
+            max_bitrate_mbps: Some(15.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            unsupported_features(&stream, &capabilities, MediaType::Movie),
+            vec!["2160p", "DV", "HEVC", "10-bit", "~24 Mbit/s"]
+        );
+
+        // Everything is playable by default
+        let defaults = PlaybackCapabilities::default();
+        assert!(unsupported_features(&stream, &defaults, MediaType::Movie).is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_sources_rank_below_playable() {
+        let mut hevc = make_stream(Some("1080p"), 2 * 1024 * 1024 * 1024, Some(500), true);
+        hevc.video_codec = Some("HEVC".to_string());
+        hevc.hdr = Some("HDR".to_string());
+        let avc = make_stream(Some("480p"), 2 * 1024 * 1024 * 1024, Some(5), false);
+
+        let opts = capability_options(PlaybackCapabilities {
+            hdr: false,
+            codecs: vec!["AVC".to_string()],
+            ..Default::default()
+        });
+        let score = calculate_source_score(&hevc, &opts);
+        assert_eq!(score.device_penalty, -5000.0);
+        assert_eq!(score.unsupported, vec!["HDR", "HEVC"]);
+        assert!(calculate_source_score(&avc, &opts).total() > score.total());
+    }
+
+    #[test]
+    fn test_continuity_bonus() {
+        let mut same_group = make_stream(Some("1080p"), 1024 * 1024 * 1024, Some(100), true);
+        same_group.release_group = Some("ntb".to_string());
+        let mut other_quality = same_group.clone();
+        other_quality.quality = Some("720p".to_string());
+        let mut same_torrent = make_stream(Some("720p"), 1024 * 1024 * 1024, Some(100), true);
+        same_torrent.info_hash = Some("ABC123".to_string());
+        let other = make_stream(Some("1080p"), 1024 * 1024 * 1024, Some(100), true);
+
+        let opts = tv_options().with_previous_choice(Some(StreamChoice {
+            provider: "eztv".to_string(),
+            release_group: Some("NTb".to_string()),
+            quality: Some("1080p".to_string()),
+            info_hash: Some("abc123".to_string()),
+        }));
+        assert_eq!(calculate_source_score(&same_group, &opts).continuity, 500.0);
+        assert_eq!(
+            calculate_source_score(&other_quality, &opts).continuity,
+            250.0
+        );
+        assert_eq!(
+            calculate_source_score(&same_torrent, &opts).continuity,
+            500.0
+        );
+        assert_eq!(calculate_source_score(&other, &opts).continuity, 0.0);
+        assert_eq!(
+            calculate_source_score(&same_group, &tv_options()).continuity,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_score_breakdown() {
+        let mut stream = make_stream(Some("1080p"), 4 * 1024 * 1024 * 1024, Some(255), true);
+        stream.languages = vec!["English".to_string()];
+        let score = calculate_source_score(&stream, &movie_options());
+
+        assert_eq!(score.trailer_penalty, 0.0);
+        assert_eq!(score.quality, 1000.0);
+        assert_eq!(score.cached, 1000.0);
+        assert_eq!(score.languages, 30.0);
+        assert_eq!(score.seeders, 400.0);
+        assert_eq!(score.size, -640.0);
+        assert_eq!(score.total(), 1790.0);
+    }
 }
@@ -1,7 +1,11 @@
-use reqwest::Client;
+use async_trait::async_trait;
 use serde::Deserialize;
 
-use crate::api::media::{Episode, Media, MediaSource, MediaType, Season};
+use crate::api::media::{
+    AiringEpisode, Collection, CollectionRef, Episode, Media, MediaSource, MediaType, Season,
+    TvAiring,
+};
+use crate::api::{HttpClient, MetadataProvider};
 use crate::error::ApiError;
 
 const TMDB_API_URL: &str = "https://api.themoviedb.org/3";
@@ -50,17 +54,15 @@ fn genres_from_ids(ids: &[i32]) -> Vec<String> {
 }
 
 /// TMDB API client
+#[derive(Clone)]
 pub struct TmdbClient {
-    client: Client,
+    http: HttpClient,
     api_key: String,
 }
 
 impl TmdbClient {
-    pub fn new(api_key: String) -> Self {
-        Self {
-            client: Client::new(),
-            api_key,
-        }
+    pub fn new(api_key: String, http: HttpClient) -> Self {
+        Self { http, api_key }
     }
 
     /// Check if the client is configured (has API key)
@@ -81,7 +83,7 @@ impl TmdbClient {
             urlencoding::encode(query)
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.send(self.http.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
@@ -108,7 +110,7 @@ impl TmdbClient {
             urlencoding::encode(query)
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.send(self.http.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
@@ -146,7 +148,7 @@ impl TmdbClient {
             TMDB_API_URL, movie_id, self.api_key
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.send(self.http.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
@@ -167,7 +169,7 @@ impl TmdbClient {
             TMDB_API_URL, tv_id, self.api_key
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.send(self.http.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
@@ -181,11 +183,50 @@ impl TmdbClient {
         data.imdb_id.ok_or(ApiError::MappingNotFound)
     }
 
+    /// Find a movie or TV show by its IMDb ID
+    ///
+    /// IMDb IDs of single episodes resolve to their show.
+    pub async fn find_by_imdb_id(&self, imdb_id: &str) -> Result<Option<Media>, ApiError> {
+        let url = format!(
+            "{}/find/{}?api_key={}&external_source=imdb_id",
+            TMDB_API_URL, imdb_id, self.api_key
+        );
+
+        let response = self.http.send(self.http.get(&url)).await?;
+
+        if !response.status().is_success() {
+            return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
+        }
+
+        let data: FindResponse = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Tmdb(format!("Failed to parse response: {}", e)))?;
+
+        let media = if let Some(movie) = data.movie_results.into_iter().next() {
+            Media::from(movie)
+        } else if let Some(tv) = data.tv_results.into_iter().next() {
+            Media::from(tv)
+        } else if let Some(episode) = data.tv_episode_results.into_iter().next() {
+            // Episode IDs don't identify the show on IMDb, so don't carry the ID over
+            return self.get_tv_show_details(episode.show_id).await.map(Some);
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(Media {
+            imdb_id: Some(imdb_id.to_string()),
+            ..media
+        }))
+    }
+
     /// Get TV show details including seasons
+    ///
+    /// Specials (season 0) are included after the regular seasons when they have episodes.
     pub async fn get_tv_details(&self, tv_id: i32) -> Result<Vec<Season>, ApiError> {
         let url = format!("{}/tv/{}?api_key={}", TMDB_API_URL, tv_id, self.api_key);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.send(self.http.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
@@ -196,15 +237,41 @@ impl TmdbClient {
             .await
             .map_err(|e| ApiError::Tmdb(format!("Failed to parse response: {}", e)))?;
 
-        Ok(data
+        let mut seasons: Vec<Season> = data
             .seasons
             .into_iter()
-            .filter(|s| s.season_number > 0) // Exclude specials (season 0)
+            .filter(|s| s.season_number > 0 || s.episode_count > 0)
             .map(|s| Season {
                 number: s.season_number,
                 episode_count: s.episode_count,
             })
-            .collect())
+            .collect();
+
+        // Regular seasons first, specials listed last as a separate entry
+        seasons.sort_by_key(|s| (s.is_specials(), s.number));
+
+        Ok(seasons)
+    }
+
+    /// Get the airing schedule of a TV show (last aired and next scheduled episode)
+    pub async fn get_tv_airing(&self, tv_id: i32) -> Result<TvAiring, ApiError> {
+        let url = format!("{}/tv/{}?api_key={}", TMDB_API_URL, tv_id, self.api_key);
+
+        let response = self.http.send(self.http.get(&url)).await?;
+
+        if !response.status().is_success() {
+            return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
+        }
+
+        let data: TvAiringResponse = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Tmdb(format!("Failed to parse response: {}", e)))?;
+
+        Ok(TvAiring {
+            last: data.last_episode_to_air.map(AiringEpisode::from),
+            next: data.next_episode_to_air.map(AiringEpisode::from),
+        })
     }
 
     /// Get episodes for a specific season with full metadata
@@ -218,7 +285,7 @@ impl TmdbClient {
             TMDB_API_URL, tv_id, season_number, self.api_key
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.send(self.http.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
@@ -254,7 +321,7 @@ impl TmdbClient {
             TMDB_API_URL, movie_id, self.api_key
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.send(self.http.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
@@ -268,11 +335,51 @@ impl TmdbClient {
         Ok(Media::from(data))
     }
 
+    /// Get a movie collection with its parts in release order
+    pub async fn get_collection(&self, collection_id: i32) -> Result<Collection, ApiError> {
+        let url = format!(
+            "{}/collection/{}?api_key={}",
+            TMDB_API_URL, collection_id, self.api_key
+        );
+
+        let response = self.http.send(self.http.get(&url)).await?;
+
+        if !response.status().is_success() {
+            return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
+        }
+
+        let mut data: CollectionResponse = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Tmdb(format!("Failed to parse response: {}", e)))?;
+
+        sort_by_release_date(&mut data.parts);
+
+        let collection_ref = CollectionRef {
+            id: data.id,
+            name: data.name.clone(),
+        };
+        let parts = data
+            .parts
+            .into_iter()
+            .map(|part| Media {
+                collection: Some(collection_ref.clone()),
+                ..Media::from(part)
+            })
+            .collect();
+
+        Ok(Collection {
+            name: data.name,
+            overview: data.overview.filter(|o| !o.is_empty()),
+            parts,
+        })
+    }
+
     /// Get TV show details by ID (returns Media object)
     pub async fn get_tv_show_details(&self, tv_id: i32) -> Result<Media, ApiError> {
         let url = format!("{}/tv/{}?api_key={}", TMDB_API_URL, tv_id, self.api_key);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.send(self.http.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(ApiError::Tmdb(format!("HTTP {}", response.status())));
@@ -289,7 +396,58 @@ impl TmdbClient {
 
 impl Default for TmdbClient {
     fn default() -> Self {
-        Self::new(String::new())
+        Self::new(String::new(), HttpClient::default())
+    }
+}
+
+/// Extract the TMDB ID of media from another provider as an error
+fn tmdb_id(source: &MediaSource) -> Result<i32, ApiError> {
+    source
+        .tmdb_id()
+        .ok_or_else(|| ApiError::Tmdb(format!("{} is not a TMDB title", source.key())))
+}
+
+#[async_trait]
+impl MetadataProvider for TmdbClient {
+    fn name(&self) -> &'static str {
+        "tmdb"
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<Media>, ApiError> {
+        self.search_all(query).await
+    }
+
+    async fn details(
+        &self,
+        source: &MediaSource,
+        media_type: MediaType,
+    ) -> Result<Media, ApiError> {
+        let id = tmdb_id(source)?;
+        match media_type {
+            MediaType::Movie => self.get_movie_details(id).await,
+            MediaType::TvShow => self.get_tv_show_details(id).await,
+        }
+    }
+
+    async fn seasons(&self, media: &Media) -> Result<Vec<Season>, ApiError> {
+        self.get_tv_details(tmdb_id(&media.source)?).await
+    }
+
+    async fn episodes(&self, media: &Media, season: u32) -> Result<Vec<Episode>, ApiError> {
+        self.get_season_episodes(tmdb_id(&media.source)?, season)
+            .await
+    }
+
+    async fn imdb_id(&self, media: &Media) -> Result<String, ApiError> {
+        if let Some(imdb_id) = &media.imdb_id {
+            return Ok(imdb_id.clone());
+        }
+
+        let id = tmdb_id(&media.source)?;
+        match media.media_type {
+            MediaType::Movie => self.get_movie_external_ids(id).await,
+            MediaType::TvShow => self.get_tv_external_ids(id).await,
+        }
     }
 }
 
@@ -336,6 +494,21 @@ struct ExternalIdsResponse {
     imdb_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct FindResponse {
+    #[serde(default)]
+    movie_results: Vec<MovieResult>,
+    #[serde(default)]
+    tv_results: Vec<TvResult>,
+    #[serde(default)]
+    tv_episode_results: Vec<FindEpisodeResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FindEpisodeResult {
+    show_id: i32,
+}
+
 #[derive(Debug, Deserialize)]
 struct TvDetailsResponse {
     #[serde(default)]
@@ -354,6 +527,22 @@ struct MovieDetailsResponse {
     #[serde(default)]
     genres: Vec<GenreInfo>,
     status: Option<String>,
+    belongs_to_collection: Option<CollectionInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionInfo {
+    id: i32,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionResponse {
+    id: i32,
+    name: String,
+    overview: Option<String>,
+    #[serde(default)]
+    parts: Vec<MovieResult>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -372,6 +561,19 @@ struct TvShowDetailsResponse {
     number_of_episodes: Option<i32>,
 }
 
+#[derive(Debug, Deserialize)]
+struct TvAiringResponse {
+    last_episode_to_air: Option<AiringEpisodeInfo>,
+    next_episode_to_air: Option<AiringEpisodeInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AiringEpisodeInfo {
+    season_number: u32,
+    episode_number: u32,
+    air_date: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct GenreInfo {
     name: String,
@@ -402,6 +604,25 @@ struct EpisodeInfo {
     vote_average: Option<f32>,
 }
 
+/// Sort movies by release date, with undated (unreleased) movies last
+fn sort_by_release_date(movies: &mut [MovieResult]) {
+    movies.sort_by_key(|m| {
+        let date = m.release_date.clone().filter(|d| !d.is_empty());
+        (date.is_none(), date)
+    });
+}
+
+/// Release status of a movie from its release date: "Released", "Upcoming" for a
+/// future date, or "TBA" without one
+fn movie_status(release_date: Option<&str>) -> &'static str {
+    let date = release_date.and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+    match date {
+        Some(date) if date <= chrono::Local::now().date_naive() => "Released",
+        Some(_) => "Upcoming",
+        None => "TBA",
+    }
+}
+
 // Conversion implementations
 
 impl From<MovieResult> for Media {
@@ -427,9 +648,10 @@ impl From<MovieResult> for Media {
                 .map(|p| format!("{}{}", TMDB_IMAGE_BASE, p)),
             episode_titles: vec![],
             description: movie.overview,
-            status: Some("Released".to_string()),
+            status: Some(movie_status(movie.release_date.as_deref()).to_string()),
             format: Some("Movie".to_string()),
             genres: genres_from_ids(&movie.genre_ids),
+            collection: None,
         }
     }
 }
@@ -458,6 +680,7 @@ impl From<TvResult> for Media {
             status: None, // Would need additional API call
             format: Some("TV".to_string()),
             genres: genres_from_ids(&tv.genre_ids),
+            collection: None,
         }
     }
 }
@@ -488,6 +711,10 @@ impl From<MovieDetailsResponse> for Media {
             status: movie.status,
             format: Some("Movie".to_string()),
             genres: movie.genres.into_iter().map(|g| g.name).collect(),
+            collection: movie.belongs_to_collection.map(|c| CollectionRef {
+                id: c.id,
+                name: c.name,
+            }),
         }
     }
 }
@@ -516,6 +743,74 @@ impl From<TvShowDetailsResponse> for Media {
             status: tv.status,
             format: Some("TV".to_string()),
             genres: tv.genres.into_iter().map(|g| g.name).collect(),
+            collection: None,
+        }
+    }
+}
+
+impl From<AiringEpisodeInfo> for AiringEpisode {
+    fn from(ep: AiringEpisodeInfo) -> Self {
+        Self {
+            season: ep.season_number,
+            episode: ep.episode_number,
+            air_date: ep.air_date.filter(|d| !d.is_empty()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collection_parts_in_release_order() {
+        let json = r#"{
+            "id": 2344,
+            "name": "The Matrix Collection",
+            "overview": "",
+            "parts": [
+                {"id": 624860, "title": "The Matrix Resurrections", "release_date": "2021-12-16"},
+                {"id": 999999, "title": "Untitled Matrix Film", "release_date": ""},
+                {"id": 603, "title": "The Matrix", "release_date": "1999-03-31"},
+                {"id": 604, "title": "The Matrix Reloaded", "release_date": "2003-05-15"}
+            ]
+        }"#;
+        let mut data: CollectionResponse = serde_json::from_str(json).unwrap();
+        sort_by_release_date(&mut data.parts);
+
+        let ids: Vec<i32> = data.parts.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![603, 604, 624860, 999999]);
+
+        // Parts without a release date can't be played yet
+        let parts: Vec<Media> = data.parts.into_iter().map(Media::from).collect();
+        assert!(parts[0].is_released());
+        assert_eq!(parts[3].status.as_deref(), Some("TBA"));
+        assert!(!parts[3].is_released());
+    }
+
+    #[test]
+    fn test_movie_status() {
+        assert_eq!(movie_status(Some("1999-03-31")), "Released");
+        assert_eq!(movie_status(Some("2999-01-01")), "Upcoming");
+        assert_eq!(movie_status(Some("")), "TBA");
+        assert_eq!(movie_status(None), "TBA");
+    }
+
+    #[test]
+    fn test_movie_details_collection() {
+        let json = r#"{
+            "id": 603,
+            "title": "The Matrix",
+            "belongs_to_collection": {"id": 2344, "name": "The Matrix Collection", "poster_path": null}
+        }"#;
+        let data: MovieDetailsResponse = serde_json::from_str(json).unwrap();
+        let media = Media::from(data);
+        assert_eq!(
+            media.collection,
+            Some(CollectionRef {
+                id: 2344,
+                name: "The Matrix Collection".to_string()
+            })
+        );
+    }
+}
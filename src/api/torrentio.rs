@@ -1,9 +1,13 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
 use lazy_static::lazy_static;
 use regex::Regex;
-use reqwest::Client;
 use serde::Deserialize;
 
-use crate::config::TorrentioConfig;
+use crate::api::release_name;
+use crate::api::HttpClient;
+use crate::config::{DebridKind, TorrentioConfig};
 use crate::error::ApiError;
 
 const TORRENTIO_URL: &str = "https://torrentio.strem.fun";
@@ -13,18 +17,8 @@ lazy_static! {
     static ref SEEDERS_RE: Regex = Regex::new(r"👤\s*(\d+)").unwrap();
     // Match patterns like "💾 1.2 GB" or "💾 800 MB" for size
     static ref SIZE_RE: Regex = Regex::new(r"💾\s*([\d.]+)\s*(GB|MB|TB)").unwrap();
-    // Match quality patterns like "1080p", "720p", "480p", "2160p", "4K"
-    static ref QUALITY_RE: Regex = Regex::new(r"\b(2160p|4K|1080p|720p|480p|360p)\b").unwrap();
-    // Match HDR patterns
-    static ref HDR_RE: Regex = Regex::new(r"(?i)\b(HDR10\+|HDR10|DoVi|DV|Dolby[\s.]?Vision|HDR)\b").unwrap();
-    // Match video codec patterns
-    static ref VIDEO_CODEC_RE: Regex = Regex::new(r"(?i)\b(HEVC|x265|x264|AVC|AV1|H\.?265|H\.?264|VC-1|10bit|10-bit)\b").unwrap();
-    // Match audio codec patterns (simplified)
-    static ref AUDIO_RE: Regex = Regex::new(r"(?i)(DTS-HD[\s.]?MA|TrueHD|Atmos|DTS|AAC|FLAC|EAC3|E-AC-3|AC3|DD|DD\+|LPCM)[\s.]?(\d\.\d)?").unwrap();
-    // Match audio channels
-    static ref AUDIO_CHANNELS_RE: Regex = Regex::new(r"\b([257]\.[01])\b").unwrap();
-    // Match source type patterns
-    static ref SOURCE_RE: Regex = Regex::new(r"(?i)\b(UHD[\s.]?BluRay|BluRay|Blu-Ray|BDRip|BRRip|WEB-DL|WEBDL|WEBRip|REMUX|HDTV|DVDRip)\b").unwrap();
+    // Match the info hash in debrid resolve URLs (".../realdebrid/{key}/{hash}/...")
+    static ref URL_INFO_HASH_RE: Regex = Regex::new(r"/([0-9a-fA-F]{40})(?:/|$)").unwrap();
     // Match language flags
     static ref LANG_FLAGS_RE: Regex = Regex::new(r"(🇬🇧|🇺🇸|🇩🇪|🇫🇷|🇮🇹|🇪🇸|🇯🇵|🇰🇷|🇨🇳|🇧🇷|🇵🇹|🇷🇺|🇳🇱|🇵🇱|🇸🇪|🇳🇴|🇩🇰|🇫🇮|🇬🇷|🇹🇷|🇮🇳|🇹🇭|🇻🇳|🇮🇩|🇲🇽|🇦🇷)").unwrap();
 }
@@ -58,69 +52,91 @@ fn flag_to_language(flag: &str) -> &'static str {
     }
 }
 
+/// Torrentio link that resolves a torrent file through a debrid service
+///
+/// Used for sources found cached by the service's own API, which Torrentio listed
+/// without a debrid link.
+pub fn resolve_url(
+    kind: DebridKind,
+    api_key: &str,
+    info_hash: &str,
+    file_idx: Option<usize>,
+) -> String {
+    format!(
+        "{}/resolve/{}/{}/{}/null/{}",
+        TORRENTIO_URL,
+        kind.key(),
+        api_key,
+        info_hash.to_lowercase(),
+        file_idx.map_or_else(|| "null".to_string(), |idx| idx.to_string())
+    )
+}
+
 /// Torrentio addon client
 pub struct TorrentioClient {
-    client: Client,
+    http: HttpClient,
     config: TorrentioConfig,
-    /// Real-Debrid API key (None for direct P2P streaming)
-    rd_api_key: Option<String>,
+    /// Debrid services with their API keys, in priority order (empty for direct P2P streaming)
+    debrid: Vec<(DebridKind, String)>,
 }
 
 impl TorrentioClient {
-    /// Create a new client with Real-Debrid support
-    pub fn new(config: TorrentioConfig, rd_api_key: String) -> Self {
+    /// Create a new client resolving streams through debrid services (in priority order)
+    pub fn new(
+        config: TorrentioConfig,
+        debrid: Vec<(DebridKind, String)>,
+        http: HttpClient,
+    ) -> Self {
         Self {
-            client: Client::new(),
+            http,
             config,
-            rd_api_key: Some(rd_api_key),
+            debrid,
         }
     }
 
-    /// Create a new client for direct P2P streaming (no Real-Debrid)
-    pub fn new_without_debrid(config: TorrentioConfig) -> Self {
-        Self {
-            client: Client::new(),
-            config,
-            rd_api_key: None,
+    /// Replace a debrid service's API key (e.g., after an OAuth token refresh)
+    pub fn set_api_key(&mut self, kind: DebridKind, api_key: String) {
+        if let Some((_, key)) = self.debrid.iter_mut().find(|(k, _)| *k == kind) {
+            *key = api_key;
         }
     }
 
-    /// Check if this client is configured for Real-Debrid
+    /// Check if this client is configured for a debrid service
     #[allow(dead_code)]
     pub fn has_debrid(&self) -> bool {
-        self.rd_api_key.is_some()
+        !self.debrid.is_empty()
     }
 
     /// Build the config string for Torrentio URL
     fn build_config_string(&self, show_uncached: bool) -> String {
         let providers = self.config.providers.join(",");
+        let mut config = format!(
+            "providers={}|sort=qualitysize|qualityfilter=scr,cam",
+            providers
+        );
 
-        match &self.rd_api_key {
-            Some(api_key) => {
-                // With Real-Debrid: debridoptions=nodownloadlinks ensures only cached/instant streams are returned
-                // This means all URLs are direct RD links that can be played immediately
-                // When show_uncached is true, we omit this option to show all available torrents
-                if show_uncached {
-                    format!(
-                        "providers={}|sort=qualitysize|qualityfilter=scr,cam|realdebrid={}",
-                        providers, api_key
-                    )
-                } else {
-                    format!(
-                        "providers={}|sort=qualitysize|qualityfilter=scr,cam|debridoptions=nodownloadlinks|realdebrid={}",
-                        providers, api_key
-                    )
-                }
-            }
-            None => {
-                // Without Real-Debrid: request streams without debrid integration
-                // URLs will be magnet links or torrent hashes that we can use for P2P streaming
-                format!(
-                    "providers={}|sort=qualitysize|qualityfilter=scr,cam",
-                    providers
-                )
-            }
+        // Without debrid: URLs will be magnet links or torrent hashes for P2P streaming.
+        // With debrid: debridoptions=nodownloadlinks ensures only cached/instant streams are
+        // returned, so all URLs are direct links that can be played immediately. When
+        // show_uncached is true, we omit this option to show all available torrents.
+        if !self.debrid.is_empty() && !show_uncached {
+            config.push_str("|debridoptions=nodownloadlinks");
         }
+        for (kind, api_key) in &self.debrid {
+            config.push_str(&format!("|{}={}", kind.key(), api_key));
+        }
+        config
+    }
+
+    /// Build the stream URL for a catalog item (`kind` is "movie" or "series")
+    fn stream_url(&self, kind: &str, id: &str, show_uncached: bool) -> String {
+        format!(
+            "{}/{}/stream/{}/{}.json",
+            TORRENTIO_URL,
+            self.build_config_string(show_uncached),
+            kind,
+            id
+        )
     }
 
     /// Get streams for a series episode
@@ -132,31 +148,22 @@ impl TorrentioClient {
         episode: u32,
         show_uncached: bool,
     ) -> Result<Vec<Stream>, ApiError> {
-        let config_str = self.build_config_string(show_uncached);
-        let url = format!(
-            "{}/{}/stream/series/{}:{}:{}.json",
-            TORRENTIO_URL, config_str, imdb_id, season, episode
-        );
-
-        tracing::debug!("Fetching streams from: {}", url);
-
-        let response = self.client.get(&url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(ApiError::Torrentio(format!("HTTP {}", response.status())));
-        }
-
-        let data: TorrentioResponse = response
-            .json()
+        let id = format!("{}:{}:{}", imdb_id, season, episode);
+        self.fetch_streams(&self.stream_url("series", &id, show_uncached))
             .await
-            .map_err(|e| ApiError::Torrentio(format!("Failed to parse response: {}", e)))?;
-
-        let streams: Vec<Stream> = data.streams.into_iter().map(Stream::from).collect();
-
-        // Sorting is handled by the caller using source_scoring::sort_streams_by_score()
-        // to allow scoring based on media context (type, anime, etc.)
+    }
 
-        Ok(streams)
+    /// Get streams for an anime episode using Kitsu numbering (`kitsu:{id}:{ep}`)
+    /// When `show_uncached` is true, returns all available torrents including uncached ones
+    pub async fn get_kitsu_streams(
+        &self,
+        kitsu_id: u32,
+        episode: u32,
+        show_uncached: bool,
+    ) -> Result<Vec<Stream>, ApiError> {
+        let id = format!("kitsu:{}:{}", kitsu_id, episode);
+        self.fetch_streams(&self.stream_url("series", &id, show_uncached))
+            .await
     }
 
     /// Get streams for a movie
@@ -166,15 +173,15 @@ impl TorrentioClient {
         imdb_id: &str,
         show_uncached: bool,
     ) -> Result<Vec<Stream>, ApiError> {
-        let config_str = self.build_config_string(show_uncached);
-        let url = format!(
-            "{}/{}/stream/movie/{}.json",
-            TORRENTIO_URL, config_str, imdb_id
-        );
+        self.fetch_streams(&self.stream_url("movie", imdb_id, show_uncached))
+            .await
+    }
 
-        tracing::debug!("Fetching movie streams from: {}", url);
+    /// Fetch and parse streams from a Torrentio stream URL
+    async fn fetch_streams(&self, url: &str) -> Result<Vec<Stream>, ApiError> {
+        tracing::debug!("Fetching streams from: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.send(self.http.get(url)).await?;
 
         if !response.status().is_success() {
             return Err(ApiError::Torrentio(format!("HTTP {}", response.status())));
@@ -186,12 +193,46 @@ impl TorrentioClient {
             .map_err(|e| ApiError::Torrentio(format!("Failed to parse response: {}", e)))?;
 
         let streams: Vec<Stream> = data.streams.into_iter().map(Stream::from).collect();
+        let streams = self.prefer_services(streams);
 
         // Sorting is handled by the caller using source_scoring::sort_streams_by_score()
         // to allow scoring based on media context (type, anime, etc.)
 
         Ok(streams)
     }
+
+    /// Keep one copy of each source found on several debrid services: a cached one,
+    /// on the service highest in the priority list
+    fn prefer_services(&self, streams: Vec<Stream>) -> Vec<Stream> {
+        if self.debrid.len() < 2 {
+            return streams;
+        }
+
+        let rank = |stream: &Stream| {
+            let priority = stream
+                .debrid
+                .and_then(|kind| self.debrid.iter().position(|(k, _)| *k == kind))
+                .unwrap_or(usize::MAX);
+            (!stream.is_cached, priority)
+        };
+
+        let mut kept: Vec<Stream> = Vec::with_capacity(streams.len());
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for stream in streams {
+            match index.get(&stream.source_key()) {
+                Some(&i) => {
+                    if rank(&stream) < rank(&kept[i]) {
+                        kept[i] = stream;
+                    }
+                }
+                None => {
+                    index.insert(stream.source_key(), kept.len());
+                    kept.push(stream);
+                }
+            }
+        }
+        kept
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -212,7 +253,7 @@ struct StreamResponse {
 }
 
 /// Parsed stream data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Stream {
     /// Provider name (e.g., "nyaasi", "1337x")
     pub provider: String,
@@ -231,7 +272,6 @@ pub struct Stream {
     /// Torrent info hash (for P2P streaming without debrid)
     pub info_hash: Option<String>,
     /// File index within the torrent (for multi-file torrents)
-    #[allow(dead_code)]
     pub file_idx: Option<usize>,
     /// Video codec (e.g., "HEVC", "x264", "AV1")
     pub video_codec: Option<String>,
@@ -243,8 +283,30 @@ pub struct Stream {
     pub source_type: Option<String>,
     /// Available languages (flag emojis)
     pub languages: Vec<String>,
-    /// Whether this stream is cached on Real-Debrid (instant playback)
+    /// Whether this stream is cached on its debrid service (instant playback)
     pub is_cached: bool,
+    /// Debrid service the stream resolves through (None for P2P)
+    pub debrid: Option<DebridKind>,
+    /// Video bit depth (e.g., 10)
+    pub bit_depth: Option<u8>,
+    /// Release group (e.g., "NTb", "SubsPlease")
+    pub release_group: Option<String>,
+    /// Repack marker ("REPACK", "PROPER", "RERIP")
+    pub repack: Option<String>,
+    /// Edition (e.g., "Director's Cut", "Extended", "IMAX")
+    pub edition: Option<String>,
+    /// Two audio tracks (usually original language and English dub)
+    pub dual_audio: bool,
+    /// Several audio languages
+    pub multi_audio: bool,
+    /// Subtitle hint ("Multi-Subs", "English subs", "Hardcoded", "Subbed")
+    pub subtitles: Option<String>,
+    /// Season(s) in the torrent
+    pub seasons: Option<RangeInclusive<u32>>,
+    /// Episode(s) in the torrent
+    pub episodes: Option<RangeInclusive<u32>>,
+    /// Whether the torrent is a season pack (the episode is one file of many)
+    pub season_pack: bool,
 }
 
 impl Stream {
@@ -255,6 +317,15 @@ impl Stream {
             .map(|hash| format!("magnet:?xt=urn:btih:{}", hash))
     }
 
+    /// Key identifying the source across fetches: the info hash, else the URL or title
+    pub fn source_key(&self) -> String {
+        match (&self.info_hash, &self.url) {
+            (Some(hash), _) => hash.to_lowercase(),
+            (None, Some(url)) => url.clone(),
+            (None, None) => self.title.clone(),
+        }
+    }
+
     /// Check if this stream can be played directly (has URL or can be streamed via P2P)
     #[allow(dead_code)]
     pub fn is_playable(&self) -> bool {
@@ -262,6 +333,27 @@ impl Stream {
     }
 }
 
+/// Release details of a chosen stream, remembered in the watch history so later
+/// episodes can stick with the same release
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamChoice {
+    pub provider: String,
+    pub release_group: Option<String>,
+    pub quality: Option<String>,
+    pub info_hash: Option<String>,
+}
+
+impl From<&Stream> for StreamChoice {
+    fn from(stream: &Stream) -> Self {
+        Self {
+            provider: stream.provider.clone(),
+            release_group: stream.release_group.clone(),
+            quality: stream.quality.clone(),
+            info_hash: stream.info_hash.clone(),
+        }
+    }
+}
+
 impl Stream {
     /// Get quality rank for sorting (higher is better)
     pub fn quality_rank(&self) -> u8 {
@@ -298,14 +390,25 @@ fn parse_size_to_bytes(size_str: &str) -> u64 {
     (value * multiplier as f64) as u64
 }
 
+/// Debrid service and cache status from a stream name ("[RD+] nyaasi", "[AD download] 1337x")
+fn debrid_tag(name: &str) -> Option<(DebridKind, bool)> {
+    let tag = name.trim_start().strip_prefix('[')?.split(']').next()?;
+    DebridKind::ALL.into_iter().find_map(|kind| {
+        let rest = tag.strip_prefix(kind.tag())?;
+        (rest.is_empty() || rest.starts_with(['+', ' '])).then(|| (kind, rest.starts_with('+')))
+    })
+}
+
 impl From<StreamResponse> for Stream {
     fn from(resp: StreamResponse) -> Self {
-        // Combine name and title for parsing (name often has quality info like "4k DV | HDR")
-        let combined = format!("{}\n{}", resp.name, resp.title);
+        // The title starts with the release name; the addon name often has quality info
+        // too (like "4k DV | HDR")
+        let release = release_name::parse(&format!("{}\n{}", resp.title, resp.name));
 
-        // Detect if stream is cached based on name prefix
-        // [RD+] = cached, [RD download] or [RD] without + = uncached
-        let is_cached = resp.name.contains("[RD+]") || resp.name.contains("[⚡]");
+        // Detect the debrid service and whether the stream is cached from the name prefix
+        // [RD+] = cached, [RD download] or [RD] without + = uncached (likewise AD, PM, ...)
+        let debrid = debrid_tag(&resp.name);
+        let is_cached = debrid.is_some_and(|(_, cached)| cached) || resp.name.contains("[⚡]");
 
         // Parse provider from name (e.g., "[RD+] nyaasi" -> "nyaasi")
         let provider = resp
@@ -316,9 +419,6 @@ impl From<StreamResponse> for Stream {
             .unwrap_or(&resp.name)
             .to_string();
 
-        // Parse quality from title
-        let quality = QUALITY_RE.find(&combined).map(|m| m.as_str().to_string());
-
         // Parse size from title
         let size = SIZE_RE
             .captures(&resp.title)
@@ -335,20 +435,6 @@ impl From<StreamResponse> for Stream {
             .captures(&resp.title)
             .and_then(|caps| caps[1].parse().ok());
 
-        // Parse HDR type
-        let hdr = parse_hdr(&combined);
-
-        // Parse video codec
-        let video_codec = parse_video_codec(&combined);
-
-        // Parse audio format
-        let audio = parse_audio(&combined);
-
-        // Parse source type
-        let source_type = SOURCE_RE
-            .find(&combined)
-            .map(|m| normalize_source(m.as_str()));
-
         // Parse language flags and convert to language names
         let languages: Vec<String> = LANG_FLAGS_RE
             .find_iter(&resp.title)
@@ -360,135 +446,38 @@ impl From<StreamResponse> for Stream {
         Self {
             provider,
             title: resp.title.clone(),
-            quality,
+            quality: release.quality,
             size,
             size_bytes,
             seeders,
+            // Debrid streams only carry the info hash in their URL
+            info_hash: resp.info_hash.or_else(|| {
+                let url = resp.url.as_deref()?;
+                Some(URL_INFO_HASH_RE.captures(url)?[1].to_lowercase())
+            }),
             url: resp.url,
-            info_hash: resp.info_hash,
             file_idx: resp.file_idx,
-            video_codec,
-            audio,
-            hdr,
-            source_type,
+            video_codec: release.video_codec,
+            audio: release.audio,
+            hdr: release.hdr,
+            source_type: release.source_type,
             languages,
             is_cached,
+            debrid: debrid.map(|(kind, _)| kind),
+            bit_depth: release.bit_depth,
+            release_group: release.release_group,
+            repack: release.repack,
+            edition: release.edition,
+            dual_audio: release.dual_audio,
+            multi_audio: release.multi_audio,
+            subtitles: release.subtitles,
+            seasons: release.seasons,
+            episodes: release.episodes,
+            season_pack: release.season_pack,
         }
     }
 }
 
-/// Parse HDR type from title, normalizing variants
-fn parse_hdr(text: &str) -> Option<String> {
-    let mut hdr_types = Vec::new();
-
-    for cap in HDR_RE.find_iter(text) {
-        let hdr = match cap.as_str().to_uppercase().as_str() {
-            "DOVI" | "DV" | "DOLBYVISION" | "DOLBY VISION" | "DOLBY.VISION" => "DV",
-            "HDR10+" => "HDR10+",
-            "HDR10" => "HDR10",
-            "HDR" => "HDR",
-            _ => continue,
-        };
-        if !hdr_types.contains(&hdr.to_string()) {
-            hdr_types.push(hdr.to_string());
-        }
-    }
-
-    if hdr_types.is_empty() {
-        None
-    } else {
-        Some(hdr_types.join(" / "))
-    }
-}
-
-/// Parse video codec from title, normalizing variants
-fn parse_video_codec(text: &str) -> Option<String> {
-    let mut codecs = Vec::new();
-
-    for cap in VIDEO_CODEC_RE.find_iter(text) {
-        let codec = match cap.as_str().to_uppercase().replace('.', "").as_str() {
-            "HEVC" | "H265" | "X265" => "HEVC",
-            "AVC" | "H264" | "X264" => "AVC",
-            "AV1" => "AV1",
-            "VC-1" => "VC-1",
-            "10BIT" | "10-BIT" => "10bit",
-            _ => continue,
-        };
-        if !codecs.contains(&codec.to_string()) {
-            codecs.push(codec.to_string());
-        }
-    }
-
-    if codecs.is_empty() {
-        None
-    } else {
-        Some(codecs.join(" "))
-    }
-}
-
-/// Parse audio format from title
-fn parse_audio(text: &str) -> Option<String> {
-    let mut audio_parts = Vec::new();
-
-    // Find audio codec
-    if let Some(cap) = AUDIO_RE.find(text) {
-        let codec = cap.as_str().to_uppercase();
-        let normalized = if codec.contains("DTS-HD") || codec.contains("DTS HD") {
-            "DTS-HD MA"
-        } else if codec.contains("TRUEHD") {
-            "TrueHD"
-        } else if codec.contains("ATMOS") {
-            "Atmos"
-        } else if codec.contains("EAC3") || codec.contains("E-AC-3") || codec.contains("DD+") {
-            "EAC3"
-        } else if codec.contains("AC3") || codec.contains("DD") {
-            "AC3"
-        } else if codec.contains("AAC") {
-            "AAC"
-        } else if codec.contains("FLAC") {
-            "FLAC"
-        } else if codec.contains("DTS") {
-            "DTS"
-        } else if codec.contains("LPCM") {
-            "LPCM"
-        } else {
-            return None;
-        };
-        audio_parts.push(normalized.to_string());
-    }
-
-    // Check for Atmos separately (can appear with other codecs like TrueHD Atmos)
-    if text.to_uppercase().contains("ATMOS") && !audio_parts.contains(&"Atmos".to_string()) {
-        audio_parts.push("Atmos".to_string());
-    }
-
-    // Find channel configuration
-    if let Some(cap) = AUDIO_CHANNELS_RE.find(text) {
-        audio_parts.push(cap.as_str().to_string());
-    }
-
-    if audio_parts.is_empty() {
-        None
-    } else {
-        Some(audio_parts.join(" "))
-    }
-}
-
-/// Normalize source type string
-fn normalize_source(source: &str) -> String {
-    match source.to_uppercase().replace(['-', ' ', '.'], "").as_str() {
-        "UHDBLURAY" => "UHD BluRay".to_string(),
-        "BLURAY" => "BluRay".to_string(),
-        "BDRIP" | "BRRIP" => "BDRip".to_string(),
-        "WEBDL" => "WEB-DL".to_string(),
-        "WEBRIP" => "WEBRip".to_string(),
-        "REMUX" => "REMUX".to_string(),
-        "HDTV" => "HDTV".to_string(),
-        "DVDRIP" => "DVDRip".to_string(),
-        _ => source.to_string(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -496,20 +485,9 @@ mod tests {
     fn make_test_stream(quality: Option<&str>) -> Stream {
         Stream {
             provider: "test".to_string(),
-            title: String::new(),
             quality: quality.map(String::from),
-            size: None,
-            size_bytes: 0,
-            seeders: None,
-            url: None,
-            info_hash: None,
-            file_idx: None,
-            video_codec: None,
-            audio: None,
-            hdr: None,
-            source_type: None,
-            languages: vec![],
             is_cached: true,
+            ..Default::default()
         }
     }
 
@@ -624,7 +602,9 @@ mod tests {
             info_hash: None,
             file_idx: None,
         };
-        assert!(Stream::from(resp).is_cached);
+        let stream = Stream::from(resp);
+        assert!(stream.is_cached);
+        assert_eq!(stream.debrid, Some(DebridKind::RealDebrid));
 
         // Uncached stream without [RD+]
         let resp = StreamResponse {
@@ -647,6 +627,92 @@ mod tests {
         assert!(Stream::from(resp).is_cached);
     }
 
+    #[test]
+    fn test_debrid_tag() {
+        assert_eq!(
+            debrid_tag("[AD+] 1337x"),
+            Some((DebridKind::AllDebrid, true))
+        );
+        assert_eq!(
+            debrid_tag("[TB download] nyaasi"),
+            Some((DebridKind::TorBox, false))
+        );
+        assert_eq!(
+            debrid_tag("[PM] yts"),
+            Some((DebridKind::Premiumize, false))
+        );
+        assert_eq!(
+            debrid_tag("[DL+] eztv"),
+            Some((DebridKind::DebridLink, true))
+        );
+        assert_eq!(debrid_tag("[⚡] 1337x"), None);
+        assert_eq!(debrid_tag("Torrentio\n4k"), None);
+    }
+
+    #[test]
+    fn test_prefer_services() {
+        let client = TorrentioClient::new(
+            TorrentioConfig::default(),
+            vec![
+                (DebridKind::AllDebrid, "ad".to_string()),
+                (DebridKind::RealDebrid, "rd".to_string()),
+            ],
+            HttpClient::default(),
+        );
+        let stream = |name: &str, hash: &str| {
+            Stream::from(StreamResponse {
+                name: name.to_string(),
+                title: "Movie 1080p".to_string(),
+                url: Some(format!(
+                    "https://torrentio.strem.fun/x/KEY/{}/null/0/M.mkv",
+                    hash
+                )),
+                info_hash: None,
+                file_idx: None,
+            })
+        };
+        let a = "a".repeat(40);
+        let b = "b".repeat(40);
+
+        let streams = client.prefer_services(vec![
+            // Cached on both: the first service in the priority list wins
+            stream("[RD+] 1337x", &a),
+            stream("[AD+] 1337x", &a),
+            // Cached on one only: the cached one wins
+            stream("[AD download] yts", &b),
+            stream("[RD+] yts", &b),
+        ]);
+        let picked: Vec<_> = streams
+            .iter()
+            .map(|s| (s.info_hash.clone().unwrap(), s.debrid))
+            .collect();
+        assert_eq!(
+            picked,
+            vec![
+                (a, Some(DebridKind::AllDebrid)),
+                (b, Some(DebridKind::RealDebrid)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_info_hash_from_debrid_url() {
+        let resp = StreamResponse {
+            name: "[RD download] 1337x".to_string(),
+            title: "Movie 1080p".to_string(),
+            url: Some(
+                "https://torrentio.strem.fun/realdebrid/KEY/ABCDEF0123456789ABCDEF0123456789ABCDEF01/null/0/Movie.mkv"
+                    .to_string(),
+            ),
+            info_hash: None,
+            file_idx: None,
+        };
+        assert_eq!(
+            Stream::from(resp).info_hash.as_deref(),
+            Some("abcdef0123456789abcdef0123456789abcdef01")
+        );
+    }
+
     #[test]
     fn test_magnet_link() {
         let mut stream = make_test_stream(Some("1080p"));
@@ -659,6 +725,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stream_url() {
+        let client = TorrentioClient::new(
+            TorrentioConfig {
+                providers: vec!["nyaasi".to_string()],
+                ..TorrentioConfig::default()
+            },
+            Vec::new(),
+            HttpClient::default(),
+        );
+        assert_eq!(
+            client.stream_url("series", "kitsu:12:1071", false),
+            "https://torrentio.strem.fun/providers=nyaasi|sort=qualitysize|qualityfilter=scr,cam/stream/series/kitsu:12:1071.json"
+        );
+        assert!(client
+            .stream_url("movie", "tt0111161", false)
+            .ends_with("/stream/movie/tt0111161.json"));
+
+        let mut client = TorrentioClient::new(
+            TorrentioConfig {
+                providers: vec!["nyaasi".to_string()],
+                ..TorrentioConfig::default()
+            },
+            vec![
+                (DebridKind::TorBox, "tb".to_string()),
+                (DebridKind::RealDebrid, "rd".to_string()),
+            ],
+            HttpClient::default(),
+        );
+        client.set_api_key(DebridKind::RealDebrid, "new".to_string());
+        assert_eq!(
+            client.build_config_string(false),
+            "providers=nyaasi|sort=qualitysize|qualityfilter=scr,cam|debridoptions=nodownloadlinks|torbox=tb|realdebrid=new"
+        );
+        assert_eq!(
+            client.build_config_string(true),
+            "providers=nyaasi|sort=qualitysize|qualityfilter=scr,cam|torbox=tb|realdebrid=new"
+        );
+    }
+
     #[test]
     fn test_is_playable() {
         let mut stream = make_test_stream(Some("1080p"));
@@ -0,0 +1,331 @@
+//! Upcoming-episode calendar
+//!
+//! Lists recently aired and upcoming episodes of followed shows (watchlist and
+//! watch history) within a configurable window around today.
+
+use std::collections::{BTreeSet, HashSet};
+
+use chrono::{Duration, Local, NaiveDate};
+use tokio::task::JoinSet;
+
+use crate::api::{Episode, MediaSource, MediaType, TmdbClient, TvAiring};
+use crate::config::CalendarConfig;
+use crate::error::ApiError;
+use crate::history::{FollowedShow, WatchHistory};
+
+/// Maximum number of followed shows checked for new episodes
+pub const MAX_CALENDAR_SHOWS: usize = 50;
+
+/// Date range covered by the calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarWindow {
+    pub today: NaiveDate,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl CalendarWindow {
+    /// Build the window around a given day
+    pub fn new(today: NaiveDate, config: &CalendarConfig) -> Self {
+        Self {
+            today,
+            start: today - Duration::days(config.past_days as i64),
+            end: today + Duration::days(config.future_days as i64),
+        }
+    }
+
+    /// Build the window around the current local date
+    pub fn from_config(config: &CalendarConfig) -> Self {
+        Self::new(Local::now().date_naive(), config)
+    }
+
+    /// Check if a date falls within the window (inclusive)
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        date >= self.start && date <= self.end
+    }
+}
+
+/// A single episode on the calendar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEntry {
+    /// TMDB ID of the show
+    pub tmdb_id: i32,
+    /// Title of the show
+    pub show_title: String,
+    pub season: u32,
+    pub episode: u32,
+    /// Episode title
+    pub episode_title: String,
+    pub air_date: NaiveDate,
+    /// Whether the episode is in the watch history
+    pub watched: bool,
+}
+
+impl CalendarEntry {
+    /// Whether the episode has aired as of a given day
+    pub fn is_aired(&self, today: NaiveDate) -> bool {
+        self.air_date <= today
+    }
+
+    /// Whether the episode has aired but hasn't been watched yet
+    pub fn is_new(&self, today: NaiveDate) -> bool {
+        self.is_aired(today) && !self.watched
+    }
+
+    /// Get a display string for the episode (e.g., "S01E05")
+    pub fn episode_display(&self) -> String {
+        format!("S{:02}E{:02}", self.season, self.episode)
+    }
+}
+
+/// Build calendar entries for the episodes of one season that air within the window
+pub fn season_entries(
+    show: &FollowedShow,
+    season: u32,
+    episodes: &[Episode],
+    window: &CalendarWindow,
+) -> Vec<CalendarEntry> {
+    episodes
+        .iter()
+        .filter_map(|ep| {
+            let air_date = ep
+                .air_date
+                .as_deref()
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())?;
+            window.contains(air_date).then(|| CalendarEntry {
+                tmdb_id: show.tmdb_id,
+                show_title: show.title.clone(),
+                season,
+                episode: ep.number,
+                episode_title: ep.title.clone(),
+                air_date,
+                watched: false,
+            })
+        })
+        .collect()
+}
+
+/// Seasons that may have episodes airing within the window, based on the airing schedule
+pub fn seasons_in_window(airing: &TvAiring, window: &CalendarWindow) -> BTreeSet<u32> {
+    let mut seasons = BTreeSet::new();
+
+    let air_date = |date: &Option<String>| {
+        date.as_deref()
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+    };
+
+    // The latest aired episode's season matters if it aired recently
+    if let Some(last) = &airing.last {
+        if air_date(&last.air_date).is_some_and(|d| d >= window.start) {
+            seasons.insert(last.season);
+        }
+    }
+
+    // The next episode's season matters if it airs soon (or has no date yet)
+    if let Some(next) = &airing.next {
+        if air_date(&next.air_date).is_none_or(|d| d <= window.end) {
+            seasons.insert(next.season);
+        }
+    }
+
+    seasons
+}
+
+/// Fetch calendar entries for followed shows from TMDB
+///
+/// Entries are sorted by air date; watched flags are left unset (see [`apply_watched`]).
+/// Shows that fail to load are skipped; fails only if none of them could be loaded.
+pub async fn fetch_calendar(
+    tmdb: &TmdbClient,
+    shows: Vec<FollowedShow>,
+    window: CalendarWindow,
+) -> Result<Vec<CalendarEntry>, ApiError> {
+    let mut tasks = JoinSet::new();
+
+    for show in shows.into_iter().take(MAX_CALENDAR_SHOWS) {
+        let tmdb = tmdb.clone();
+        tasks.spawn(async move {
+            let airing = match tmdb.get_tv_airing(show.tmdb_id).await {
+                Ok(airing) => airing,
+                Err(e) => {
+                    tracing::debug!("Failed to load schedule for {}: {}", show.title, e);
+                    return Err(e);
+                }
+            };
+
+            let mut entries = Vec::new();
+            for season in seasons_in_window(&airing, &window) {
+                if let Ok(episodes) = tmdb.get_season_episodes(show.tmdb_id, season).await {
+                    entries.extend(season_entries(&show, season, &episodes, &window));
+                }
+            }
+            Ok(entries)
+        });
+    }
+
+    let mut entries = Vec::new();
+    let mut loaded = false;
+    let mut last_error = None;
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok(Ok(show_entries)) => {
+                loaded = true;
+                entries.extend(show_entries);
+            }
+            Ok(Err(e)) => last_error = Some(e),
+            Err(_) => {}
+        }
+    }
+    if let (false, Some(e)) = (loaded, last_error) {
+        return Err(e);
+    }
+
+    entries.sort_by(|a, b| {
+        (a.air_date, &a.show_title, a.season, a.episode).cmp(&(
+            b.air_date,
+            &b.show_title,
+            b.season,
+            b.episode,
+        ))
+    });
+    Ok(entries)
+}
+
+/// Mark entries that are in the watch history
+pub fn apply_watched(entries: &mut [CalendarEntry], history: &WatchHistory) {
+    for entry in entries {
+        entry.watched = history.is_watched(
+            &MediaSource::Tmdb { id: entry.tmdb_id },
+            MediaType::TvShow,
+            entry.season,
+            entry.episode,
+        );
+    }
+}
+
+/// TMDB IDs of shows with aired, unwatched episodes (for "new episode" badges)
+pub fn shows_with_new_episodes(entries: &[CalendarEntry], today: NaiveDate) -> HashSet<i32> {
+    entries
+        .iter()
+        .filter(|e| e.is_new(today))
+        .map(|e| e.tmdb_id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::media::AiringEpisode;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn make_window() -> CalendarWindow {
+        CalendarWindow::new(date("2024-03-10"), &CalendarConfig::default())
+    }
+
+    fn make_episode(number: u32, air_date: Option<&str>) -> Episode {
+        Episode {
+            number,
+            title: format!("Episode {}", number),
+            air_date: air_date.map(String::from),
+            overview: None,
+            runtime: None,
+            vote_average: None,
+        }
+    }
+
+    fn make_airing(last: Option<(u32, &str)>, next: Option<(u32, Option<&str>)>) -> TvAiring {
+        TvAiring {
+            last: last.map(|(season, d)| AiringEpisode {
+                season,
+                episode: 1,
+                air_date: Some(d.to_string()),
+            }),
+            next: next.map(|(season, d)| AiringEpisode {
+                season,
+                episode: 1,
+                air_date: d.map(String::from),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_window() {
+        let window = make_window();
+        assert_eq!(window.start, date("2024-03-03"));
+        assert_eq!(window.end, date("2024-03-24"));
+        assert!(window.contains(date("2024-03-03")));
+        assert!(window.contains(date("2024-03-24")));
+        assert!(!window.contains(date("2024-03-02")));
+        assert!(!window.contains(date("2024-03-25")));
+    }
+
+    #[test]
+    fn test_season_entries_filters_by_window() {
+        let show = FollowedShow {
+            tmdb_id: 1,
+            title: "Show".to_string(),
+        };
+        let episodes = vec![
+            make_episode(1, Some("2024-02-25")),
+            make_episode(2, Some("2024-03-03")),
+            make_episode(3, Some("2024-03-10")),
+            make_episode(4, Some("2024-03-17")),
+            make_episode(5, Some("2024-03-31")),
+            make_episode(6, None),
+        ];
+
+        let entries = season_entries(&show, 2, &episodes, &make_window());
+        let numbers: Vec<u32> = entries.iter().map(|e| e.episode).collect();
+        assert_eq!(numbers, vec![2, 3, 4]);
+        assert_eq!(entries[0].episode_display(), "S02E02");
+    }
+
+    #[test]
+    fn test_seasons_in_window() {
+        let window = make_window();
+
+        // Mid-season: last and next in the same season
+        let airing = make_airing(Some((3, "2024-03-08")), Some((3, Some("2024-03-15"))));
+        assert_eq!(seasons_in_window(&airing, &window), BTreeSet::from([3]));
+
+        // Finale aired recently, next season announced far out
+        let airing = make_airing(Some((3, "2024-03-08")), Some((4, Some("2024-10-01"))));
+        assert_eq!(seasons_in_window(&airing, &window), BTreeSet::from([3]));
+
+        // Next season premiere without a date yet
+        let airing = make_airing(Some((3, "2023-06-01")), Some((4, None)));
+        assert_eq!(seasons_in_window(&airing, &window), BTreeSet::from([4]));
+
+        // Ended show
+        let airing = make_airing(Some((5, "2020-01-01")), None);
+        assert!(seasons_in_window(&airing, &window).is_empty());
+    }
+
+    #[test]
+    fn test_new_episodes() {
+        let today = date("2024-03-10");
+        let entry = |tmdb_id, air_date: &str, watched| CalendarEntry {
+            tmdb_id,
+            show_title: "Show".to_string(),
+            season: 1,
+            episode: 1,
+            episode_title: "Episode".to_string(),
+            air_date: date(air_date),
+            watched,
+        };
+        let entries = vec![
+            entry(1, "2024-03-09", false),
+            entry(2, "2024-03-09", true),
+            entry(3, "2024-03-11", false),
+            entry(4, "2024-03-10", false),
+        ];
+
+        assert_eq!(
+            shows_with_new_episodes(&entries, today),
+            HashSet::from([1, 4])
+        );
+    }
+}
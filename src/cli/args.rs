@@ -1,5 +1,7 @@
 use clap::{Parser, Subcommand};
 
+use crate::config::AutoSelect;
+
 /// miru - A terminal-native streaming CLI for movies and TV shows
 #[derive(Parser)]
 #[command(name = "miru")]
@@ -14,6 +16,10 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub dl: bool,
 
+    /// Pick a source automatically, overriding `playback.auto_select`
+    #[arg(long, global = true, value_name = "MODE")]
+    pub auto_select: Option<AutoSelect>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -45,6 +51,10 @@ pub enum Commands {
         query: Option<String>,
     },
 
+    /// Show upcoming and recently aired episodes of followed shows
+    #[command(alias = "cal")]
+    Calendar,
+
     /// Play first result, first unwatched episode (coming soon)
     #[command(alias = "p")]
     Play {
@@ -1,7 +1,12 @@
 use std::io::{self, Write};
 
-use crate::config::{config_path, load_config, save_config, Config, PlayerConfig};
-use crate::error::Result;
+use crate::api::{HttpClient, TmdbClient};
+use crate::calendar::{apply_watched, fetch_calendar, CalendarWindow, MAX_CALENDAR_SHOWS};
+use crate::config::{
+    config_path, load_config, save_config, AutoSelect, Config, DebridKind, PlayerConfig,
+};
+use crate::error::{ConfigError, Result};
+use crate::history::WatchHistory;
 use crate::ui::{App, AppMode, InitWizard};
 
 /// Run the first-time setup wizard
@@ -22,8 +27,9 @@ pub async fn init() -> Result<()> {
         }
     }
 
-    // Run the TUI wizard
-    let mut wizard = InitWizard::new(config_exists);
+    // Run the TUI wizard, keeping the network settings (e.g. a proxy) of an existing config
+    let http = load_config().map(|config| config.http).unwrap_or_default();
+    let mut wizard = InitWizard::new(config_exists, http)?;
     let completed = wizard.run().await?;
 
     if completed {
@@ -35,6 +41,10 @@ pub async fn init() -> Result<()> {
     Ok(())
 }
 
+/// Keys accepted by `miru config --set`
+const CONFIG_KEYS: &str = "rd_api_key, alldebrid_api_key, premiumize_api_key, torbox_api_key, \
+     debridlink_api_key, debrid_services, tmdb_api_key, player_command";
+
 /// Handle the config command
 pub async fn config(show: bool, set: Option<String>, reset: bool) -> Result<()> {
     if reset {
@@ -51,26 +61,50 @@ pub async fn config(show: bool, set: Option<String>, reset: bool) -> Result<()>
         let parts: Vec<&str> = key_value.splitn(2, '=').collect();
         if parts.len() != 2 {
             println!("Invalid format. Use: --set key=value");
-            println!("Available keys: rd_api_key, tmdb_api_key, player_command");
+            println!("Available keys: {}", CONFIG_KEYS);
             return Ok(());
         }
 
         let mut config =
             load_config().unwrap_or_else(|_| Config::new(String::new(), String::new()));
 
-        match parts[0] {
-            "rd_api_key" => {
+        let debrid_kind = parts[0]
+            .strip_suffix("_api_key")
+            .and_then(DebridKind::from_key);
+
+        match (parts[0], debrid_kind) {
+            (_, Some(kind)) => {
+                config.set_debrid_api_key(kind, parts[1].to_string());
+            }
+            ("rd_api_key", _) => {
                 config.real_debrid.api_key = parts[1].to_string();
             }
-            "tmdb_api_key" => {
+            ("debrid_services", _) => {
+                let mut services = Vec::new();
+                for key in parts[1].split(',').map(str::trim).filter(|k| !k.is_empty()) {
+                    match DebridKind::from_key(key) {
+                        Some(kind) => services.push(kind),
+                        None => {
+                            println!("Unknown debrid service: {}", key);
+                            println!(
+                                "Available services: {}",
+                                DebridKind::ALL.map(DebridKind::key).join(", ")
+                            );
+                            return Ok(());
+                        }
+                    }
+                }
+                config.debrid.services = services;
+            }
+            ("tmdb_api_key", _) => {
                 config.tmdb.api_key = parts[1].to_string();
             }
-            "player_command" => {
+            ("player_command", _) => {
                 config.player.command = parts[1].to_string();
             }
             _ => {
                 println!("Unknown key: {}", parts[0]);
-                println!("Available keys: rd_api_key, tmdb_api_key, player_command");
+                println!("Available keys: {}", CONFIG_KEYS);
                 return Ok(());
             }
         }
@@ -89,6 +123,17 @@ pub async fn config(show: bool, set: Option<String>, reset: bool) -> Result<()>
                     "api_key = \"{}...\"",
                     &config.real_debrid.api_key[..8.min(config.real_debrid.api_key.len())]
                 );
+                println!("\n[debrid]");
+                let services = config.debrid_services();
+                if services.is_empty() {
+                    println!("services = (none, using direct P2P streaming)");
+                } else {
+                    let names: Vec<&str> = services.iter().map(|(kind, _)| kind.key()).collect();
+                    println!("services = {:?}", names);
+                }
+                if config.debrid.check_availability {
+                    println!("check_availability = true");
+                }
                 println!("\n[tmdb]");
                 if config.tmdb.api_key.is_empty() {
                     println!("api_key = (not configured)");
@@ -124,9 +169,15 @@ pub async fn config(show: bool, set: Option<String>, reset: bool) -> Result<()>
     println!("  --reset        Reset configuration to defaults");
     println!();
     println!("Available keys for --set:");
-    println!("  rd_api_key      Real-Debrid API key");
-    println!("  tmdb_api_key    TMDB API key");
-    println!("  player_command  Media player command (default: mpv)");
+    println!("  rd_api_key          Real-Debrid API key");
+    println!("  alldebrid_api_key   AllDebrid API key");
+    println!("  premiumize_api_key  Premiumize API key");
+    println!("  torbox_api_key      TorBox API key");
+    println!("  debridlink_api_key  Debrid-Link API key");
+    println!("  debrid_services     Debrid services to use, in priority order");
+    println!("                      (e.g. torbox,realdebrid)");
+    println!("  tmdb_api_key        TMDB API key");
+    println!("  player_command      Media player command (default: mpv)");
 
     Ok(())
 }
@@ -136,12 +187,16 @@ pub async fn search(
     query: Option<String>,
     player_override: Option<PlayerConfig>,
     app_mode: AppMode,
+    auto_select: Option<AutoSelect>,
 ) -> Result<()> {
     let mut config = load_config()?;
     if let Some(player_config) = player_override {
-        config.player = player_config;
+        config.player.override_with(player_config);
+    }
+    let mut app = App::new(config, app_mode)?;
+    if let Some(auto_select) = auto_select {
+        app.set_auto_select(auto_select);
     }
-    let mut app = App::new(config, app_mode);
 
     if let Some(q) = query {
         app.set_initial_query(&q);
@@ -150,8 +205,76 @@ pub async fn search(
     app.run().await
 }
 
+/// Print upcoming and recently aired episodes of followed shows
+pub async fn calendar() -> Result<()> {
+    let config = load_config()?;
+    if config.tmdb.api_key.is_empty() {
+        return Err(ConfigError::MissingTmdbApiKey.into());
+    }
+    let history = WatchHistory::open()?;
+    let window = CalendarWindow::from_config(&config.calendar);
+
+    let shows = history.get_followed_shows(MAX_CALENDAR_SHOWS);
+    if shows.is_empty() {
+        println!("No followed shows. Add shows to your watchlist or watch an episode first.");
+        return Ok(());
+    }
+
+    let tmdb = TmdbClient::new(config.tmdb.api_key.clone(), HttpClient::new(&config.http)?);
+    let mut entries = fetch_calendar(&tmdb, shows, window).await?;
+    apply_watched(&mut entries, &history);
+
+    println!(
+        "Episodes from {} to {}\n",
+        window.start.format("%b %-d"),
+        window.end.format("%b %-d")
+    );
+
+    if entries.is_empty() {
+        println!("No episodes aired or airing for your followed shows in this window.");
+        return Ok(());
+    }
+
+    let mut current_date = None;
+    for entry in &entries {
+        if current_date != Some(entry.air_date) {
+            if current_date.is_some() {
+                println!();
+            }
+            let label = if entry.air_date == window.today {
+                " (today)"
+            } else {
+                ""
+            };
+            println!("{}{}", entry.air_date.format("%a %b %-d, %Y"), label);
+            current_date = Some(entry.air_date);
+        }
+
+        let status = if entry.watched {
+            "[x]  "
+        } else if entry.is_aired(window.today) {
+            "[new]"
+        } else {
+            "[ ]  "
+        };
+        println!(
+            "  {} {} {} - {}",
+            status,
+            entry.show_title,
+            entry.episode_display(),
+            entry.episode_title
+        );
+    }
+
+    Ok(())
+}
+
 /// Run interactive mode (default)
-pub async fn interactive(player_override: Option<PlayerConfig>, app_mode: AppMode) -> Result<()> {
+pub async fn interactive(
+    player_override: Option<PlayerConfig>,
+    app_mode: AppMode,
+    auto_select: Option<AutoSelect>,
+) -> Result<()> {
     let mut config = match load_config() {
         Ok(c) => c,
         Err(_) => {
@@ -162,9 +285,12 @@ pub async fn interactive(player_override: Option<PlayerConfig>, app_mode: AppMod
     };
 
     if let Some(player_config) = player_override {
-        config.player = player_config;
+        config.player.override_with(player_config);
     }
 
-    let mut app = App::new(config, app_mode);
+    let mut app = App::new(config, app_mode)?;
+    if let Some(auto_select) = auto_select {
+        app.set_auto_select(auto_select);
+    }
     app.run().await
 }
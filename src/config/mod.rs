@@ -2,4 +2,8 @@ mod loader;
 mod schema;
 
 pub use loader::{config_path, load_config, save_config};
-pub use schema::{Config, PlayerConfig, ThemeColors, TorrentioConfig, UiConfig};
+pub use schema::{
+    AnimeConfig, AutoSelect, CalendarConfig, Config, DebridKind, FilterAction, FilterConfig,
+    FilterRule, HttpConfig, PlaybackCapabilities, PlayerConfig, RealDebridOAuth, ScoringConfig,
+    ScoringProfile, ThemeColors, TorrentioConfig, UiConfig, UnsupportedAction,
+};
@@ -1,12 +1,18 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    /// Real-Debrid configuration (optional - if not set, direct P2P streaming is used)
+    /// Real-Debrid configuration (optional - without a debrid service, direct P2P streaming is used)
     #[serde(default)]
     pub real_debrid: RealDebridConfig,
 
+    /// Other debrid services, and which services to use
+    #[serde(default)]
+    pub debrid: DebridConfig,
+
     #[serde(default)]
     pub tmdb: TmdbConfig,
 
@@ -19,9 +25,37 @@ pub struct Config {
     #[serde(default)]
     pub ui: UiConfig,
 
-    /// Direct P2P streaming configuration (used when Real-Debrid is not configured)
+    /// Direct P2P streaming configuration (used when no debrid service is configured)
     #[serde(default)]
     pub streaming: StreamingConfig,
+
+    /// Anime ID mapping configuration (TMDB -> Kitsu/AniList)
+    #[serde(default)]
+    pub anime: AnimeConfig,
+
+    /// Upcoming-episode calendar configuration
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+
+    /// HTTP client configuration (timeouts, retries, rate limits, proxy)
+    #[serde(default)]
+    pub http: HttpConfig,
+
+    /// Metadata provider configuration
+    #[serde(default)]
+    pub metadata: MetadataConfig,
+
+    /// Source scoring profiles
+    #[serde(default)]
+    pub scoring: ScoringConfig,
+
+    /// Rules that hide, demote or allow releases
+    #[serde(default)]
+    pub filters: FilterConfig,
+
+    /// Playback device configuration
+    #[serde(default)]
+    pub playback: PlaybackConfig,
 }
 
 impl Config {
@@ -30,7 +64,9 @@ impl Config {
         Self {
             real_debrid: RealDebridConfig {
                 api_key: rd_api_key,
+                ..Default::default()
             },
+            debrid: DebridConfig::default(),
             tmdb: TmdbConfig {
                 api_key: tmdb_api_key,
             },
@@ -38,17 +74,66 @@ impl Config {
             player: PlayerConfig::default(),
             ui: UiConfig::default(),
             streaming: StreamingConfig::default(),
+            anime: AnimeConfig::default(),
+            calendar: CalendarConfig::default(),
+            http: HttpConfig::default(),
+            metadata: MetadataConfig::default(),
+            scoring: ScoringConfig::default(),
+            filters: FilterConfig::default(),
+            playback: PlaybackConfig::default(),
+        }
+    }
+
+    /// API key of a debrid service (empty if not configured)
+    pub fn debrid_api_key(&self, kind: DebridKind) -> &str {
+        match kind {
+            DebridKind::RealDebrid => &self.real_debrid.api_key,
+            DebridKind::AllDebrid => &self.debrid.alldebrid_api_key,
+            DebridKind::Premiumize => &self.debrid.premiumize_api_key,
+            DebridKind::TorBox => &self.debrid.torbox_api_key,
+            DebridKind::DebridLink => &self.debrid.debridlink_api_key,
+        }
+    }
+
+    /// Set the API key of a debrid service
+    pub fn set_debrid_api_key(&mut self, kind: DebridKind, api_key: String) {
+        match kind {
+            DebridKind::RealDebrid => self.real_debrid.api_key = api_key,
+            DebridKind::AllDebrid => self.debrid.alldebrid_api_key = api_key,
+            DebridKind::Premiumize => self.debrid.premiumize_api_key = api_key,
+            DebridKind::TorBox => self.debrid.torbox_api_key = api_key,
+            DebridKind::DebridLink => self.debrid.debridlink_api_key = api_key,
         }
     }
 
-    /// Check if the config has a valid Real-Debrid API key
-    pub fn has_rd_api_key(&self) -> bool {
-        !self.real_debrid.api_key.is_empty()
+    /// Debrid services to use, in priority order, with their API keys
+    ///
+    /// Without a `services` list, every service with an API key is used (Real-Debrid first).
+    pub fn debrid_services(&self) -> Vec<(DebridKind, String)> {
+        let kinds = if self.debrid.services.is_empty() {
+            DebridKind::ALL.as_slice()
+        } else {
+            self.debrid.services.as_slice()
+        };
+
+        let mut services: Vec<(DebridKind, String)> = Vec::new();
+        for &kind in kinds {
+            let api_key = self.debrid_api_key(kind);
+            if !api_key.is_empty() && !services.iter().any(|(k, _)| *k == kind) {
+                services.push((kind, api_key.to_string()));
+            }
+        }
+        services
     }
 
-    /// Check if direct P2P streaming should be used (no RD key configured)
+    /// Check if any debrid service is configured
+    pub fn has_debrid(&self) -> bool {
+        !self.debrid_services().is_empty()
+    }
+
+    /// Check if direct P2P streaming should be used (no debrid service configured)
     pub fn use_direct_streaming(&self) -> bool {
-        !self.has_rd_api_key()
+        !self.has_debrid()
     }
 }
 
@@ -60,12 +145,164 @@ impl Config {
 /// - **Without Real-Debrid**: Use direct P2P streaming to download torrents to your device
 ///
 /// To add a Real-Debrid account later, run: `miru config --set rd_api_key=YOUR_KEY`
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RealDebridConfig {
     /// Real-Debrid API key (optional). Leave empty to use direct P2P streaming.
     /// Get yours at: https://real-debrid.com/apitoken
     #[serde(default)]
     pub api_key: String,
+
+    /// Warn on the search screen when premium ends within this many days
+    #[serde(default = "default_expiry_warning_days")]
+    pub expiry_warning_days: u64,
+
+    /// OAuth credentials from logging in with a device code (`miru init`). `api_key`
+    /// then holds the access token, which is refreshed with these when it expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth: Option<RealDebridOAuth>,
+}
+
+impl Default for RealDebridConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            expiry_warning_days: default_expiry_warning_days(),
+            oauth: None,
+        }
+    }
+}
+
+/// Real-Debrid OAuth credentials (device-code login)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RealDebridOAuth {
+    /// Client ID issued for this device
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+    /// When the access token expires (Unix time)
+    pub expires_at: i64,
+}
+
+/// A debrid service that Torrentio can resolve streams with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DebridKind {
+    RealDebrid,
+    AllDebrid,
+    Premiumize,
+    TorBox,
+    DebridLink,
+}
+
+impl DebridKind {
+    /// All services, in the default priority order
+    pub const ALL: [DebridKind; 5] = [
+        DebridKind::RealDebrid,
+        DebridKind::AllDebrid,
+        DebridKind::Premiumize,
+        DebridKind::TorBox,
+        DebridKind::DebridLink,
+    ];
+
+    /// Display name
+    pub fn name(self) -> &'static str {
+        match self {
+            DebridKind::RealDebrid => "Real-Debrid",
+            DebridKind::AllDebrid => "AllDebrid",
+            DebridKind::Premiumize => "Premiumize",
+            DebridKind::TorBox => "TorBox",
+            DebridKind::DebridLink => "Debrid-Link",
+        }
+    }
+
+    /// Key in config.toml and in Torrentio's config string ("realdebrid", ...)
+    pub fn key(self) -> &'static str {
+        match self {
+            DebridKind::RealDebrid => "realdebrid",
+            DebridKind::AllDebrid => "alldebrid",
+            DebridKind::Premiumize => "premiumize",
+            DebridKind::TorBox => "torbox",
+            DebridKind::DebridLink => "debridlink",
+        }
+    }
+
+    /// Tag in Torrentio stream names ("[RD+]" is cached on Real-Debrid)
+    pub fn tag(self) -> &'static str {
+        match self {
+            DebridKind::RealDebrid => "RD",
+            DebridKind::AllDebrid => "AD",
+            DebridKind::Premiumize => "PM",
+            DebridKind::TorBox => "TB",
+            DebridKind::DebridLink => "DL",
+        }
+    }
+
+    /// Where to get an API key
+    pub fn api_key_url(self) -> &'static str {
+        match self {
+            DebridKind::RealDebrid => "https://real-debrid.com/apitoken",
+            DebridKind::AllDebrid => "https://alldebrid.com/apikeys",
+            DebridKind::Premiumize => "https://www.premiumize.me/account",
+            DebridKind::TorBox => "https://torbox.app/settings",
+            DebridKind::DebridLink => "https://debrid-link.com/webapp/apikey",
+        }
+    }
+
+    /// Whether the service's API can tell which torrents it has cached
+    ///
+    /// Real-Debrid and AllDebrid have shut down their instant-availability endpoints;
+    /// only Torrentio knows what they have cached.
+    pub fn checks_cache_by_hash(self) -> bool {
+        !matches!(self, DebridKind::RealDebrid | DebridKind::AllDebrid)
+    }
+
+    /// Look up a service by its key
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.key() == key)
+    }
+}
+
+/// Debrid services other than Real-Debrid, and which services to use
+///
+/// Several services can be used at once: Torrentio looks sources up on all of
+/// them, and a source cached on more than one plays through the first in `services`.
+///
+/// With `check_availability`, the API keys of services that can be asked directly
+/// which torrents they have cached aren't sent to Torrentio: it lists plain torrents,
+/// and miru asks those services itself. Real-Debrid and AllDebrid keys still go to
+/// Torrentio (see [`DebridKind::checks_cache_by_hash`]).
+///
+/// Example in config.toml:
+/// ```text
+/// [debrid]
+/// services = ["alldebrid", "realdebrid"]
+/// alldebrid_api_key = "..."
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DebridConfig {
+    /// Services to use, in priority order (default: every service with an API key)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<DebridKind>,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub alldebrid_api_key: String,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub premiumize_api_key: String,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub torbox_api_key: String,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub debridlink_api_key: String,
+
+    /// Check caches by info hash with the services' own APIs instead of through Torrentio
+    pub check_availability: bool,
+}
+
+fn default_expiry_warning_days() -> u64 {
+    7
 }
 
 /// TMDB configuration (required)
@@ -75,20 +312,12 @@ pub struct RealDebridConfig {
 ///
 /// Get your API key at: https://www.themoviedb.org/settings/api
 /// Use the "API Key (v3 auth)", not the Read Access Token.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TmdbConfig {
     /// TMDB API key (required). Must be obtained from https://www.themoviedb.org/settings/api
     pub api_key: String,
 }
 
-impl Default for TmdbConfig {
-    fn default() -> Self {
-        Self {
-            api_key: String::new(),
-        }
-    }
-}
-
 /// Torrentio addon configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TorrentioConfig {
@@ -146,6 +375,15 @@ pub struct PlayerConfig {
     /// Additional arguments passed to player
     #[serde(default)]
     pub args: Vec<String>,
+
+    /// Preferred audio languages, most preferred first (names or ISO 639 codes).
+    /// Favors matching sources and is passed to mpv/VLC for track selection.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preferred_audio_languages: Vec<String>,
+
+    /// Preferred subtitle languages, most preferred first (names or ISO 639 codes)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preferred_subtitle_languages: Vec<String>,
 }
 
 impl Default for PlayerConfig {
@@ -153,6 +391,8 @@ impl Default for PlayerConfig {
         Self {
             command: default_player_command(),
             args: vec!["--fullscreen".to_string()],
+            preferred_audio_languages: Vec::new(),
+            preferred_subtitle_languages: Vec::new(),
         }
     }
 }
@@ -163,8 +403,16 @@ impl PlayerConfig {
         Self {
             command: "vlc".to_string(),
             args: vec!["--fullscreen".to_string(), "--play-and-exit".to_string()],
+            preferred_audio_languages: Vec::new(),
+            preferred_subtitle_languages: Vec::new(),
         }
     }
+
+    /// Use another player command and arguments, keeping the language preferences
+    pub fn override_with(&mut self, player: PlayerConfig) {
+        self.command = player.command;
+        self.args = player.args;
+    }
 }
 
 fn default_player_command() -> String {
@@ -276,6 +524,538 @@ fn default_cleanup_after_playback() -> bool {
     true
 }
 
+/// Anime configuration
+///
+/// Long-running anime are often released with absolute episode numbering that doesn't
+/// match TMDB seasons. A mapping from TMDB to Kitsu/AniList IDs lets miru look up sources
+/// through Torrentio's Kitsu catalog instead.
+///
+/// Example in config.toml:
+/// ```text
+/// [anime]
+/// mapping_file = "/home/me/anime-mapping.json"
+/// mapping_url = "http://localhost:8080/anime-mapping.json"
+/// absolute_numbering = true
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnimeConfig {
+    /// Local mapping file (default: ~/.config/miru/anime-mapping.json)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mapping_file: Option<String>,
+
+    /// Mapping service URL returning the same JSON format (tried before the local file)
+    #[serde(default)]
+    pub mapping_url: String,
+
+    /// Show absolute episode numbers by default for anime (toggle with 'n')
+    #[serde(default)]
+    pub absolute_numbering: bool,
+}
+
+/// Calendar configuration
+///
+/// The calendar lists episodes of followed shows (watchlist and watch history)
+/// that aired or will air within a window around today.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarConfig {
+    /// Days back to include recently aired episodes (default: 7)
+    #[serde(default = "default_calendar_past_days")]
+    pub past_days: u32,
+
+    /// Days ahead to include upcoming episodes (default: 14)
+    #[serde(default = "default_calendar_future_days")]
+    pub future_days: u32,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            past_days: default_calendar_past_days(),
+            future_days: default_calendar_future_days(),
+        }
+    }
+}
+
+fn default_calendar_past_days() -> u32 {
+    7
+}
+
+fn default_calendar_future_days() -> u32 {
+    14
+}
+
+/// HTTP client configuration
+///
+/// Shared by all API clients (TMDB, Torrentio, Real-Debrid) and direct downloads.
+///
+/// Example in config.toml:
+/// ```text
+/// [http]
+/// connect_timeout_secs = 10
+/// read_timeout_secs = 30
+/// max_retries = 3
+/// proxy = "socks5://127.0.0.1:9050"
+///
+/// [http.rate_limits]
+/// "torrentio.strem.fun" = 2.0
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpConfig {
+    /// Seconds to wait for a connection to be established (default: 10)
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+
+    /// Seconds to wait for data on an open connection before giving up (default: 30)
+    #[serde(default = "default_read_timeout_secs")]
+    pub read_timeout_secs: u64,
+
+    /// Retries for idempotent requests on network errors, 429 and 5xx (default: 3)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Base delay for exponential backoff between retries in milliseconds (default: 500)
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+
+    /// Maximum requests per second to any single host, 0 for no limit (default: 20)
+    #[serde(default = "default_requests_per_second")]
+    pub requests_per_second: f64,
+
+    /// Per-host overrides of `requests_per_second` (also applies to subdomains)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rate_limits: HashMap<String, f64>,
+
+    /// User-Agent header sent with every request
+    #[serde(default = "default_user_agent")]
+    pub user_agent: String,
+
+    /// Proxy for all requests (http://, https:// or socks5:// URL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: default_connect_timeout_secs(),
+            read_timeout_secs: default_read_timeout_secs(),
+            max_retries: default_max_retries(),
+            retry_base_delay_ms: default_retry_base_delay_ms(),
+            requests_per_second: default_requests_per_second(),
+            rate_limits: HashMap::new(),
+            user_agent: default_user_agent(),
+            proxy: None,
+        }
+    }
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_read_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_base_delay_ms() -> u64 {
+    500
+}
+
+fn default_requests_per_second() -> f64 {
+    20.0
+}
+
+fn default_user_agent() -> String {
+    format!("miru/{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Metadata provider configuration
+///
+/// TMDB is the default provider. A local JSON catalog can be searched instead, or
+/// alongside it for titles already in the watch history.
+///
+/// Example in config.toml:
+/// ```text
+/// [metadata]
+/// provider = "local"
+/// local_catalog = "/home/me/catalog.json"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataConfig {
+    /// Provider used for searches: "tmdb" or "local" (default: "tmdb")
+    #[serde(default = "default_metadata_provider")]
+    pub provider: String,
+
+    /// Local catalog file (default: ~/.config/miru/catalog.json, loaded if it exists)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_catalog: Option<String>,
+}
+
+impl Default for MetadataConfig {
+    fn default() -> Self {
+        Self {
+            provider: default_metadata_provider(),
+            local_catalog: None,
+        }
+    }
+}
+
+fn default_metadata_provider() -> String {
+    "tmdb".to_string()
+}
+
+/// Source scoring configuration
+///
+/// Sources are ranked with a named scoring profile. The built-in profiles are
+/// "default", "bandwidth-saver", "home-theater" and "anime"; profiles defined under
+/// `[scoring.profiles]` override them or add new ones. Press 'p' on the sources screen
+/// to switch profiles for the session.
+///
+/// Example in config.toml:
+/// ```text
+/// [scoring]
+/// movie_profile = "home-theater"
+/// anime_profile = "anime"
+///
+/// [scoring.profiles.living-room]
+/// cached_bonus = 1500.0
+/// hdr_bonus = 300.0
+///
+/// [scoring.profiles.living-room.quality]
+/// "2160p" = 1400.0
+/// "1080p" = 1000.0
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringConfig {
+    /// Profile for movies (default: "default")
+    #[serde(default = "default_scoring_profile")]
+    pub movie_profile: String,
+
+    /// Profile for TV episodes (default: "default")
+    #[serde(default = "default_scoring_profile")]
+    pub tv_profile: String,
+
+    /// Profile for anime, movies and episodes alike (default: the movie/TV profile)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anime_profile: Option<String>,
+
+    /// Custom profiles by name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profiles: HashMap<String, ScoringProfile>,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            movie_profile: default_scoring_profile(),
+            tv_profile: default_scoring_profile(),
+            anime_profile: None,
+            profiles: HashMap::new(),
+        }
+    }
+}
+
+fn default_scoring_profile() -> String {
+    "default".to_string()
+}
+
+/// Weights used to score sources
+///
+/// Fields left out of a custom profile keep the values of the "default" profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringProfile {
+    /// Points by resolution
+    pub quality: QualityWeights,
+
+    /// Bonus for sources cached on Real-Debrid
+    pub cached_bonus: f64,
+
+    /// Bonus per detected language
+    pub language_bonus: f64,
+
+    /// Multiplier for the log2(seeders + 1) bonus
+    pub seeder_weight: f64,
+
+    /// Size penalty multiplier: the penalty is `size_weight * size_gb ^ size_exponent`
+    pub size_weight: f64,
+
+    /// Size penalty exponent (1.0 is linear, higher punishes large files harder)
+    pub size_exponent: f64,
+
+    /// Bonus (or penalty, if negative) by video codec, e.g. "HEVC", "AVC", "AV1"
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub codecs: HashMap<String, f64>,
+
+    /// Bonus for HDR or Dolby Vision sources
+    pub hdr_bonus: f64,
+
+    /// Bonus for Dolby Atmos audio
+    pub atmos_bonus: f64,
+
+    /// Bonus for the first preferred audio language (`[player]`); later choices get
+    /// a half, a third, and so on
+    pub preferred_audio_bonus: f64,
+
+    /// Bonus for the first preferred subtitle language, scaled like the audio bonus
+    pub preferred_subtitle_bonus: f64,
+
+    /// Bonus for the torrent, or the release group and quality, last played for the
+    /// same movie or show (half for the same group in another quality)
+    pub continuity_bonus: f64,
+
+    /// Bonus (or penalty) by provider name, e.g. "nyaasi", "yts"
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub providers: HashMap<String, f64>,
+}
+
+impl Default for ScoringProfile {
+    fn default() -> Self {
+        Self {
+            quality: QualityWeights::default(),
+            cached_bonus: 1000.0,
+            language_bonus: 30.0,
+            seeder_weight: 50.0,
+            size_weight: 80.0,
+            size_exponent: 1.5,
+            codecs: HashMap::new(),
+            hdr_bonus: 0.0,
+            atmos_bonus: 0.0,
+            preferred_audio_bonus: 800.0,
+            preferred_subtitle_bonus: 300.0,
+            continuity_bonus: 500.0,
+            providers: HashMap::new(),
+        }
+    }
+}
+
+/// Points by resolution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualityWeights {
+    /// 2160p / 4K
+    #[serde(rename = "2160p")]
+    pub uhd: f64,
+
+    #[serde(rename = "1080p")]
+    pub full_hd: f64,
+
+    #[serde(rename = "720p")]
+    pub hd: f64,
+
+    /// Anything else (480p, unknown)
+    pub other: f64,
+}
+
+impl Default for QualityWeights {
+    fn default() -> Self {
+        Self {
+            uhd: 600.0,
+            full_hd: 1000.0,
+            hd: 800.0,
+            other: 400.0,
+        }
+    }
+}
+
+/// Release filter configuration
+///
+/// Rules hide or demote sources before they are ranked. Allow rules exempt the
+/// sources they match from the other rules. Press 'h' on the sources screen to add
+/// a rule hiding the selected source's release group.
+///
+/// Example in config.toml:
+/// ```text
+/// [[filters.rules]]
+/// group = "YIFY"
+///
+/// [[filters.rules]]
+/// action = "demote"
+/// codec = "AV1"
+///
+/// [[filters.rules]]
+/// action = "allow"
+/// provider = "nyaasi"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FilterConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<FilterRule>,
+}
+
+/// A release filter rule
+///
+/// A rule matches a source when all of its criteria match; a rule without criteria
+/// matches nothing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilterRule {
+    /// What to do with matching sources (default: "hide")
+    #[serde(default)]
+    pub action: FilterAction,
+
+    /// Release group, e.g. "YIFY" (case-insensitive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+
+    /// Whole word in the release title, e.g. "CAM" (case-insensitive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+
+    /// Regular expression matched against the release title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+
+    /// Torrentio provider, e.g. "yts" (case-insensitive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+
+    /// Video codec, e.g. "AV1" or "x265"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+
+    /// Matches sources smaller than this many GB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_size_gb: Option<f64>,
+
+    /// Matches sources larger than this many GB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_gb: Option<f64>,
+}
+
+/// What a filter rule does with matching sources
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterAction {
+    /// Remove from the sources list
+    #[default]
+    Hide,
+    /// List after all other sources, never recommended
+    Demote,
+    /// Exempt from hide and demote rules
+    Allow,
+}
+
+/// Playback device configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaybackConfig {
+    #[serde(default)]
+    pub capabilities: PlaybackCapabilities,
+
+    /// Play the best source without showing the sources list (default: ask)
+    #[serde(default)]
+    pub auto_select: AutoSelect,
+
+    /// Other recommended sources to try when a source fails to play (default: 4)
+    #[serde(default = "default_fallback_retries")]
+    pub fallback_retries: usize,
+
+    /// Top sources checked in the background on the sources screen (default: 5)
+    #[serde(default = "default_health_checks")]
+    pub health_checks: usize,
+}
+
+impl Default for PlaybackConfig {
+    fn default() -> Self {
+        Self {
+            capabilities: PlaybackCapabilities::default(),
+            auto_select: AutoSelect::default(),
+            fallback_retries: default_fallback_retries(),
+            health_checks: default_health_checks(),
+        }
+    }
+}
+
+fn default_fallback_retries() -> usize {
+    4
+}
+
+fn default_health_checks() -> usize {
+    5
+}
+
+/// Whether to pick a source automatically
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum AutoSelect {
+    /// Play the top recommended source
+    Best,
+    /// Play the top recommended cached source
+    BestCached,
+    /// Always show the sources list
+    #[default]
+    Ask,
+}
+
+/// What the playback device can decode smoothly
+///
+/// Sources beyond these limits are ranked below every playable source, or hidden.
+/// The defaults allow everything.
+///
+/// Example in config.toml:
+/// ```text
+/// [playback.capabilities]
+/// max_resolution = "1080p"
+/// hdr = false
+/// codecs = ["AVC"]
+/// max_bit_depth = 8
+/// max_bitrate_mbps = 15.0
+/// unsupported = "hide"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaybackCapabilities {
+    /// Highest resolution: "2160p", "1080p", "720p" or "480p" (default: any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_resolution: Option<String>,
+
+    /// Whether HDR and Dolby Vision can be shown (default: true)
+    pub hdr: bool,
+
+    /// Decodable video codecs, e.g. ["AVC", "HEVC"] (default: any)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub codecs: Vec<String>,
+
+    /// Highest video bit depth, e.g. 8 (default: any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bit_depth: Option<u8>,
+
+    /// Highest average bitrate in Mbit/s, estimated from the file size (default: any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bitrate_mbps: Option<f64>,
+
+    /// What to do with sources beyond these limits (default: "penalize")
+    pub unsupported: UnsupportedAction,
+}
+
+impl Default for PlaybackCapabilities {
+    fn default() -> Self {
+        Self {
+            max_resolution: None,
+            hdr: true,
+            codecs: Vec::new(),
+            max_bit_depth: None,
+            max_bitrate_mbps: None,
+            unsupported: UnsupportedAction::default(),
+        }
+    }
+}
+
+/// What to do with sources the playback device can't handle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnsupportedAction {
+    /// Rank below all playable sources
+    #[default]
+    Penalize,
+    /// Remove from the sources list
+    Hide,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,15 +1065,60 @@ mod tests {
         let config = Config::new("test_key".to_string(), "tmdb_key".to_string());
         assert_eq!(config.real_debrid.api_key, "test_key");
         assert_eq!(config.tmdb.api_key, "tmdb_key");
-        assert!(config.has_rd_api_key());
+        assert!(config.has_debrid());
         assert!(!config.use_direct_streaming());
     }
 
     #[test]
     fn test_config_empty_key() {
         let config = Config::new("".to_string(), "".to_string());
-        assert!(!config.has_rd_api_key());
+        assert!(!config.has_debrid());
+        assert!(config.use_direct_streaming());
+    }
+
+    #[test]
+    fn test_debrid_services() {
+        let toml_str = r#"
+[real_debrid]
+api_key = "rd_key"
+
+[debrid]
+torbox_api_key = "tb_key"
+premiumize_api_key = "pm_key"
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.debrid_services(),
+            vec![
+                (DebridKind::RealDebrid, "rd_key".to_string()),
+                (DebridKind::Premiumize, "pm_key".to_string()),
+                (DebridKind::TorBox, "tb_key".to_string()),
+            ]
+        );
+
+        // A priority list picks and orders services, skipping those without a key
+        config.debrid.services = vec![
+            DebridKind::TorBox,
+            DebridKind::AllDebrid,
+            DebridKind::RealDebrid,
+        ];
+        assert_eq!(
+            config.debrid_services(),
+            vec![
+                (DebridKind::TorBox, "tb_key".to_string()),
+                (DebridKind::RealDebrid, "rd_key".to_string()),
+            ]
+        );
+
+        let parsed: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(parsed.debrid.services, config.debrid.services);
+
+        // P2P only if no service has a key
+        let mut config = Config::new(String::new(), String::new());
         assert!(config.use_direct_streaming());
+        config.set_debrid_api_key(DebridKind::DebridLink, "dl_key".to_string());
+        assert!(config.has_debrid());
+        assert_eq!(config.debrid_api_key(DebridKind::DebridLink), "dl_key");
     }
 
     #[test]
@@ -318,10 +1143,27 @@ api_key = "test_key"
 "#;
         let config: Config = toml::from_str(toml_str).unwrap();
         assert_eq!(config.real_debrid.api_key, "test_key");
+        assert_eq!(config.real_debrid.expiry_warning_days, 7);
         assert_eq!(config.player.command, "mpv");
         assert_eq!(config.torrentio.quality, "best");
     }
 
+    #[test]
+    fn test_real_debrid_oauth_roundtrip() {
+        let mut config = Config::new("access".to_string(), "tmdb".to_string());
+        assert!(!toml::to_string(&config).unwrap().contains("oauth"));
+
+        config.real_debrid.oauth = Some(RealDebridOAuth {
+            client_id: "CID".to_string(),
+            client_secret: "secret".to_string(),
+            refresh_token: "refresh".to_string(),
+            expires_at: 1_800_000_000,
+        });
+        let parsed: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(parsed.real_debrid.api_key, "access");
+        assert_eq!(parsed.real_debrid.oauth, config.real_debrid.oauth);
+    }
+
     #[test]
     fn test_config_deserialization_full() {
         let toml_str = r#"
@@ -336,6 +1178,7 @@ sort = "seeders"
 [player]
 command = "vlc"
 args = ["--fullscreen", "--loop"]
+preferred_audio_languages = ["Japanese", "en"]
 
 [ui]
 theme = "dark"
@@ -345,6 +1188,11 @@ theme = "dark"
         assert_eq!(config.torrentio.providers, vec!["nyaasi"]);
         assert_eq!(config.torrentio.quality, "1080p");
         assert_eq!(config.player.command, "vlc");
+        assert_eq!(
+            config.player.preferred_audio_languages,
+            vec!["Japanese", "en"]
+        );
+        assert!(config.player.preferred_subtitle_languages.is_empty());
         assert_eq!(config.ui.theme, "dark");
     }
 
@@ -365,6 +1213,152 @@ text = "#ffffff"
         assert_eq!(config.ui.colors.secondary, None);
     }
 
+    #[test]
+    fn test_config_anime_section() {
+        let toml_str = r#"
+[anime]
+mapping_url = "http://localhost:8080/mapping.json"
+absolute_numbering = true
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.anime.mapping_url,
+            "http://localhost:8080/mapping.json"
+        );
+        assert!(config.anime.mapping_file.is_none());
+        assert!(config.anime.absolute_numbering);
+
+        let config = Config::new("".to_string(), "".to_string());
+        assert!(config.anime.mapping_url.is_empty());
+        assert!(!config.anime.absolute_numbering);
+    }
+
+    #[test]
+    fn test_config_calendar_defaults() {
+        let toml_str = r#"
+[calendar]
+future_days = 30
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.calendar.past_days, 7);
+        assert_eq!(config.calendar.future_days, 30);
+    }
+
+    #[test]
+    fn test_config_http_section() {
+        let toml_str = r#"
+[http]
+read_timeout_secs = 60
+proxy = "socks5://127.0.0.1:9050"
+
+[http.rate_limits]
+"torrentio.strem.fun" = 2.0
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.http.connect_timeout_secs, 10);
+        assert_eq!(config.http.read_timeout_secs, 60);
+        assert_eq!(config.http.max_retries, 3);
+        assert_eq!(
+            config.http.proxy,
+            Some("socks5://127.0.0.1:9050".to_string())
+        );
+        assert_eq!(
+            config.http.rate_limits.get("torrentio.strem.fun"),
+            Some(&2.0)
+        );
+        assert!(config.http.user_agent.starts_with("miru/"));
+    }
+
+    #[test]
+    fn test_config_scoring_section() {
+        let toml_str = r#"
+[scoring]
+movie_profile = "home-theater"
+
+[scoring.profiles.living-room]
+hdr_bonus = 300.0
+
+[scoring.profiles.living-room.quality]
+"2160p" = 1400.0
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.scoring.movie_profile, "home-theater");
+        assert_eq!(config.scoring.tv_profile, "default");
+        assert!(config.scoring.anime_profile.is_none());
+
+        // Unset weights keep the default profile's values
+        let profile = &config.scoring.profiles["living-room"];
+        assert_eq!(profile.hdr_bonus, 300.0);
+        assert_eq!(profile.quality.uhd, 1400.0);
+        assert_eq!(profile.quality.full_hd, 1000.0);
+        assert_eq!(profile.cached_bonus, 1000.0);
+    }
+
+    #[test]
+    fn test_config_filter_rules() {
+        let toml_str = r#"
+[[filters.rules]]
+group = "YIFY"
+
+[[filters.rules]]
+action = "demote"
+codec = "AV1"
+max_size_gb = 40.0
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let rules = &config.filters.rules;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].action, FilterAction::Hide);
+        assert_eq!(rules[0].group.as_deref(), Some("YIFY"));
+        assert_eq!(rules[1].action, FilterAction::Demote);
+        assert_eq!(rules[1].max_size_gb, Some(40.0));
+
+        // Round-trips without the unset criteria
+        let saved = toml::to_string(&config).unwrap();
+        assert!(saved.contains("group = \"YIFY\""));
+        assert!(!saved.contains("keyword"));
+    }
+
+    #[test]
+    fn test_config_playback_capabilities() {
+        let toml_str = r#"
+[playback.capabilities]
+max_resolution = "1080p"
+hdr = false
+codecs = ["AVC"]
+unsupported = "hide"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let capabilities = &config.playback.capabilities;
+        assert_eq!(capabilities.max_resolution.as_deref(), Some("1080p"));
+        assert!(!capabilities.hdr);
+        assert_eq!(capabilities.codecs, vec!["AVC"]);
+        assert!(capabilities.max_bit_depth.is_none());
+        assert_eq!(capabilities.unsupported, UnsupportedAction::Hide);
+
+        let config = Config::new("".to_string(), "".to_string());
+        assert!(config.playback.capabilities.hdr);
+        assert_eq!(
+            config.playback.capabilities.unsupported,
+            UnsupportedAction::Penalize
+        );
+    }
+
+    #[test]
+    fn test_config_auto_select() {
+        let toml_str = r#"
+[playback]
+auto_select = "best_cached"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.playback.auto_select, AutoSelect::BestCached);
+
+        assert_eq!(config.playback.fallback_retries, 4);
+
+        let config = Config::new("".to_string(), "".to_string());
+        assert_eq!(config.playback.auto_select, AutoSelect::Ask);
+    }
+
     #[test]
     fn test_config_ui_auto_theme() {
         let config = UiConfig::default();
@@ -15,9 +15,24 @@ pub enum ApiError {
     #[error("Real-Debrid authentication failed. Please check your API key.")]
     RealDebridAuth,
 
+    #[error("{0} API error: {1}")]
+    Debrid(&'static str, String),
+
+    #[error("{0} authentication failed. Please check your API key.")]
+    DebridAuth(&'static str),
+
     #[error("Torrentio error: {0}")]
     Torrentio(String),
 
+    #[error("No movie or TV show found for \"{0}\"")]
+    NotFound(String),
+
+    #[error("Metadata error: {0}")]
+    Metadata(String),
+
+    #[error("Anime mapping error: {0}")]
+    AnimeMapping(String),
+
     #[error("Could not find IMDB ID for this title.\n\nThis title may not have an IMDB entry.\nTry searching with an alternative title.")]
     MappingNotFound,
 
@@ -38,6 +53,9 @@ pub enum ConfigError {
     #[allow(dead_code)]
     MissingApiKey,
 
+    #[error("TMDB API key is required. Run 'miru config --set tmdb_api_key <KEY>' to set it.")]
+    MissingTmdbApiKey,
+
     #[error("Failed to save config: {0}")]
     SaveFailed(String),
 
@@ -54,6 +72,9 @@ pub enum PlayerError {
     #[error("Player exited with error: {0}")]
     ExitError(String),
 
+    #[error("Player couldn't play the source: {0}")]
+    PlaybackFailed(String),
+
     #[error("Failed to launch player: {0}")]
     LaunchFailed(String),
 }
@@ -8,7 +8,10 @@ use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqliteResult};
 use serde::{Deserialize, Serialize};
 
-use crate::api::MediaType;
+use crate::api::{MediaSource, MediaType, StreamChoice};
+
+/// Days a failed source stays demoted
+const FAILED_SOURCE_DAYS: u32 = 30;
 
 /// Get the database file path (~/.config/miru/history.db)
 pub fn db_path() -> PathBuf {
@@ -23,8 +26,8 @@ pub fn db_path() -> PathBuf {
 pub struct WatchlistItem {
     /// Unique ID in database
     pub id: i64,
-    /// TMDB ID of the media
-    pub tmdb_id: i32,
+    /// Provider-qualified ID of the media
+    pub source: MediaSource,
     /// Type of media (Movie or TvShow)
     pub media_type: MediaType,
     /// Title of the media
@@ -55,18 +58,27 @@ impl WatchlistItem {
     }
 }
 
+/// A TV show followed through the watchlist or watch history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowedShow {
+    /// TMDB ID of the show
+    pub tmdb_id: i32,
+    /// Title of the show
+    pub title: String,
+}
+
 /// A watched item record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WatchedItem {
     /// Unique ID in database
     pub id: i64,
-    /// TMDB ID of the media
-    pub tmdb_id: i32,
+    /// Provider-qualified ID of the media
+    pub source: MediaSource,
     /// Type of media (Movie or TvShow)
     pub media_type: MediaType,
     /// Title of the media
     pub title: String,
-    /// Season number (0 for movies)
+    /// Season number (0 for movies, and for specials of TV shows)
     pub season: u32,
     /// Episode number (0 for movies)
     pub episode: u32,
@@ -132,10 +144,12 @@ impl WatchHistory {
 
     /// Initialize database schema
     fn init_schema(&self) -> SqliteResult<()> {
+        self.migrate_tmdb_ids()?;
+
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS watched (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
-                tmdb_id INTEGER NOT NULL,
+                media_key TEXT NOT NULL,
                 media_type TEXT NOT NULL,
                 title TEXT NOT NULL,
                 season INTEGER NOT NULL DEFAULT 0,
@@ -143,10 +157,15 @@ impl WatchHistory {
                 episode_title TEXT,
                 cover_image TEXT,
                 watched_at TEXT NOT NULL DEFAULT (datetime('now')),
-                UNIQUE(tmdb_id, media_type, season, episode)
+                stream_provider TEXT,
+                stream_group TEXT,
+                stream_quality TEXT,
+                stream_info_hash TEXT,
+                UNIQUE(media_key, media_type, season, episode)
             )",
             [],
         )?;
+        self.migrate_stream_columns()?;
 
         // Index for fast recent queries
         self.conn.execute(
@@ -158,12 +177,12 @@ impl WatchHistory {
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS watchlist (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
-                tmdb_id INTEGER NOT NULL,
+                media_key TEXT NOT NULL,
                 media_type TEXT NOT NULL,
                 title TEXT NOT NULL,
                 cover_image TEXT,
                 added_at TEXT NOT NULL DEFAULT (datetime('now')),
-                UNIQUE(tmdb_id, media_type)
+                UNIQUE(media_key, media_type)
             )",
             [],
         )?;
@@ -173,6 +192,93 @@ impl WatchHistory {
             [],
         )?;
 
+        // Sources that failed to play (keyed by `Stream::source_key`)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS failed_sources (
+                source_key TEXT PRIMARY KEY,
+                failures INTEGER NOT NULL DEFAULT 1,
+                failed_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Migrate tables keyed by TMDB ID (before other metadata providers) to media keys
+    fn migrate_tmdb_ids(&self) -> SqliteResult<()> {
+        let has_tmdb_id = |table: &str| {
+            self.conn
+                .prepare(&format!("SELECT tmdb_id FROM {} LIMIT 0", table))
+                .is_ok()
+        };
+
+        if has_tmdb_id("watched") {
+            self.conn.execute_batch(
+                "BEGIN;
+                 ALTER TABLE watched RENAME TO watched_old;
+                 CREATE TABLE watched (
+                     id INTEGER PRIMARY KEY AUTOINCREMENT,
+                     media_key TEXT NOT NULL,
+                     media_type TEXT NOT NULL,
+                     title TEXT NOT NULL,
+                     season INTEGER NOT NULL DEFAULT 0,
+                     episode INTEGER NOT NULL DEFAULT 0,
+                     episode_title TEXT,
+                     cover_image TEXT,
+                     watched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                     UNIQUE(media_key, media_type, season, episode)
+                 );
+                 INSERT INTO watched (id, media_key, media_type, title, season, episode, episode_title, cover_image, watched_at)
+                     SELECT id, 'tmdb:' || tmdb_id, media_type, title, season, episode, episode_title, cover_image, watched_at
+                     FROM watched_old;
+                 DROP TABLE watched_old;
+                 COMMIT;",
+            )?;
+        }
+
+        if has_tmdb_id("watchlist") {
+            self.conn.execute_batch(
+                "BEGIN;
+                 ALTER TABLE watchlist RENAME TO watchlist_old;
+                 CREATE TABLE watchlist (
+                     id INTEGER PRIMARY KEY AUTOINCREMENT,
+                     media_key TEXT NOT NULL,
+                     media_type TEXT NOT NULL,
+                     title TEXT NOT NULL,
+                     cover_image TEXT,
+                     added_at TEXT NOT NULL DEFAULT (datetime('now')),
+                     UNIQUE(media_key, media_type)
+                 );
+                 INSERT INTO watchlist (id, media_key, media_type, title, cover_image, added_at)
+                     SELECT id, 'tmdb:' || tmdb_id, media_type, title, cover_image, added_at
+                     FROM watchlist_old;
+                 DROP TABLE watchlist_old;
+                 COMMIT;",
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Add the stream choice columns to watched tables created before they existed
+    fn migrate_stream_columns(&self) -> SqliteResult<()> {
+        let has_stream_columns = self
+            .conn
+            .prepare("SELECT stream_provider FROM watched LIMIT 0")
+            .is_ok();
+
+        if !has_stream_columns {
+            self.conn.execute_batch(
+                "BEGIN;
+                 ALTER TABLE watched ADD COLUMN stream_provider TEXT;
+                 ALTER TABLE watched ADD COLUMN stream_group TEXT;
+                 ALTER TABLE watched ADD COLUMN stream_quality TEXT;
+                 ALTER TABLE watched ADD COLUMN stream_info_hash TEXT;
+                 COMMIT;",
+            )?;
+        }
+
         Ok(())
     }
 
@@ -180,7 +286,7 @@ impl WatchHistory {
     #[allow(clippy::too_many_arguments)]
     pub fn mark_watched(
         &self,
-        tmdb_id: i32,
+        source: &MediaSource,
         media_type: MediaType,
         title: &str,
         season: u32,
@@ -194,23 +300,120 @@ impl WatchHistory {
         };
 
         self.conn.execute(
-            "INSERT INTO watched (tmdb_id, media_type, title, season, episode, episode_title, cover_image, watched_at)
+            "INSERT INTO watched (media_key, media_type, title, season, episode, episode_title, cover_image, watched_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'))
-             ON CONFLICT(tmdb_id, media_type, season, episode) DO UPDATE SET
+             ON CONFLICT(media_key, media_type, season, episode) DO UPDATE SET
                  title = excluded.title,
                  episode_title = excluded.episode_title,
                  cover_image = excluded.cover_image,
                  watched_at = datetime('now')",
-            params![tmdb_id, media_type_str, title, season, episode, episode_title, cover_image],
+            params![source.key(), media_type_str, title, season, episode, episode_title, cover_image],
         )?;
 
         Ok(())
     }
 
+    /// Record the stream a watched item was played from
+    pub fn set_stream_choice(
+        &self,
+        source: &MediaSource,
+        media_type: MediaType,
+        season: u32,
+        episode: u32,
+        choice: &StreamChoice,
+    ) -> SqliteResult<()> {
+        let media_type_str = match media_type {
+            MediaType::Movie => "movie",
+            MediaType::TvShow => "tvshow",
+        };
+
+        self.conn.execute(
+            "UPDATE watched SET stream_provider = ?5, stream_group = ?6, stream_quality = ?7, stream_info_hash = ?8
+             WHERE media_key = ?1 AND media_type = ?2 AND season = ?3 AND episode = ?4",
+            params![
+                source.key(),
+                media_type_str,
+                season,
+                episode,
+                choice.provider,
+                choice.release_group,
+                choice.quality,
+                choice.info_hash
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get the stream most recently played for a movie or show (any episode)
+    pub fn last_stream_choice(
+        &self,
+        source: &MediaSource,
+        media_type: MediaType,
+    ) -> Option<StreamChoice> {
+        let media_type_str = match media_type {
+            MediaType::Movie => "movie",
+            MediaType::TvShow => "tvshow",
+        };
+
+        self.conn
+            .query_row(
+                "SELECT stream_provider, stream_group, stream_quality, stream_info_hash
+                 FROM watched
+                 WHERE media_key = ?1 AND media_type = ?2 AND stream_provider IS NOT NULL
+                 ORDER BY watched_at DESC, id DESC
+                 LIMIT 1",
+                params![source.key(), media_type_str],
+                |row| {
+                    Ok(StreamChoice {
+                        provider: row.get(0)?,
+                        release_group: row.get(1)?,
+                        quality: row.get(2)?,
+                        info_hash: row.get(3)?,
+                    })
+                },
+            )
+            .ok()
+    }
+
+    /// Record that a source failed to play
+    pub fn record_source_failure(&self, source_key: &str) -> SqliteResult<()> {
+        self.conn.execute(
+            "INSERT INTO failed_sources (source_key, failures, failed_at)
+             VALUES (?1, 1, datetime('now'))
+             ON CONFLICT(source_key) DO UPDATE SET
+                 failures = failures + 1,
+                 failed_at = excluded.failed_at",
+            params![source_key],
+        )?;
+
+        Ok(())
+    }
+
+    /// Keys of sources that failed to play recently (failures expire after a while,
+    /// as dead links and swarms can recover)
+    pub fn failed_sources(&self) -> std::collections::HashSet<String> {
+        let mut stmt = match self
+            .conn
+            .prepare("SELECT source_key FROM failed_sources WHERE failed_at >= datetime('now', ?1)")
+        {
+            Ok(stmt) => stmt,
+            Err(_) => return std::collections::HashSet::new(),
+        };
+
+        let window = format!("-{} days", FAILED_SOURCE_DAYS);
+        let rows = match stmt.query_map(params![window], |row| row.get(0)) {
+            Ok(rows) => rows,
+            Err(_) => return std::collections::HashSet::new(),
+        };
+
+        rows.filter_map(|r| r.ok()).collect()
+    }
+
     /// Remove a watched item
     pub fn mark_unwatched(
         &self,
-        tmdb_id: i32,
+        source: &MediaSource,
         media_type: MediaType,
         season: u32,
         episode: u32,
@@ -221,8 +424,8 @@ impl WatchHistory {
         };
 
         self.conn.execute(
-            "DELETE FROM watched WHERE tmdb_id = ?1 AND media_type = ?2 AND season = ?3 AND episode = ?4",
-            params![tmdb_id, media_type_str, season, episode],
+            "DELETE FROM watched WHERE media_key = ?1 AND media_type = ?2 AND season = ?3 AND episode = ?4",
+            params![source.key(), media_type_str, season, episode],
         )?;
 
         Ok(())
@@ -232,7 +435,7 @@ impl WatchHistory {
     #[allow(dead_code)]
     pub fn is_watched(
         &self,
-        tmdb_id: i32,
+        source: &MediaSource,
         media_type: MediaType,
         season: u32,
         episode: u32,
@@ -244,19 +447,19 @@ impl WatchHistory {
 
         self.conn
             .query_row(
-                "SELECT 1 FROM watched WHERE tmdb_id = ?1 AND media_type = ?2 AND season = ?3 AND episode = ?4",
-                params![tmdb_id, media_type_str, season, episode],
+                "SELECT 1 FROM watched WHERE media_key = ?1 AND media_type = ?2 AND season = ?3 AND episode = ?4",
+                params![source.key(), media_type_str, season, episode],
                 |_| Ok(()),
             )
             .is_ok()
     }
 
     /// Get watched episode count for a season
-    pub fn watched_episode_count(&self, tmdb_id: i32, season: u32) -> u32 {
+    pub fn watched_episode_count(&self, source: &MediaSource, season: u32) -> u32 {
         self.conn
             .query_row(
-                "SELECT COUNT(*) FROM watched WHERE tmdb_id = ?1 AND media_type = 'tvshow' AND season = ?2",
-                params![tmdb_id, season],
+                "SELECT COUNT(*) FROM watched WHERE media_key = ?1 AND media_type = 'tvshow' AND season = ?2",
+                params![source.key(), season],
                 |row| row.get::<_, u32>(0),
             )
             .unwrap_or(0)
@@ -266,7 +469,7 @@ impl WatchHistory {
     #[allow(dead_code)]
     pub fn get_recent(&self, limit: usize) -> Vec<WatchedItem> {
         let mut stmt = match self.conn.prepare(
-            "SELECT id, tmdb_id, media_type, title, season, episode, episode_title, cover_image, watched_at
+            "SELECT id, media_key, media_type, title, season, episode, episode_title, cover_image, watched_at
              FROM watched
              ORDER BY watched_at DESC
              LIMIT ?1",
@@ -294,7 +497,7 @@ impl WatchHistory {
 
             Ok(WatchedItem {
                 id: row.get(0)?,
-                tmdb_id: row.get(1)?,
+                source: media_source(row, 1)?,
                 media_type,
                 title: row.get(3)?,
                 season: row.get(4)?,
@@ -314,18 +517,18 @@ impl WatchHistory {
     /// Get watched episode numbers for a specific season
     pub fn get_watched_episodes(
         &self,
-        tmdb_id: i32,
+        source: &MediaSource,
         season: u32,
     ) -> std::collections::HashSet<u32> {
         let mut stmt = match self.conn.prepare(
             "SELECT episode FROM watched 
-             WHERE tmdb_id = ?1 AND media_type = 'tvshow' AND season = ?2",
+             WHERE media_key = ?1 AND media_type = 'tvshow' AND season = ?2",
         ) {
             Ok(stmt) => stmt,
             Err(_) => return std::collections::HashSet::new(),
         };
 
-        let rows = match stmt.query_map(params![tmdb_id, season], |row| row.get::<_, u32>(0)) {
+        let rows = match stmt.query_map(params![source.key(), season], |row| row.get::<_, u32>(0)) {
             Ok(rows) => rows,
             Err(_) => return std::collections::HashSet::new(),
         };
@@ -336,7 +539,7 @@ impl WatchHistory {
     /// Add a media item to the watchlist
     pub fn add_to_watchlist(
         &self,
-        tmdb_id: i32,
+        source: &MediaSource,
         media_type: MediaType,
         title: &str,
         cover_image: Option<&str>,
@@ -347,27 +550,31 @@ impl WatchHistory {
         };
 
         self.conn.execute(
-            "INSERT INTO watchlist (tmdb_id, media_type, title, cover_image, added_at)
+            "INSERT INTO watchlist (media_key, media_type, title, cover_image, added_at)
              VALUES (?1, ?2, ?3, ?4, datetime('now'))
-             ON CONFLICT(tmdb_id, media_type) DO UPDATE SET
+             ON CONFLICT(media_key, media_type) DO UPDATE SET
                  title = excluded.title,
                  cover_image = excluded.cover_image",
-            params![tmdb_id, media_type_str, title, cover_image],
+            params![source.key(), media_type_str, title, cover_image],
         )?;
 
         Ok(())
     }
 
     /// Remove a media item from the watchlist
-    pub fn remove_from_watchlist(&self, tmdb_id: i32, media_type: MediaType) -> SqliteResult<()> {
+    pub fn remove_from_watchlist(
+        &self,
+        source: &MediaSource,
+        media_type: MediaType,
+    ) -> SqliteResult<()> {
         let media_type_str = match media_type {
             MediaType::Movie => "movie",
             MediaType::TvShow => "tvshow",
         };
 
         self.conn.execute(
-            "DELETE FROM watchlist WHERE tmdb_id = ?1 AND media_type = ?2",
-            params![tmdb_id, media_type_str],
+            "DELETE FROM watchlist WHERE media_key = ?1 AND media_type = ?2",
+            params![source.key(), media_type_str],
         )?;
 
         Ok(())
@@ -375,7 +582,7 @@ impl WatchHistory {
 
     /// Check if a media item is in the watchlist
     #[allow(dead_code)]
-    pub fn is_in_watchlist(&self, tmdb_id: i32, media_type: MediaType) -> bool {
+    pub fn is_in_watchlist(&self, source: &MediaSource, media_type: MediaType) -> bool {
         let media_type_str = match media_type {
             MediaType::Movie => "movie",
             MediaType::TvShow => "tvshow",
@@ -383,8 +590,8 @@ impl WatchHistory {
 
         self.conn
             .query_row(
-                "SELECT 1 FROM watchlist WHERE tmdb_id = ?1 AND media_type = ?2",
-                params![tmdb_id, media_type_str],
+                "SELECT 1 FROM watchlist WHERE media_key = ?1 AND media_type = ?2",
+                params![source.key(), media_type_str],
                 |_| Ok(()),
             )
             .is_ok()
@@ -393,7 +600,7 @@ impl WatchHistory {
     /// Get all watchlist items (most recently added first)
     pub fn get_watchlist(&self, limit: usize) -> Vec<WatchlistItem> {
         let mut stmt = match self.conn.prepare(
-            "SELECT id, tmdb_id, media_type, title, cover_image, added_at
+            "SELECT id, media_key, media_type, title, cover_image, added_at
              FROM watchlist
              ORDER BY added_at DESC
              LIMIT ?1",
@@ -420,7 +627,7 @@ impl WatchHistory {
 
             Ok(WatchlistItem {
                 id: row.get(0)?,
-                tmdb_id: row.get(1)?,
+                source: media_source(row, 1)?,
                 media_type,
                 title: row.get(3)?,
                 cover_image: row.get(4)?,
@@ -434,24 +641,24 @@ impl WatchHistory {
         rows.filter_map(|r| r.ok()).collect()
     }
 
-    /// Get the set of (tmdb_id, media_type) pairs in the watchlist
-    pub fn get_watchlist_ids(&self) -> std::collections::HashSet<(i32, MediaType)> {
+    /// Get the set of (source, media_type) pairs in the watchlist
+    pub fn get_watchlist_ids(&self) -> std::collections::HashSet<(MediaSource, MediaType)> {
         let mut stmt = match self
             .conn
-            .prepare("SELECT tmdb_id, media_type FROM watchlist")
+            .prepare("SELECT media_key, media_type FROM watchlist")
         {
             Ok(stmt) => stmt,
             Err(_) => return std::collections::HashSet::new(),
         };
 
         let rows = match stmt.query_map([], |row| {
-            let tmdb_id: i32 = row.get(0)?;
+            let source = media_source(row, 0)?;
             let media_type_str: String = row.get(1)?;
             let media_type = match media_type_str.as_str() {
                 "movie" => MediaType::Movie,
                 _ => MediaType::TvShow,
             };
-            Ok((tmdb_id, media_type))
+            Ok((source, media_type))
         }) {
             Ok(rows) => rows,
             Err(_) => return std::collections::HashSet::new(),
@@ -460,15 +667,46 @@ impl WatchHistory {
         rows.filter_map(|r| r.ok()).collect()
     }
 
+    /// Get TMDB TV shows in the watchlist or watch history, most recently active first
+    pub fn get_followed_shows(&self, limit: usize) -> Vec<FollowedShow> {
+        let mut stmt = match self.conn.prepare(
+            "SELECT media_key, MAX(title), MAX(activity_at) AS last_activity
+             FROM (
+                 SELECT media_key, title, added_at AS activity_at FROM watchlist WHERE media_type = 'tvshow'
+                 UNION ALL
+                 SELECT media_key, title, watched_at AS activity_at FROM watched WHERE media_type = 'tvshow'
+             )
+             WHERE media_key LIKE 'tmdb:%'
+             GROUP BY media_key
+             ORDER BY last_activity DESC
+             LIMIT ?1",
+        ) {
+            Ok(stmt) => stmt,
+            Err(_) => return Vec::new(),
+        };
+
+        let rows = match stmt.query_map(params![limit as i64], |row| {
+            Ok(FollowedShow {
+                tmdb_id: media_source(row, 0)?.tmdb_id().unwrap_or_default(),
+                title: row.get(1)?,
+            })
+        }) {
+            Ok(rows) => rows,
+            Err(_) => return Vec::new(),
+        };
+
+        rows.filter_map(|r| r.ok()).collect()
+    }
+
     /// Get unique shows/movies from history (for "continue watching" feature)
     /// Returns the most recent watch entry for each unique media item
     pub fn get_recent_media(&self, limit: usize) -> Vec<WatchedItem> {
         let mut stmt = match self.conn.prepare(
-            "SELECT id, tmdb_id, media_type, title, season, episode, episode_title, cover_image, watched_at
+            "SELECT id, media_key, media_type, title, season, episode, episode_title, cover_image, watched_at
              FROM watched w1
              WHERE watched_at = (
                  SELECT MAX(watched_at) FROM watched w2 
-                 WHERE w2.tmdb_id = w1.tmdb_id AND w2.media_type = w1.media_type
+                 WHERE w2.media_key = w1.media_key AND w2.media_type = w1.media_type
              )
              ORDER BY watched_at DESC
              LIMIT ?1",
@@ -495,7 +733,7 @@ impl WatchHistory {
 
             Ok(WatchedItem {
                 id: row.get(0)?,
-                tmdb_id: row.get(1)?,
+                source: media_source(row, 1)?,
                 media_type,
                 title: row.get(3)?,
                 season: row.get(4)?,
@@ -513,6 +751,18 @@ impl WatchHistory {
     }
 }
 
+/// Read a media key column as a media source
+fn media_source(row: &rusqlite::Row, index: usize) -> SqliteResult<MediaSource> {
+    let key: String = row.get(index)?;
+    MediaSource::from_key(&key).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            index,
+            rusqlite::types::Type::Text,
+            format!("Unknown media key: {}", key).into(),
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -525,13 +775,17 @@ mod tests {
         history
     }
 
+    fn tmdb(id: i32) -> MediaSource {
+        MediaSource::Tmdb { id }
+    }
+
     #[test]
     fn test_mark_watched() {
         let history = create_test_db();
 
         history
             .mark_watched(
-                12345,
+                &tmdb(12345),
                 MediaType::TvShow,
                 "Test Show",
                 1,
@@ -541,8 +795,116 @@ mod tests {
             )
             .unwrap();
 
-        assert!(history.is_watched(12345, MediaType::TvShow, 1, 5));
-        assert!(!history.is_watched(12345, MediaType::TvShow, 1, 6));
+        assert!(history.is_watched(&tmdb(12345), MediaType::TvShow, 1, 5));
+        assert!(!history.is_watched(&tmdb(12345), MediaType::TvShow, 1, 6));
+    }
+
+    #[test]
+    fn test_stream_choice() {
+        let history = create_test_db();
+        assert!(history
+            .last_stream_choice(&tmdb(12345), MediaType::TvShow)
+            .is_none());
+
+        let choice = StreamChoice {
+            provider: "eztv".to_string(),
+            release_group: Some("NTb".to_string()),
+            quality: Some("1080p".to_string()),
+            info_hash: Some("abc123".to_string()),
+        };
+        history
+            .mark_watched(
+                &tmdb(12345),
+                MediaType::TvShow,
+                "Test Show",
+                1,
+                1,
+                None,
+                None,
+            )
+            .unwrap();
+        history
+            .set_stream_choice(&tmdb(12345), MediaType::TvShow, 1, 1, &choice)
+            .unwrap();
+
+        // Marking another episode watched by hand keeps the last choice
+        history
+            .mark_watched(
+                &tmdb(12345),
+                MediaType::TvShow,
+                "Test Show",
+                1,
+                2,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            history.last_stream_choice(&tmdb(12345), MediaType::TvShow),
+            Some(choice)
+        );
+        assert!(history
+            .last_stream_choice(&tmdb(999), MediaType::TvShow)
+            .is_none());
+        // A movie with the same TMDB id is a different title
+        assert!(history
+            .last_stream_choice(&tmdb(12345), MediaType::Movie)
+            .is_none());
+    }
+
+    #[test]
+    fn test_failed_sources() {
+        let history = create_test_db();
+        assert!(history.failed_sources().is_empty());
+
+        history.record_source_failure("abc123").unwrap();
+        history.record_source_failure("abc123").unwrap();
+        history
+            .record_source_failure("https://example.com/file.mkv")
+            .unwrap();
+        let failed = history.failed_sources();
+        assert_eq!(failed.len(), 2);
+        assert!(failed.contains("abc123"));
+
+        // Old failures expire
+        history
+            .conn
+            .execute(
+                "UPDATE failed_sources SET failed_at = datetime('now', '-60 days')
+                 WHERE source_key = 'abc123'",
+                [],
+            )
+            .unwrap();
+        assert!(!history.failed_sources().contains("abc123"));
+    }
+
+    #[test]
+    fn test_migrate_stream_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE watched (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 media_key TEXT NOT NULL,
+                 media_type TEXT NOT NULL,
+                 title TEXT NOT NULL,
+                 season INTEGER NOT NULL DEFAULT 0,
+                 episode INTEGER NOT NULL DEFAULT 0,
+                 episode_title TEXT,
+                 cover_image TEXT,
+                 watched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                 UNIQUE(media_key, media_type, season, episode)
+             );
+             INSERT INTO watched (media_key, media_type, title, season, episode)
+                 VALUES ('tmdb:1', 'tvshow', 'Old Show', 1, 1);",
+        )
+        .unwrap();
+        let history = WatchHistory { conn };
+        history.init_schema().unwrap();
+
+        assert!(history.is_watched(&tmdb(1), MediaType::TvShow, 1, 1));
+        assert!(history
+            .last_stream_choice(&tmdb(1), MediaType::TvShow)
+            .is_none());
     }
 
     #[test]
@@ -550,14 +912,22 @@ mod tests {
         let history = create_test_db();
 
         history
-            .mark_watched(12345, MediaType::TvShow, "Test Show", 1, 5, None, None)
+            .mark_watched(
+                &tmdb(12345),
+                MediaType::TvShow,
+                "Test Show",
+                1,
+                5,
+                None,
+                None,
+            )
             .unwrap();
-        assert!(history.is_watched(12345, MediaType::TvShow, 1, 5));
+        assert!(history.is_watched(&tmdb(12345), MediaType::TvShow, 1, 5));
 
         history
-            .mark_unwatched(12345, MediaType::TvShow, 1, 5)
+            .mark_unwatched(&tmdb(12345), MediaType::TvShow, 1, 5)
             .unwrap();
-        assert!(!history.is_watched(12345, MediaType::TvShow, 1, 5));
+        assert!(!history.is_watched(&tmdb(12345), MediaType::TvShow, 1, 5));
     }
 
     #[test]
@@ -565,20 +935,20 @@ mod tests {
         let history = create_test_db();
 
         history
-            .add_to_watchlist(12345, MediaType::TvShow, "Test Show", None)
+            .add_to_watchlist(&tmdb(12345), MediaType::TvShow, "Test Show", None)
             .unwrap();
 
-        assert!(history.is_in_watchlist(12345, MediaType::TvShow));
-        assert!(!history.is_in_watchlist(12345, MediaType::Movie));
+        assert!(history.is_in_watchlist(&tmdb(12345), MediaType::TvShow));
+        assert!(!history.is_in_watchlist(&tmdb(12345), MediaType::Movie));
 
         let items = history.get_watchlist(10);
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].title, "Test Show");
 
         history
-            .remove_from_watchlist(12345, MediaType::TvShow)
+            .remove_from_watchlist(&tmdb(12345), MediaType::TvShow)
             .unwrap();
-        assert!(!history.is_in_watchlist(12345, MediaType::TvShow));
+        assert!(!history.is_in_watchlist(&tmdb(12345), MediaType::TvShow));
 
         let items = history.get_watchlist(10);
         assert!(items.is_empty());
@@ -589,16 +959,16 @@ mod tests {
         let history = create_test_db();
 
         history
-            .add_to_watchlist(111, MediaType::Movie, "Movie 1", None)
+            .add_to_watchlist(&tmdb(111), MediaType::Movie, "Movie 1", None)
             .unwrap();
         history
-            .add_to_watchlist(222, MediaType::TvShow, "Show 1", None)
+            .add_to_watchlist(&tmdb(222), MediaType::TvShow, "Show 1", None)
             .unwrap();
 
         let ids = history.get_watchlist_ids();
         assert_eq!(ids.len(), 2);
-        assert!(ids.contains(&(111, MediaType::Movie)));
-        assert!(ids.contains(&(222, MediaType::TvShow)));
+        assert!(ids.contains(&(tmdb(111), MediaType::Movie)));
+        assert!(ids.contains(&(tmdb(222), MediaType::TvShow)));
     }
 
     #[test]
@@ -606,16 +976,200 @@ mod tests {
         let history = create_test_db();
 
         history
-            .mark_watched(12345, MediaType::TvShow, "Test Show", 1, 1, None, None)
+            .mark_watched(
+                &tmdb(12345),
+                MediaType::TvShow,
+                "Test Show",
+                1,
+                1,
+                None,
+                None,
+            )
+            .unwrap();
+        history
+            .mark_watched(
+                &tmdb(12345),
+                MediaType::TvShow,
+                "Test Show",
+                1,
+                2,
+                None,
+                None,
+            )
+            .unwrap();
+        history
+            .mark_watched(
+                &tmdb(12345),
+                MediaType::TvShow,
+                "Test Show",
+                1,
+                3,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(history.watched_episode_count(&tmdb(12345), 1), 3);
+        assert_eq!(history.watched_episode_count(&tmdb(12345), 2), 0);
+    }
+
+    #[test]
+    fn test_get_followed_shows() {
+        let history = create_test_db();
+
+        history
+            .mark_watched(
+                &tmdb(1),
+                MediaType::TvShow,
+                "Watched Show",
+                1,
+                1,
+                None,
+                None,
+            )
+            .unwrap();
+        history
+            .mark_watched(
+                &tmdb(1),
+                MediaType::TvShow,
+                "Watched Show",
+                1,
+                2,
+                None,
+                None,
+            )
+            .unwrap();
+        history
+            .mark_watched(&tmdb(2), MediaType::Movie, "A Movie", 0, 0, None, None)
+            .unwrap();
+        history
+            .add_to_watchlist(&tmdb(3), MediaType::TvShow, "Listed Show", None)
+            .unwrap();
+        history
+            .add_to_watchlist(&tmdb(1), MediaType::TvShow, "Watched Show", None)
+            .unwrap();
+        history
+            .add_to_watchlist(&tmdb(4), MediaType::Movie, "Listed Movie", None)
+            .unwrap();
+
+        let mut ids: Vec<i32> = history
+            .get_followed_shows(10)
+            .into_iter()
+            .map(|s| s.tmdb_id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(history.get_followed_shows(1).len(), 1);
+    }
+
+    #[test]
+    fn test_specials_not_confused_with_movies() {
+        let history = create_test_db();
+
+        // TMDB movie and TV ids live in separate namespaces, so the same id can refer to both
+        history
+            .mark_watched(
+                &tmdb(12345),
+                MediaType::Movie,
+                "Test Movie",
+                0,
+                0,
+                None,
+                None,
+            )
             .unwrap();
         history
-            .mark_watched(12345, MediaType::TvShow, "Test Show", 1, 2, None, None)
+            .mark_watched(
+                &tmdb(12345),
+                MediaType::TvShow,
+                "Test Show",
+                0,
+                1,
+                Some("OVA"),
+                None,
+            )
             .unwrap();
+
+        assert!(history.is_watched(&tmdb(12345), MediaType::Movie, 0, 0));
+        assert!(history.is_watched(&tmdb(12345), MediaType::TvShow, 0, 1));
+        assert!(!history.is_watched(&tmdb(12345), MediaType::TvShow, 0, 0));
+        assert_eq!(history.watched_episode_count(&tmdb(12345), 0), 1);
+        assert_eq!(
+            history.get_watched_episodes(&tmdb(12345), 0),
+            std::collections::HashSet::from([1])
+        );
+
+        let recent = history.get_recent_media(10);
+        assert_eq!(recent.len(), 2);
+        let special = recent
+            .iter()
+            .find(|item| item.media_type == MediaType::TvShow)
+            .unwrap();
+        assert_eq!(special.episode_display(), "S00E01");
+    }
+
+    #[test]
+    fn test_local_media_separate_from_tmdb() {
+        let history = create_test_db();
+        let local = MediaSource::Local {
+            id: "home-videos".to_string(),
+        };
+
         history
-            .mark_watched(12345, MediaType::TvShow, "Test Show", 1, 3, None, None)
+            .mark_watched(&local, MediaType::TvShow, "Home Videos", 1, 1, None, None)
             .unwrap();
+        history
+            .add_to_watchlist(&local, MediaType::TvShow, "Home Videos", None)
+            .unwrap();
+
+        assert!(history.is_watched(&local, MediaType::TvShow, 1, 1));
+        assert_eq!(history.get_recent_media(10)[0].source, local);
+        assert!(history
+            .get_watchlist_ids()
+            .contains(&(local, MediaType::TvShow)));
+        // Only TMDB shows have a calendar
+        assert!(history.get_followed_shows(10).is_empty());
+    }
+
+    #[test]
+    fn test_migrate_tmdb_ids() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE watched (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 tmdb_id INTEGER NOT NULL,
+                 media_type TEXT NOT NULL,
+                 title TEXT NOT NULL,
+                 season INTEGER NOT NULL DEFAULT 0,
+                 episode INTEGER NOT NULL DEFAULT 0,
+                 episode_title TEXT,
+                 cover_image TEXT,
+                 watched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                 UNIQUE(tmdb_id, media_type, season, episode)
+             );
+             CREATE INDEX idx_watched_at ON watched(watched_at DESC);
+             CREATE TABLE watchlist (
+                 id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 tmdb_id INTEGER NOT NULL,
+                 media_type TEXT NOT NULL,
+                 title TEXT NOT NULL,
+                 cover_image TEXT,
+                 added_at TEXT NOT NULL DEFAULT (datetime('now')),
+                 UNIQUE(tmdb_id, media_type)
+             );
+             INSERT INTO watched (tmdb_id, media_type, title, season, episode)
+                 VALUES (1396, 'tvshow', 'Breaking Bad', 1, 2);
+             INSERT INTO watchlist (tmdb_id, media_type, title) VALUES (603, 'movie', 'The Matrix');",
+        )
+        .unwrap();
+
+        let history = WatchHistory { conn };
+        history.init_schema().unwrap();
+        // Migrating twice is a no-op
+        history.init_schema().unwrap();
 
-        assert_eq!(history.watched_episode_count(12345, 1), 3);
-        assert_eq!(history.watched_episode_count(12345, 2), 0);
+        assert!(history.is_watched(&tmdb(1396), MediaType::TvShow, 1, 2));
+        assert!(history.is_in_watchlist(&tmdb(603), MediaType::Movie));
+        assert_eq!(history.get_recent(10)[0].title, "Breaking Bad");
     }
 }
@@ -1,4 +1,5 @@
 mod api;
+mod calendar;
 mod cli;
 mod config;
 mod error;
@@ -51,13 +52,16 @@ async fn main() -> Result<()> {
             cli::commands::config(show, set, reset).await?;
         }
         Some(Commands::Search { query }) => {
-            cli::commands::search(query, player_override, app_mode).await?;
+            cli::commands::search(query, player_override, app_mode, cli.auto_select).await?;
+        }
+        Some(Commands::Calendar) => {
+            cli::commands::calendar().await?;
         }
         Some(Commands::Play { query: _ }) => {
             println!("Coming soon: direct play feature");
         }
         None => {
-            cli::commands::interactive(player_override, app_mode).await?;
+            cli::commands::interactive(player_override, app_mode, cli.auto_select).await?;
         }
     }
 
@@ -1,8 +1,20 @@
+use std::path::Path;
 use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
+use crate::api::language::language_codes;
 use crate::config::PlayerConfig;
 use crate::error::PlayerError;
 
+/// mpv's exit code when no file could be played
+const MPV_EXIT_PLAYBACK_FAILED: i32 = 2;
+
+/// mpv's exit code when quit by a signal (e.g. Ctrl+C in the terminal)
+const MPV_EXIT_QUIT: i32 = 4;
+
+/// A player exiting with an error sooner than this never started playing the source
+const EARLY_EXIT: Duration = Duration::from_secs(5);
+
 /// Player wrapper for launching external media players
 pub struct Player {
     config: PlayerConfig,
@@ -18,8 +30,8 @@ impl Player {
         which::which(&self.config.command).is_ok()
     }
 
-    /// Play a URL with the configured player
-    pub fn play(&self, url: &str) -> Result<(), PlayerError> {
+    /// Play URLs with the configured player (several play in order, as a playlist)
+    pub fn play(&self, urls: &[String]) -> Result<(), PlayerError> {
         if !self.is_available() {
             return Err(PlayerError::NotFound(self.config.command.clone()));
         }
@@ -31,26 +43,143 @@ impl Player {
             cmd.arg(arg);
         }
 
-        // Add the URL
-        cmd.arg(url);
+        // Add track selection for the preferred languages
+        cmd.args(self.language_args());
+
+        // Add the URLs
+        cmd.args(urls);
 
         // Inherit stdio so player can interact with terminal
         cmd.stdin(Stdio::inherit())
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit());
 
-        tracing::info!("Launching player: {} {}", self.config.command, url);
+        tracing::info!(
+            "Launching player: {} {}",
+            self.config.command,
+            urls.join(" ")
+        );
 
+        let started = Instant::now();
         let status = cmd
             .status()
             .map_err(|e| PlayerError::LaunchFailed(e.to_string()))?;
 
-        if !status.success() {
-            if let Some(code) = status.code() {
-                return Err(PlayerError::ExitError(format!("Exit code: {}", code)));
+        match status.code() {
+            Some(code) if code != 0 => self.exit_result(code, started.elapsed()),
+            _ => Ok(()),
+        }
+    }
+
+    /// What a nonzero exit code means: the source couldn't be played, the user quit,
+    /// or the player failed otherwise
+    fn exit_result(&self, code: i32, elapsed: Duration) -> Result<(), PlayerError> {
+        let mpv = self.player_name().contains("mpv");
+        if mpv && code == MPV_EXIT_QUIT {
+            return Ok(());
+        }
+        if (mpv && code == MPV_EXIT_PLAYBACK_FAILED) || elapsed < EARLY_EXIT {
+            return Err(PlayerError::PlaybackFailed(format!("Exit code: {}", code)));
+        }
+        Err(PlayerError::ExitError(format!("Exit code: {}", code)))
+    }
+
+    /// Lowercase file name of the player command, without extension
+    fn player_name(&self) -> String {
+        Path::new(&self.config.command)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    }
+
+    /// Audio/subtitle language flags for mpv or VLC, unless set in the configured arguments
+    fn language_args(&self) -> Vec<String> {
+        let player = self.player_name();
+        let (audio_flag, subtitle_flag) = if player.contains("mpv") {
+            ("--alang", "--slang")
+        } else if player.contains("vlc") {
+            ("--audio-language", "--sub-language")
+        } else {
+            return Vec::new();
+        };
+
+        let mut args = Vec::new();
+        for (flag, preferences) in [
+            (audio_flag, &self.config.preferred_audio_languages),
+            (subtitle_flag, &self.config.preferred_subtitle_languages),
+        ] {
+            let configured = self.config.args.iter().any(|arg| arg.starts_with(flag));
+            if !preferences.is_empty() && !configured {
+                args.push(format!("{}={}", flag, language_codes(preferences)));
             }
         }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(command: &str, args: &[&str]) -> Player {
+        Player::new(PlayerConfig {
+            command: command.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            preferred_audio_languages: vec!["Japanese".to_string(), "en".to_string()],
+            preferred_subtitle_languages: vec!["English".to_string()],
+        })
+    }
+
+    #[test]
+    fn test_mpv_language_args() {
+        assert_eq!(
+            player("/usr/bin/mpv", &["--fullscreen"]).language_args(),
+            vec!["--alang=ja,jpn,en,eng", "--slang=en,eng"]
+        );
+    }
+
+    #[test]
+    fn test_vlc_language_args() {
+        assert_eq!(
+            player("vlc", &[]).language_args(),
+            vec!["--audio-language=ja,jpn,en,eng", "--sub-language=en,eng"]
+        );
+    }
+
+    #[test]
+    fn test_language_args_respect_configured_flags() {
+        assert_eq!(
+            player("mpv", &["--slang=de"]).language_args(),
+            vec!["--alang=ja,jpn,en,eng"]
+        );
+        assert!(player("iina", &[]).language_args().is_empty());
+    }
+
+    #[test]
+    fn test_exit_result() {
+        let mpv = player("mpv", &[]);
+        let later = EARLY_EXIT * 60;
+
+        // Quitting with Ctrl+C is not a failure, even right away
+        assert!(mpv.exit_result(MPV_EXIT_QUIT, Duration::ZERO).is_ok());
+        assert!(matches!(
+            mpv.exit_result(MPV_EXIT_PLAYBACK_FAILED, later),
+            Err(PlayerError::PlaybackFailed(_))
+        ));
+        assert!(matches!(
+            mpv.exit_result(1, later),
+            Err(PlayerError::ExitError(_))
+        ));
 
-        Ok(())
+        // Other players: only an early exit means the source didn't play
+        let vlc = player("vlc", &[]);
+        assert!(matches!(
+            vlc.exit_result(MPV_EXIT_QUIT, Duration::from_secs(1)),
+            Err(PlayerError::PlaybackFailed(_))
+        ));
+        assert!(matches!(
+            vlc.exit_result(MPV_EXIT_QUIT, later),
+            Err(PlayerError::ExitError(_))
+        ));
     }
 }
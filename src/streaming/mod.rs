@@ -7,9 +7,12 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
+use lazy_static::lazy_static;
 use librqbit::{
-    AddTorrent, AddTorrentOptions, AddTorrentResponse, Api, ManagedTorrent, Session, SessionOptions,
+    AddTorrent, AddTorrentOptions, AddTorrentResponse, Api, ManagedTorrent, Session,
+    SessionOptions, TorrentStatsState,
 };
+use regex::Regex;
 use tokio::sync::RwLock;
 
 use crate::error::StreamingError;
@@ -22,6 +25,16 @@ const VIDEO_EXTENSIONS: &[&str] = &[
     "mkv", "mp4", "avi", "mov", "wmv", "flv", "webm", "m4v", "mpg", "mpeg", "ts", "m2ts",
 ];
 
+lazy_static! {
+    /// Episode markers in file names: "S01E03", "s1.e3", "1x03"
+    static ref EPISODE_RE: Regex =
+        Regex::new(r"(?i)(?:^|[^a-z0-9])s(\d{1,2})[ ._-]?e(\d{1,3})|(?:^|[^0-9])(\d{1,2})x(\d{2,3})(?:[^0-9]|$)")
+            .unwrap();
+}
+
+/// Subtitle file extensions
+const SUBTITLE_EXTENSIONS: &[&str] = &["srt", "ass", "ssa", "sub", "idx", "vtt"];
+
 /// Torrent streaming manager using librqbit
 pub struct TorrentStreamer {
     session: Arc<Session>,
@@ -33,11 +46,58 @@ pub struct TorrentStreamer {
 
 /// Information about an active torrent stream
 struct ActiveTorrent {
-    #[allow(dead_code)]
     handle: Arc<ManagedTorrent>,
-    #[allow(dead_code)]
-    file_index: usize,
     torrent_id: usize,
+    /// All files in the torrent
+    files: Vec<TorrentFile>,
+    /// Indices of the files being downloaded (empty until files are started)
+    selected: Vec<usize>,
+}
+
+/// A file inside a torrent
+#[derive(Debug, Clone)]
+pub struct TorrentFile {
+    /// Index of the file within the torrent
+    pub index: usize,
+    /// Path within the torrent
+    pub name: String,
+    /// Size in bytes
+    pub length: u64,
+    /// Season and episode parsed from the file name
+    pub episode: Option<(u32, u32)>,
+}
+
+impl TorrentFile {
+    pub fn new(index: usize, name: String, length: u64) -> Self {
+        let episode = file_episode(&name);
+        Self {
+            index,
+            name,
+            length,
+            episode,
+        }
+    }
+
+    fn extension(&self) -> String {
+        self.name.rsplit('.').next().unwrap_or("").to_lowercase()
+    }
+
+    pub fn is_video(&self) -> bool {
+        VIDEO_EXTENSIONS.contains(&self.extension().as_str())
+    }
+
+    pub fn is_subtitle(&self) -> bool {
+        SUBTITLE_EXTENSIONS.contains(&self.extension().as_str())
+    }
+}
+
+/// Which file of a multi-file torrent (e.g., a season pack) to stream
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileTarget {
+    /// File index reported by the source (Torrentio's `fileIdx`)
+    pub file_idx: Option<usize>,
+    /// Season and episode to look for in file names
+    pub episode: Option<(u32, u32)>,
 }
 
 /// Result of starting a stream
@@ -107,22 +167,24 @@ impl TorrentStreamer {
         })
     }
 
-    /// Start streaming a magnet link
+    /// Add a magnet link without downloading anything and list its files
     ///
-    /// Returns a StreamHandle with the HTTP URL for playback
-    pub async fn stream_magnet(&self, magnet: &str) -> Result<StreamHandle, StreamingError> {
+    /// Replaces any active torrent. Call [`start_files`](Self::start_files) to start
+    /// downloading some of the files.
+    pub async fn open_magnet(&self, magnet: &str) -> Result<Vec<TorrentFile>, StreamingError> {
         tracing::info!(
-            "Starting torrent stream for magnet: {}...",
+            "Opening torrent for magnet: {}...",
             &magnet[..magnet.len().min(60)]
         );
 
         // Clean up any existing torrent first
         self.cleanup().await;
 
-        // Add the torrent
+        // Add the torrent paused, so nothing is downloaded before files are selected
         let add_torrent = AddTorrent::from_url(magnet);
         let opts = AddTorrentOptions {
             overwrite: true,
+            paused: true,
             ..Default::default()
         };
 
@@ -145,35 +207,113 @@ impl TorrentStreamer {
         // Wait for metadata to be available
         let handle = self.wait_for_metadata(handle).await?;
 
-        // Find the largest video file using the API
-        let (file_index, file_name) = self.find_video_file(torrent_id).await?;
+        let details = self
+            .api
+            .api_torrent_details(torrent_id.into())
+            .map_err(|e| {
+                StreamingError::NoVideoFile(format!("Failed to get torrent details: {}", e))
+            })?;
+
+        let files: Vec<TorrentFile> = details
+            .files
+            .ok_or_else(|| StreamingError::NoVideoFile("No files in torrent".to_string()))?
+            .into_iter()
+            .enumerate()
+            .map(|(index, file)| TorrentFile::new(index, file.name, file.length))
+            .collect();
 
-        tracing::info!("Streaming file: {} (index {})", file_name, file_index);
+        let mut active = self.active_torrent.write().await;
+        *active = Some(ActiveTorrent {
+            handle,
+            torrent_id,
+            files: files.clone(),
+            selected: Vec::new(),
+        });
 
-        // Store the active torrent
-        {
-            let mut active = self.active_torrent.write().await;
-            *active = Some(ActiveTorrent {
-                handle: handle.clone(),
-                file_index,
-                torrent_id,
-            });
+        Ok(files)
+    }
+
+    /// Fetch a magnet's metadata without adding the torrent, returning the number of
+    /// peers seen
+    ///
+    /// Leaves the active torrent alone, so it can run alongside a stream.
+    pub async fn probe_magnet(
+        &self,
+        magnet: &str,
+        timeout: Duration,
+    ) -> Result<usize, StreamingError> {
+        let opts = AddTorrentOptions {
+            list_only: true,
+            ..Default::default()
+        };
+        let response = tokio::time::timeout(
+            timeout,
+            self.session
+                .add_torrent(AddTorrent::from_url(magnet), Some(opts)),
+        )
+        .await
+        .map_err(|_| StreamingError::Timeout("Timeout waiting for torrent metadata".to_string()))?
+        .map_err(|e| StreamingError::AddTorrent(e.to_string()))?;
+
+        match response {
+            AddTorrentResponse::ListOnly(list) => Ok(list.seen_peers.len()),
+            // Already being streamed, so the metadata is known
+            AddTorrentResponse::AlreadyManaged(_, handle)
+            | AddTorrentResponse::Added(_, handle) => Ok(handle
+                .stats()
+                .live
+                .map_or(0, |live| live.snapshot.peer_stats.live)),
         }
+    }
 
-        // Build the stream URL
-        // librqbit API: /torrents/{id}/stream/{file_idx}
-        let stream_url = format!(
-            "http://127.0.0.1:{}/torrents/{}/stream/{}",
-            self.http_port, torrent_id, file_index
-        );
+    /// Start downloading files of the opened torrent
+    ///
+    /// Only the given files' pieces are downloaded. Returns a StreamHandle for each file,
+    /// in the given order.
+    pub async fn start_files(
+        &self,
+        indices: &[usize],
+    ) -> Result<Vec<StreamHandle>, StreamingError> {
+        let mut active = self.active_torrent.write().await;
+        let active = active
+            .as_mut()
+            .ok_or_else(|| StreamingError::AddTorrent("No torrent opened".to_string()))?;
+
+        let handles = indices
+            .iter()
+            .map(|&index| {
+                let file = active.files.get(index).ok_or_else(|| {
+                    StreamingError::NoVideoFile(format!("No file {} in torrent", index))
+                })?;
+                tracing::info!("Streaming file: {} (index {})", file.name, index);
+
+                // librqbit API: /torrents/{id}/stream/{file_idx}
+                Ok(StreamHandle {
+                    stream_url: format!(
+                        "http://127.0.0.1:{}/torrents/{}/stream/{}",
+                        self.http_port, active.torrent_id, index
+                    ),
+                    file_name: file.name.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, StreamingError>>()?;
+
+        self.session
+            .update_only_files(&active.handle, &indices.iter().copied().collect())
+            .await
+            .map_err(|e| StreamingError::AddTorrent(e.to_string()))?;
+        if active.handle.is_paused() {
+            self.session
+                .unpause(&active.handle)
+                .await
+                .map_err(|e| StreamingError::AddTorrent(e.to_string()))?;
+        }
+        active.selected = indices.to_vec();
 
-        Ok(StreamHandle {
-            stream_url,
-            file_name,
-        })
+        Ok(handles)
     }
 
-    /// Wait for torrent metadata to be available
+    /// Wait for torrent metadata to be available and the torrent to finish initializing
     async fn wait_for_metadata(
         &self,
         handle: Arc<ManagedTorrent>,
@@ -191,47 +331,21 @@ impl TorrentStreamer {
 
             // Check if we have file info available via stats
             let stats = handle.stats();
-            if stats.total_bytes > 0 {
-                return Ok(handle);
+            match stats.state {
+                TorrentStatsState::Error => {
+                    return Err(StreamingError::AddTorrent(
+                        stats.error.unwrap_or_else(|| "Torrent failed".to_string()),
+                    ));
+                }
+                TorrentStatsState::Initializing => {}
+                _ if stats.total_bytes > 0 => return Ok(handle),
+                _ => {}
             }
 
             tokio::time::sleep(Duration::from_millis(500)).await;
         }
     }
 
-    /// Find the best video file in the torrent (largest video file)
-    async fn find_video_file(&self, torrent_id: usize) -> Result<(usize, String), StreamingError> {
-        // Use the API to get torrent details with file list
-        let details = self
-            .api
-            .api_torrent_details(torrent_id.into())
-            .map_err(|e| {
-                StreamingError::NoVideoFile(format!("Failed to get torrent details: {}", e))
-            })?;
-
-        let files = details
-            .files
-            .ok_or_else(|| StreamingError::NoVideoFile("No files in torrent".to_string()))?;
-
-        let mut best_match: Option<(usize, String, u64)> = None;
-
-        for (idx, file) in files.iter().enumerate() {
-            let filename = file.name.clone();
-            let extension = filename.rsplit('.').next().unwrap_or("").to_lowercase();
-
-            if VIDEO_EXTENSIONS.contains(&extension.as_str()) {
-                let size = file.length;
-                if best_match.as_ref().map_or(true, |(_, _, s)| size > *s) {
-                    best_match = Some((idx, filename, size));
-                }
-            }
-        }
-
-        best_match.map(|(idx, name, _)| (idx, name)).ok_or_else(|| {
-            StreamingError::NoVideoFile("No video files found in torrent".to_string())
-        })
-    }
-
     /// Get current streaming progress
     pub async fn get_progress(&self) -> Option<StreamProgress> {
         let active = self.active_torrent.read().await;
@@ -243,8 +357,15 @@ impl TorrentStreamer {
             .ok()?;
         let stats = details.stats?;
 
-        let total_bytes = stats.total_bytes;
-        let downloaded_bytes = stats.progress_bytes;
+        // Progress of the selected files only; other files aren't downloaded
+        let file_progress = |index: usize| stats.file_progress.get(index).copied().unwrap_or(0);
+        let total_bytes: u64 = active
+            .selected
+            .iter()
+            .filter_map(|&index| active.files.get(index))
+            .map(|file| file.length)
+            .sum();
+        let downloaded_bytes: u64 = active.selected.iter().map(|&i| file_progress(i)).sum();
         let progress_percent = if total_bytes > 0 {
             (downloaded_bytes as f64 / total_bytes as f64) * 100.0
         } else {
@@ -264,8 +385,12 @@ impl TorrentStreamer {
             total_bytes,
             download_speed,
             peers,
-            // Consider ready to play when we have at least 2% or 5MB
-            ready_to_play: progress_percent >= 2.0 || downloaded_bytes >= 5 * 1024 * 1024,
+            // Consider ready to play when we have at least 2% or 5MB of the first file
+            ready_to_play: active.selected.first().is_some_and(|&index| {
+                let length = active.files.get(index).map_or(0, |f| f.length);
+                let downloaded = file_progress(index);
+                downloaded as f64 >= length as f64 * 0.02 || downloaded >= 5 * 1024 * 1024
+            }),
         })
     }
 
@@ -299,6 +424,54 @@ impl TorrentStreamer {
     }
 }
 
+/// Pick the video file to stream from a torrent's file list
+///
+/// Uses the source's file index when it points at a video file. Otherwise, for an
+/// episode, the largest video file whose name contains its `SxxEyy` marker; when no
+/// file names carry episode markers (or for movies), the largest video file.
+pub fn select_video_file(files: &[TorrentFile], target: FileTarget) -> Option<usize> {
+    let largest = |matches: &dyn Fn(&TorrentFile) -> bool| {
+        files
+            .iter()
+            .filter(|file| file.is_video() && matches(file))
+            .max_by_key(|file| file.length)
+            .map(|file| file.index)
+    };
+
+    if let Some(idx) = target.file_idx {
+        if files.get(idx).is_some_and(|file| file.is_video()) {
+            return Some(idx);
+        }
+    }
+
+    if let Some(episode) = target.episode {
+        if let Some(idx) = largest(&|file| file.episode == Some(episode)) {
+            return Some(idx);
+        }
+        // A season pack without this episode must not fall back to another episode
+        if largest(&|file| file.episode.is_some()).is_some() {
+            return None;
+        }
+    }
+
+    largest(&|_| true)
+}
+
+/// Whether a torrent's files are worth choosing from (several videos, or subtitles)
+pub fn has_file_choice(files: &[TorrentFile]) -> bool {
+    files.iter().filter(|file| file.is_video()).count() > 1
+        || files.iter().any(|file| file.is_subtitle())
+}
+
+/// Parse the season and episode from a file name (ignoring directories)
+fn file_episode(name: &str) -> Option<(u32, u32)> {
+    let file_name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    let caps = EPISODE_RE.captures(file_name)?;
+    let season = caps.get(1).or_else(|| caps.get(3))?.as_str().parse().ok()?;
+    let episode = caps.get(2).or_else(|| caps.get(4))?.as_str().parse().ok()?;
+    Some((season, episode))
+}
+
 impl Drop for TorrentStreamer {
     fn drop(&mut self) {
         // Note: async cleanup is handled by stop() method
@@ -316,4 +489,96 @@ mod tests {
         assert!(VIDEO_EXTENSIONS.contains(&"mp4"));
         assert!(!VIDEO_EXTENSIONS.contains(&"txt"));
     }
+
+    fn make_files(names: &[(&str, u64)]) -> Vec<TorrentFile> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(index, (name, length))| TorrentFile::new(index, name.to_string(), *length))
+            .collect()
+    }
+
+    fn pack() -> Vec<TorrentFile> {
+        make_files(&[
+            ("Show S01/Show.S01E01.1080p.mkv", 900),
+            ("Show S01/Show.S01E02.1080p.mkv", 1000),
+            ("Show S01/Show.S01E03.1080p.mkv", 800),
+            ("Show S01/Show.S01E03.1080p.srt", 10),
+            ("Show S01/Sample.mkv", 50),
+        ])
+    }
+
+    #[test]
+    fn test_file_episode() {
+        assert_eq!(file_episode("Show.S01E03.1080p.mkv"), Some((1, 3)));
+        assert_eq!(file_episode("show s2 e10.mp4"), Some((2, 10)));
+        assert_eq!(file_episode("Show - 3x07 - Title.mkv"), Some((3, 7)));
+        assert_eq!(file_episode("Season 1/Show 1920x1080.mkv"), None);
+        assert_eq!(file_episode("S01E05/Movie.mkv"), None);
+    }
+
+    #[test]
+    fn test_select_video_file() {
+        let files = pack();
+
+        // Source file index wins
+        let target = FileTarget {
+            file_idx: Some(0),
+            episode: Some((1, 3)),
+        };
+        assert_eq!(select_video_file(&files, target), Some(0));
+
+        // Otherwise match the episode, not the largest file
+        let target = FileTarget {
+            file_idx: None,
+            episode: Some((1, 3)),
+        };
+        assert_eq!(select_video_file(&files, target), Some(2));
+
+        // A file index that isn't a video file is ignored
+        let target = FileTarget {
+            file_idx: Some(3),
+            episode: Some((1, 3)),
+        };
+        assert_eq!(select_video_file(&files, target), Some(2));
+
+        // Episode missing from the pack
+        let target = FileTarget {
+            file_idx: None,
+            episode: Some((1, 9)),
+        };
+        assert_eq!(select_video_file(&files, target), None);
+
+        // Movies get the largest video file
+        assert_eq!(select_video_file(&files, FileTarget::default()), Some(1));
+    }
+
+    #[test]
+    fn test_select_video_file_single_episode_torrent() {
+        // Untagged file names fall back to the largest video file
+        let files = make_files(&[("Episode.mkv", 1000), ("Sample.mkv", 10)]);
+        let target = FileTarget {
+            file_idx: None,
+            episode: Some((2, 4)),
+        };
+        assert_eq!(select_video_file(&files, target), Some(0));
+    }
+
+    #[test]
+    fn test_torrent_file_kinds() {
+        let files = pack();
+        assert_eq!(files[2].episode, Some((1, 3)));
+        assert!(files[2].is_video() && !files[2].is_subtitle());
+        assert!(files[3].is_subtitle() && !files[3].is_video());
+
+        assert!(has_file_choice(&files));
+        assert!(!has_file_choice(&make_files(&[
+            ("Movie.mkv", 1000),
+            ("info.nfo", 1)
+        ])));
+        assert!(has_file_choice(&make_files(&[
+            ("Movie.mkv", 1000),
+            ("Movie.srt", 1)
+        ])));
+    }
 }
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, VecDeque};
 use std::io::{self, Stdout};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -18,33 +19,65 @@ use ratatui::{
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio::sync::RwLock;
 
+use crate::api::anime_mapping::{absolute_offset, seasonal_episode};
+use crate::api::external_id::ExternalId;
+use crate::api::local_catalog::default_catalog_path;
+use crate::api::release_name;
 use crate::api::{
-    get_recommended_indices, pin_recommended_to_top, sort_streams_by_score, Media, MediaType,
-    ScoringOptions, Season, Stream, TmdbClient, TorrentioClient,
+    auto_select_index, check_availability, debrid_service, fallback_streams, probe_url,
+    profile_names, swarm_health, unsupported_features, AccountStatus, AnimeMapping, CollectionRef,
+    DebridService, Episode, HttpClient, KitsuEpisode, LocalCatalog, Media, MediaSource, MediaType,
+    MetadataProvider, OAuthLogin, Providers, RealDebridClient, ReleaseFilter, ScoringOptions,
+    Season, Stream, StreamChoice, StreamHealth, TmdbClient, TorrentFileInfo, TorrentInfo,
+    TorrentStep, TorrentioClient,
 };
-use crate::config::{save_config, Config};
-use crate::error::Result;
+use crate::calendar::{
+    apply_watched, fetch_calendar, shows_with_new_episodes, CalendarEntry, CalendarWindow,
+    MAX_CALENDAR_SHOWS,
+};
+use crate::config::{
+    load_config, save_config, AutoSelect, Config, DebridKind, FilterRule, UnsupportedAction,
+};
+use crate::error::{ApiError, PlayerError, Result, StreamingError};
 use crate::history::{WatchHistory, WatchedItem, WatchlistItem};
 use crate::player::Player;
-use crate::streaming::TorrentStreamer;
+use crate::streaming::{
+    has_file_choice, select_video_file, FileTarget, TorrentFile, TorrentStreamer,
+};
 use crate::ui::components::Spinner;
 use crate::ui::screens::{
+    CalendarAction, CalendarScreen, CollectionAction, CollectionScreen, DebridAction, DebridScreen,
     DownloadAction, DownloadScreen, EpisodesAction, EpisodesScreen, ErrorAction, ErrorScreen,
-    ResultsAction, ResultsScreen, SearchAction, SearchScreen, SeasonsAction, SeasonsScreen,
-    SourcesAction, SourcesContext, SourcesScreen,
+    FilesAction, FilesScreen, LibraryAction, LibraryFile, LibraryScreen, ResultsAction,
+    ResultsScreen, SearchAction, SearchScreen, SeasonsAction, SeasonsScreen, SourcesAction,
+    SourcesContext, SourcesScreen,
 };
 use crate::ui::theme::{Theme, ThemeVariant};
 
+/// Longest a background torrent health check waits for metadata
+const P2P_PROBE_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// How often to check on a torrent Real-Debrid is downloading
+const DEBRID_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Number of torrents and downloads listed in the Real-Debrid library
+const LIBRARY_LIMIT: usize = 100;
+
 /// Application state
 enum Screen {
     Search(SearchScreen),
     Results(ResultsScreen),
     Seasons(SeasonsScreen),
     Episodes(EpisodesScreen),
-    Sources(SourcesScreen),
+    Sources(Box<SourcesScreen>),
     Loading(Spinner),
     Error(ErrorScreen),
     Download(DownloadScreen),
+    Debrid(DebridScreen),
+    Library(Box<LibraryScreen>),
+    Calendar(CalendarScreen),
+    Collection(CollectionScreen),
+    Files(FilesScreen),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -81,6 +114,14 @@ enum DownloadUpdate {
     },
 }
 
+/// Progress of an uncached torrent on Real-Debrid
+enum DebridUpdate {
+    Progress(TorrentInfo),
+    /// Direct link to the downloaded file
+    Ready(String),
+    Failed(String),
+}
+
 /// Pending async operation
 enum PendingOperation {
     None,
@@ -101,10 +142,28 @@ enum PendingOperation {
     ResolveStream(Stream),
     /// Start P2P streaming for a torrent
     StartP2PStream(Stream),
+    /// Stream files of the opened torrent (as a playlist if several)
+    StreamFiles(Vec<usize>),
+    /// Open a torrent for downloading
+    StartP2PDownload(Stream),
     /// Select an item from watch history
     SelectHistoryItem(WatchedItem),
     /// Select an item from the watchlist
     SelectWatchlistItem(WatchlistItem),
+    /// Open the season containing an absolute episode number (anime)
+    JumpToAbsoluteEpisode(Media, u32),
+    /// Fetch the upcoming-episode calendar
+    LoadCalendar,
+    /// Open sources for an episode from the calendar
+    SelectCalendarEntry(CalendarEntry),
+    /// Open the collection a movie belongs to
+    OpenCollection(Media),
+    /// List the torrents and downloads in the Real-Debrid account
+    LoadLibrary,
+    /// List the files of a downloaded torrent in the Real-Debrid account
+    OpenLibraryTorrent(String),
+    /// Play or download a file from the Real-Debrid account
+    PlayLibraryFile(LibraryFile),
 }
 
 /// Context for tracking what's currently being played
@@ -114,6 +173,35 @@ struct PlaybackContext {
     season: u32,
     episode: u32,
     episode_title: Option<String>,
+    /// Stream being played, remembered to favor the same release next time
+    stream: Option<StreamChoice>,
+}
+
+/// Sources to try in turn when playback fails
+struct FallbackChain {
+    /// Sources context the streams were listed for
+    context: SourcesContext,
+    /// Key (`Stream::source_key`) of the source being tried
+    current_key: String,
+    /// Sources left to try, best first
+    remaining: VecDeque<Stream>,
+    /// Number of the source being tried, from 1
+    attempt: usize,
+    /// Number of sources in the chain
+    total: usize,
+}
+
+impl PlaybackContext {
+    /// Context for playing a stream chosen for a sources context
+    fn for_stream(context: &SourcesContext, stream: &Stream) -> Self {
+        Self {
+            media: context.media.clone(),
+            season: context.season,
+            episode: context.episode,
+            episode_title: None, // Could be enhanced to get episode title
+            stream: Some(StreamChoice::from(stream)),
+        }
+    }
 }
 
 /// Main TUI application
@@ -122,8 +210,16 @@ pub struct App {
     pending: PendingOperation,
     should_quit: bool,
     // API clients
+    http: HttpClient,
+    /// Metadata providers (TMDB and/or a local catalog)
+    providers: Providers,
+    /// TMDB client for TMDB-only features (collections, calendar, ID lookups)
     tmdb: TmdbClient,
     torrentio: TorrentioClient,
+    /// Configured debrid services, in priority order
+    debrid: Vec<Arc<dyn DebridService>>,
+    /// Real-Debrid client, for downloading uncached torrents
+    real_debrid: Option<RealDebridClient>,
     player: Player,
     mode: AppMode,
     // Theme
@@ -132,11 +228,13 @@ pub struct App {
     theme_variant: ThemeVariant,
     /// Full config (for saving theme changes)
     config: Config,
-    /// Whether to use direct P2P streaming (no Real-Debrid)
+    /// Whether to use direct P2P streaming (no debrid service)
     #[allow(dead_code)]
     use_direct_streaming: bool,
     /// Torrent streamer for P2P playback (lazily initialized)
     torrent_streamer: Arc<RwLock<Option<TorrentStreamer>>>,
+    /// Torrent opened for downloading, waiting for files to be chosen
+    download_streamer: Option<TorrentStreamer>,
     /// Streaming HTTP port
     #[allow(dead_code)]
     streaming_port: u16,
@@ -144,26 +242,95 @@ pub struct App {
     history: Option<WatchHistory>,
     /// Current playback context (for recording history)
     playback_context: Option<PlaybackContext>,
+    /// Sources to fall back to if the current one fails to play
+    fallback: Option<FallbackChain>,
+    /// Sources list the current source was chosen from (to go back to from the file browser)
+    sources_screen: Option<Box<SourcesScreen>>,
+    /// Open the file browser for the chosen torrent even when the file to play is known
+    browse_files: bool,
+    /// Health check results for the listed sources, by `Stream::source_key`
+    stream_health: HashMap<String, StreamHealth>,
+    /// Background health check result receiver
+    health_updates: Option<UnboundedReceiver<(String, StreamHealth)>>,
     /// Active download progress channel receiver
     download_updates: Option<UnboundedReceiver<DownloadUpdate>>,
     /// Cancellation flag for active download
     download_cancel: Option<Arc<AtomicBool>>,
+    /// Uncached torrent progress channel receiver
+    debrid_updates: Option<UnboundedReceiver<DebridUpdate>>,
+    /// Cancellation flag for waiting on an uncached torrent
+    debrid_cancel: Option<Arc<AtomicBool>>,
+    /// Anime ID mapping (lazily loaded on first anime lookup)
+    anime_mapping: Option<AnimeMapping>,
+    /// Seasons of the most recently opened show (source, seasons)
+    seasons_cache: Option<(MediaSource, Vec<Season>)>,
+    /// Scoring profile chosen on the sources screen, overriding the configured ones
+    scoring_profile: Option<String>,
+    /// Whether to play the best source without showing the sources screen
+    auto_select: AutoSelect,
+    /// Calendar entries for followed shows (fetched in the background at startup)
+    calendar_entries: Option<Vec<CalendarEntry>>,
+    /// Background calendar fetch result receiver
+    calendar_updates: Option<UnboundedReceiver<Vec<CalendarEntry>>>,
+    /// Real-Debrid account status (fetched in the background at startup)
+    account: Option<AccountStatus>,
+    /// Background account status fetch result receiver
+    account_updates: Option<UnboundedReceiver<AccountStatus>>,
+    /// Collections of movies looked up for search results (None if not part of one)
+    movie_collections: HashMap<MediaSource, Option<CollectionRef>>,
+    /// Background receiver of the collections of movies in search results
+    collection_updates: Option<UnboundedReceiver<(MediaSource, Option<CollectionRef>)>>,
 }
 
 impl App {
-    pub fn new(config: Config, mode: AppMode) -> Self {
+    pub fn new(config: Config, mode: AppMode) -> Result<Self> {
         let use_direct_streaming = config.use_direct_streaming();
 
-        // Create Torrentio client based on whether we have RD configured
-        let torrentio = if use_direct_streaming {
-            TorrentioClient::new_without_debrid(config.torrentio.clone())
-        } else {
-            TorrentioClient::new(config.torrentio.clone(), config.real_debrid.api_key.clone())
-        };
-
-        let tmdb = TmdbClient::new(config.tmdb.api_key.clone());
+        // All API clients share one HTTP client (connection pool and rate limits)
+        let http = HttpClient::new(&config.http)?;
+
+        // Create Torrentio client with the configured debrid services (none for P2P).
+        // Services whose caches miru checks itself are left out if configured so.
+        let debrid_services = config.debrid_services();
+        let torrentio = TorrentioClient::new(
+            config.torrentio.clone(),
+            debrid_services
+                .iter()
+                .filter(|(kind, _)| {
+                    !(config.debrid.check_availability && kind.checks_cache_by_hash())
+                })
+                .cloned()
+                .collect(),
+            http.clone(),
+        );
+
+        // Real-Debrid also gets its own client for uncached torrents, the library and
+        // the account status
+        let real_debrid = debrid_services
+            .iter()
+            .any(|(kind, _)| *kind == DebridKind::RealDebrid)
+            .then(|| {
+                let client =
+                    RealDebridClient::new(config.real_debrid.api_key.clone(), http.clone());
+                match config.real_debrid.oauth.clone() {
+                    Some(oauth) => client.with_oauth(oauth, save_real_debrid_login),
+                    None => client,
+                }
+            });
+        let debrid = debrid_services
+            .into_iter()
+            .map(|(kind, api_key)| match (kind, &real_debrid) {
+                (DebridKind::RealDebrid, Some(client)) => {
+                    Arc::new(client.clone()) as Arc<dyn DebridService>
+                }
+                _ => debrid_service(kind, api_key, http.clone()),
+            })
+            .collect();
+        let tmdb = TmdbClient::new(config.tmdb.api_key.clone(), http.clone());
+        let providers = build_providers(&config, &tmdb)?;
         let player = Player::new(config.player.clone());
         let streaming_port = config.streaming.http_port;
+        let auto_select = config.playback.auto_select;
 
         // Open watch history database
         let history = WatchHistory::open().ok();
@@ -185,12 +352,48 @@ impl App {
         let mut search_screen = SearchScreen::new_with_history(recent_history);
         search_screen.set_watchlist(watchlist);
 
-        Self {
+        // Check followed shows for new episodes in the background
+        let followed_shows = history
+            .as_ref()
+            .map(|h| h.get_followed_shows(MAX_CALENDAR_SHOWS))
+            .unwrap_or_default();
+        let calendar_updates = if tmdb.is_configured() && !followed_shows.is_empty() {
+            let (tx, rx) = mpsc::unbounded_channel();
+            let tmdb = tmdb.clone();
+            let window = CalendarWindow::from_config(&config.calendar);
+            tokio::spawn(async move {
+                let entries = fetch_calendar(&tmdb, followed_shows, window).await;
+                let _ = tx.send(entries.unwrap_or_default());
+            });
+            Some(rx)
+        } else {
+            None
+        };
+
+        // Check the Real-Debrid account (premium expiry) in the background
+        let account_updates = real_debrid.clone().map(|client| {
+            let (tx, rx) = mpsc::unbounded_channel();
+            tokio::spawn(async move {
+                match client.account().await {
+                    Ok(account) => {
+                        let _ = tx.send(account);
+                    }
+                    Err(e) => tracing::warn!("Failed to get Real-Debrid account status: {}", e),
+                }
+            });
+            rx
+        });
+
+        Ok(Self {
             screen: Screen::Search(search_screen),
             pending: PendingOperation::None,
             should_quit: false,
+            http,
+            providers,
             tmdb,
             torrentio,
+            debrid,
+            real_debrid,
             player,
             mode,
             theme: Theme::from_config(&config.ui),
@@ -198,12 +401,30 @@ impl App {
             config,
             use_direct_streaming,
             torrent_streamer: Arc::new(RwLock::new(None)),
+            download_streamer: None,
             streaming_port,
             history,
             playback_context: None,
+            fallback: None,
+            sources_screen: None,
+            browse_files: false,
+            stream_health: HashMap::new(),
+            health_updates: None,
             download_updates: None,
             download_cancel: None,
-        }
+            debrid_updates: None,
+            debrid_cancel: None,
+            anime_mapping: None,
+            seasons_cache: None,
+            scoring_profile: None,
+            auto_select,
+            calendar_entries: None,
+            calendar_updates,
+            account: None,
+            account_updates,
+            movie_collections: HashMap::new(),
+            collection_updates: None,
+        })
     }
 
     /// Cycle to the next theme variant and persist to config
@@ -216,6 +437,7 @@ impl App {
 
         // Update config and save
         self.config.ui.theme = self.theme_variant.to_config_string().to_string();
+        sync_real_debrid_tokens(&mut self.config, self.real_debrid.as_ref());
         if let Err(e) = save_config(&self.config) {
             tracing::warn!("Failed to save theme preference: {}", e);
         }
@@ -238,7 +460,7 @@ impl App {
     }
 
     /// Get watchlist IDs as a HashSet for quick lookup
-    pub fn get_watchlist_ids(&self) -> std::collections::HashSet<(i32, MediaType)> {
+    pub fn get_watchlist_ids(&self) -> std::collections::HashSet<(MediaSource, MediaType)> {
         self.history
             .as_ref()
             .map(|h| h.get_watchlist_ids())
@@ -295,23 +517,23 @@ impl App {
     #[allow(dead_code)]
     pub fn is_episode_watched(
         &self,
-        tmdb_id: i32,
+        source: &MediaSource,
         media_type: MediaType,
         season: u32,
         episode: u32,
     ) -> bool {
         self.history
             .as_ref()
-            .map(|h| h.is_watched(tmdb_id, media_type, season, episode))
+            .map(|h| h.is_watched(source, media_type, season, episode))
             .unwrap_or(false)
     }
 
     /// Get watched episode count for a season
     #[allow(dead_code)]
-    pub fn watched_episode_count(&self, tmdb_id: i32, season: u32) -> u32 {
+    pub fn watched_episode_count(&self, source: &MediaSource, season: u32) -> u32 {
         self.history
             .as_ref()
-            .map(|h| h.watched_episode_count(tmdb_id, season))
+            .map(|h| h.watched_episode_count(source, season))
             .unwrap_or(0)
     }
 
@@ -319,7 +541,7 @@ impl App {
     fn record_playback(&self) {
         if let (Some(history), Some(ctx)) = (&self.history, &self.playback_context) {
             let _ = history.mark_watched(
-                ctx.media.tmdb_id(),
+                &ctx.media.source,
                 ctx.media.media_type,
                 &ctx.media.title,
                 ctx.season,
@@ -330,24 +552,246 @@ impl App {
         }
     }
 
+    /// Remember the release that played, once it has played without failing
+    fn record_stream_choice(&self, ctx: &PlaybackContext) {
+        if let (Some(history), Some(choice)) = (&self.history, &ctx.stream) {
+            let _ = history.set_stream_choice(
+                &ctx.media.source,
+                ctx.media.media_type,
+                ctx.season,
+                ctx.episode,
+                choice,
+            );
+        }
+    }
+
     /// Create a new search screen with current history and watchlist
     fn new_search_screen(&self) -> SearchScreen {
         let mut screen = SearchScreen::new_with_history(self.get_recent_history());
         screen.set_watchlist(self.get_watchlist());
+        screen.set_new_episode_ids(self.new_episode_ids());
+        self.show_account(&mut screen);
         screen
     }
 
+    /// Show the Real-Debrid account status on a search screen
+    fn show_account(&self, screen: &mut SearchScreen) {
+        if let Some(account) = &self.account {
+            let warning = account.expiry_warning(self.config.real_debrid.expiry_warning_days);
+            screen.set_account(account.summary(), warning);
+        }
+    }
+
+    /// Why Real-Debrid has no cached sources, if premium has expired
+    fn account_warning(&self) -> Option<String> {
+        self.account
+            .as_ref()
+            .filter(|account| !account.user.is_premium())
+            .and_then(|account| account.expiry_warning(0))
+    }
+
+    /// Calendar entries with up-to-date watched flags
+    fn calendar_entries(&self) -> Option<Vec<CalendarEntry>> {
+        let mut entries = self.calendar_entries.clone()?;
+        if let Some(history) = &self.history {
+            apply_watched(&mut entries, history);
+        }
+        Some(entries)
+    }
+
+    /// TMDB IDs of followed shows with aired, unwatched episodes
+    fn new_episode_ids(&self) -> std::collections::HashSet<i32> {
+        let today = CalendarWindow::from_config(&self.config.calendar).today;
+        self.calendar_entries()
+            .map(|entries| shows_with_new_episodes(&entries, today))
+            .unwrap_or_default()
+    }
+
+    /// Receive the background calendar fetch and update "new episode" badges
+    fn poll_calendar_updates(&mut self) {
+        let Some(rx) = &mut self.calendar_updates else {
+            return;
+        };
+        let Ok(entries) = rx.try_recv() else {
+            return;
+        };
+
+        self.calendar_updates = None;
+        self.calendar_entries = Some(entries);
+        let ids = self.new_episode_ids();
+        if let Screen::Search(screen) = &mut self.screen {
+            screen.set_new_episode_ids(ids);
+        }
+    }
+
+    fn poll_account_updates(&mut self) {
+        let Some(rx) = &mut self.account_updates else {
+            return;
+        };
+        let Ok(account) = rx.try_recv() else {
+            return;
+        };
+
+        self.account_updates = None;
+        let warning = account.expiry_warning(self.config.real_debrid.expiry_warning_days);
+        if let Screen::Search(screen) = &mut self.screen {
+            screen.set_account(account.summary(), warning);
+        }
+        self.account = Some(account);
+    }
+
+    /// Show the collections of search results as they are looked up
+    fn poll_collection_updates(&mut self) {
+        let Some(rx) = &mut self.collection_updates else {
+            return;
+        };
+
+        // Results screens aren't shown again once left, so stop looking up
+        let Screen::Results(screen) = &mut self.screen else {
+            self.collection_updates = None;
+            return;
+        };
+
+        while let Ok((source, collection)) = rx.try_recv() {
+            if let Some(collection) = &collection {
+                screen.set_collection(&source, collection.clone());
+            }
+            self.movie_collections.insert(source, collection);
+        }
+    }
+
+    /// Show the collections already known for the movies in search results, and look
+    /// up the rest in the background (search results don't say)
+    fn start_collection_lookups(&mut self, results: &mut [Media]) {
+        let mut movies: Vec<(MediaSource, i32)> = vec![];
+        for media in results
+            .iter_mut()
+            .filter(|media| media.media_type == MediaType::Movie && media.collection.is_none())
+        {
+            match self.movie_collections.get(&media.source) {
+                Some(collection) => media.collection = collection.clone(),
+                None => {
+                    if let Some(id) = media.tmdb_id() {
+                        movies.push((media.source.clone(), id));
+                    }
+                }
+            }
+        }
+        if movies.is_empty() {
+            self.collection_updates = None;
+            return;
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let tmdb = self.tmdb.clone();
+        tokio::spawn(async move {
+            for (source, id) in movies {
+                // Stop once the results are gone
+                if tx.is_closed() {
+                    break;
+                }
+                let Ok(details) = tmdb.get_movie_details(id).await else {
+                    continue;
+                };
+                if tx.send((source, details.collection)).is_err() {
+                    break;
+                }
+            }
+        });
+        self.collection_updates = Some(rx);
+    }
+
+    /// Show background health check results on the sources screen
+    fn poll_health_updates(&mut self) {
+        let Some(rx) = &mut self.health_updates else {
+            return;
+        };
+
+        while let Ok((key, health)) = rx.try_recv() {
+            if let Screen::Sources(screen) = &mut self.screen {
+                screen.set_health(key.clone(), health.clone());
+            }
+            self.stream_health.insert(key, health);
+        }
+    }
+
+    /// Show uncached torrent progress, and play or download the file once it's ready
+    fn poll_debrid_updates(&mut self) {
+        let mut pending = vec![];
+        if let Some(rx) = &mut self.debrid_updates {
+            while let Ok(update) = rx.try_recv() {
+                pending.push(update);
+            }
+        }
+
+        for update in pending {
+            match update {
+                DebridUpdate::Progress(info) => {
+                    if let Screen::Debrid(screen) = &mut self.screen {
+                        screen.set_info(info);
+                    }
+                }
+                DebridUpdate::Ready(url) => {
+                    self.stop_debrid_wait();
+                    match self.mode {
+                        AppMode::Playback => self.play_url(&url),
+                        AppMode::Download => self.download_url(url),
+                    }
+                }
+                DebridUpdate::Failed(message) => {
+                    self.stop_debrid_wait();
+                    self.source_failed(message);
+                }
+            }
+        }
+    }
+
+    /// Check the top listed sources in the background
+    fn start_health_checks(&mut self) {
+        self.stream_health.clear();
+        self.health_updates = None;
+
+        let Screen::Sources(screen) = &self.screen else {
+            return;
+        };
+        if self.mode != AppMode::Playback {
+            return;
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let streams = screen
+            .list
+            .items
+            .iter()
+            .take(self.config.playback.health_checks)
+            // Requesting an uncached debrid link starts a download on the debrid service
+            .filter(|stream| stream.url.is_none() || stream.is_cached);
+        for stream in streams {
+            let tx = tx.clone();
+            let key = stream.source_key();
+            let check = check_stream_health(
+                self.http.clone(),
+                self.torrent_streamer.clone(),
+                stream.clone(),
+            );
+            tokio::spawn(async move {
+                let _ = tx.send((key, check.await));
+            });
+        }
+        self.health_updates = Some(rx);
+    }
+
     /// Get watched episodes for a specific season of a show
     fn get_watched_episodes_for_season(
         &self,
-        tmdb_id: i32,
+        source: &MediaSource,
         season_number: u32,
     ) -> std::collections::HashSet<u32> {
         self.history
             .as_ref()
             .map(|h| {
                 // Query all watched episodes for this media and season
-                h.get_watched_episodes(tmdb_id, season_number)
+                h.get_watched_episodes(source, season_number)
             })
             .unwrap_or_default()
     }
@@ -355,7 +799,7 @@ impl App {
     /// Get watched episode counts for all seasons of a show
     fn get_watched_counts_by_season(
         &self,
-        tmdb_id: i32,
+        source: &MediaSource,
         seasons: &[Season],
     ) -> std::collections::HashMap<u32, u32> {
         self.history
@@ -363,7 +807,7 @@ impl App {
             .map(|h| {
                 seasons
                     .iter()
-                    .map(|s| (s.number, h.watched_episode_count(tmdb_id, s.number)))
+                    .map(|s| (s.number, h.watched_episode_count(source, s.number)))
                     .collect()
             })
             .unwrap_or_default()
@@ -372,6 +816,17 @@ impl App {
     /// Set an initial search query
     pub fn set_initial_query(&mut self, query: &str) {
         self.screen = Screen::Search(SearchScreen::with_query(query));
+
+        // IDs and URLs are unambiguous, so open them right away
+        if ExternalId::parse(query).is_some() {
+            self.pending = PendingOperation::Search(query.to_string());
+            self.screen = Screen::Loading(Spinner::new("Opening title..."));
+        }
+    }
+
+    /// Override the configured auto-select mode for this session
+    pub fn set_auto_select(&mut self, auto_select: AutoSelect) {
+        self.auto_select = auto_select;
     }
 
     /// Run the TUI application
@@ -380,6 +835,11 @@ impl App {
 
         while !self.should_quit {
             self.poll_download_updates();
+            self.poll_calendar_updates();
+            self.poll_health_updates();
+            self.poll_debrid_updates();
+            self.poll_account_updates();
+            self.poll_collection_updates();
 
             // Render current screen
             terminal.draw(|f| self.render(f))?;
@@ -444,6 +904,11 @@ impl App {
             }
             Screen::Error(screen) => screen.render(frame, area, &self.theme),
             Screen::Download(screen) => screen.render(frame, area, &self.theme),
+            Screen::Debrid(screen) => screen.render(frame, area, &self.theme),
+            Screen::Library(screen) => screen.render(frame, area, &self.theme),
+            Screen::Calendar(screen) => screen.render(frame, area, &self.theme),
+            Screen::Collection(screen) => screen.render(frame, area, &self.theme),
+            Screen::Files(screen) => screen.render(frame, area, &self.theme),
         }
     }
 
@@ -464,6 +929,41 @@ impl App {
             return Ok(());
         }
 
+        // Global Ctrl+U handler - open the upcoming-episode calendar
+        if key.code == KeyCode::Char('u')
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+            && !matches!(
+                self.screen,
+                Screen::Loading(_) | Screen::Download(_) | Screen::Debrid(_) | Screen::Calendar(_)
+            )
+        {
+            match self.calendar_entries() {
+                Some(entries) => {
+                    let window = CalendarWindow::from_config(&self.config.calendar);
+                    self.screen = Screen::Calendar(CalendarScreen::new(entries, window));
+                }
+                None => {
+                    self.pending = PendingOperation::LoadCalendar;
+                    self.screen = Screen::Loading(Spinner::new("Loading calendar..."));
+                }
+            }
+            return Ok(());
+        }
+
+        // Global Ctrl+R handler - open the Real-Debrid library
+        if key.code == KeyCode::Char('r')
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+            && self.real_debrid.is_some()
+            && !matches!(
+                self.screen,
+                Screen::Loading(_) | Screen::Download(_) | Screen::Debrid(_)
+            )
+        {
+            self.pending = PendingOperation::LoadLibrary;
+            self.screen = Screen::Loading(Spinner::new("Loading Real-Debrid library..."));
+            return Ok(());
+        }
+
         // Global quit handler
         if key.code == KeyCode::Char('q') && matches!(self.screen, Screen::Search(_)) {
             self.should_quit = true;
@@ -494,7 +994,7 @@ impl App {
                         SearchAction::RemoveFromWatchlist(item) => {
                             if let Some(history) = &self.history {
                                 let _ =
-                                    history.remove_from_watchlist(item.tmdb_id, item.media_type);
+                                    history.remove_from_watchlist(&item.source, item.media_type);
                             }
                         }
                     }
@@ -508,14 +1008,14 @@ impl App {
                             self.screen = Screen::Loading(Spinner::new("Loading..."));
                         }
                         ResultsAction::ToggleWatchlist(media) => {
-                            let tmdb_id = media.tmdb_id();
+                            let source = &media.source;
                             let media_type = media.media_type;
                             if let Some(history) = &self.history {
-                                if screen.is_in_watchlist(tmdb_id, media_type) {
-                                    let _ = history.remove_from_watchlist(tmdb_id, media_type);
+                                if screen.is_in_watchlist(source, media_type) {
+                                    let _ = history.remove_from_watchlist(source, media_type);
                                 } else {
                                     let _ = history.add_to_watchlist(
-                                        tmdb_id,
+                                        source,
                                         media_type,
                                         &media.title,
                                         media.cover_image.as_deref(),
@@ -523,7 +1023,7 @@ impl App {
                                 }
                             }
                             // Toggle local state
-                            screen.toggle_watchlist(tmdb_id, media_type);
+                            screen.toggle_watchlist(source, media_type);
                         }
                         ResultsAction::Back => {
                             let query = screen.query.clone();
@@ -531,11 +1031,16 @@ impl App {
                             let mut search_screen =
                                 SearchScreen::with_query_and_history(&query, history);
                             search_screen.set_watchlist(self.get_watchlist());
+                            search_screen.set_new_episode_ids(self.new_episode_ids());
                             self.screen = Screen::Search(search_screen);
                         }
                         ResultsAction::Search => {
                             self.screen = Screen::Search(self.new_search_screen());
                         }
+                        ResultsAction::OpenCollection(media) => {
+                            self.pending = PendingOperation::OpenCollection(media);
+                            self.screen = Screen::Loading(Spinner::new("Loading collection..."));
+                        }
                     }
                 }
             }
@@ -568,21 +1073,21 @@ impl App {
                         }
                         EpisodesAction::ToggleWatched(episode) => {
                             let season_num = screen.season_number();
-                            let tmdb_id = screen.media.tmdb_id();
+                            let source = screen.media.source.clone();
                             let is_currently_watched = screen.is_watched(episode.number);
 
                             // Toggle in database
                             if let Some(history) = &self.history {
                                 if is_currently_watched {
                                     let _ = history.mark_unwatched(
-                                        tmdb_id,
+                                        &source,
                                         screen.media.media_type,
                                         season_num,
                                         episode.number,
                                     );
                                 } else {
                                     let _ = history.mark_watched(
-                                        tmdb_id,
+                                        &source,
                                         screen.media.media_type,
                                         &screen.media.title,
                                         season_num,
@@ -596,6 +1101,13 @@ impl App {
                             // Toggle locally in the screen
                             screen.toggle_watched(episode.number);
                         }
+                        EpisodesAction::JumpToAbsolute(absolute) => {
+                            self.pending = PendingOperation::JumpToAbsoluteEpisode(
+                                screen.media.clone(),
+                                absolute,
+                            );
+                            self.screen = Screen::Loading(Spinner::new("Loading episodes..."));
+                        }
                         EpisodesAction::Back => {
                             self.screen = Screen::Search(self.new_search_screen());
                         }
@@ -605,17 +1117,8 @@ impl App {
             Screen::Sources(screen) => {
                 if let Some(action) = screen.handle_key(key) {
                     match action {
-                        SourcesAction::Select(stream) => {
-                            // Set playback context for history tracking
-                            self.playback_context = Some(PlaybackContext {
-                                media: screen.context.media.clone(),
-                                season: screen.context.season,
-                                episode: screen.context.episode,
-                                episode_title: None, // Could be enhanced to get episode title
-                            });
-                            self.pending = PendingOperation::ResolveStream(stream);
-                            self.screen = Screen::Loading(Spinner::new("Resolving stream..."));
-                        }
+                        SourcesAction::Select(stream) => self.choose_source(*stream, false),
+                        SourcesAction::BrowseFiles(stream) => self.choose_source(*stream, true),
                         SourcesAction::Back => {
                             self.screen = Screen::Search(self.new_search_screen());
                         }
@@ -632,6 +1135,37 @@ impl App {
                             };
                             self.screen = Screen::Loading(Spinner::new(msg));
                         }
+                        SourcesAction::NextProfile => {
+                            // Re-rank the listed sources; the choice sticks for the session
+                            let names = profile_names(&self.config.scoring);
+                            let next = names
+                                .iter()
+                                .position(|name| *name == screen.scoring.profile_name)
+                                .map_or(0, |i| (i + 1) % names.len());
+                            self.scoring_profile = Some(names[next].clone());
+                            let previous_choice = screen.scoring.previous_choice.clone();
+                            let failed_sources = screen.scoring.failed_sources.clone();
+                            screen.set_scoring(
+                                scoring_options(
+                                    &self.config,
+                                    self.scoring_profile.as_deref(),
+                                    &screen.context.media,
+                                )
+                                .with_previous_choice(previous_choice)
+                                .with_failed_sources(failed_sources),
+                            );
+                        }
+                        SourcesAction::HideGroup(group) => {
+                            screen.hide_release_group(&group);
+                            self.config.filters.rules.push(FilterRule {
+                                group: Some(group),
+                                ..Default::default()
+                            });
+                            sync_real_debrid_tokens(&mut self.config, self.real_debrid.as_ref());
+                            if let Err(e) = save_config(&self.config) {
+                                tracing::warn!("Failed to save filter rule: {}", e);
+                            }
+                        }
                     }
                 }
             }
@@ -642,6 +1176,7 @@ impl App {
                         && key.modifiers.contains(KeyModifiers::CONTROL))
                 {
                     self.pending = PendingOperation::None;
+                    self.fallback = None;
                     self.screen = Screen::Search(self.new_search_screen());
                 }
             }
@@ -657,6 +1192,106 @@ impl App {
                     }
                 }
             }
+            Screen::Collection(screen) => {
+                if let Some(action) = screen.handle_key(key) {
+                    match action {
+                        CollectionAction::Select(media) => {
+                            self.pending = PendingOperation::SelectMedia(*media);
+                            self.screen = Screen::Loading(Spinner::new("Loading..."));
+                        }
+                        CollectionAction::Back => {
+                            self.screen = Screen::Search(self.new_search_screen());
+                        }
+                    }
+                }
+            }
+            Screen::Library(screen) => {
+                if let Some(action) = screen.handle_key(key) {
+                    match action {
+                        LibraryAction::OpenTorrent(id) => {
+                            screen.set_message("Loading torrent files...");
+                            self.pending = PendingOperation::OpenLibraryTorrent(id);
+                        }
+                        LibraryAction::Play(file) => {
+                            self.pending = PendingOperation::PlayLibraryFile(file);
+                            self.screen = Screen::Loading(Spinner::new("Opening file..."));
+                        }
+                        LibraryAction::Back => {
+                            self.screen = Screen::Search(self.new_search_screen());
+                        }
+                    }
+                }
+            }
+            Screen::Files(screen) => {
+                if let Some(action) = screen.handle_key(key) {
+                    match action {
+                        FilesAction::Confirm(indices) => {
+                            if let Some(streamer) = self.download_streamer.take() {
+                                self.start_p2p_download(streamer, indices);
+                            } else {
+                                self.pending = PendingOperation::StreamFiles(indices);
+                                self.screen =
+                                    Screen::Loading(Spinner::new("Starting P2P stream..."));
+                            }
+                        }
+                        FilesAction::Back => {
+                            // Drop the opened torrent and its partial files
+                            if let Some(streamer) = self.download_streamer.take() {
+                                tokio::spawn(async move { streamer.cleanup().await });
+                            } else {
+                                let streamer = self.torrent_streamer.clone();
+                                tokio::spawn(async move {
+                                    if let Some(streamer) = streamer.read().await.as_ref() {
+                                        streamer.cleanup().await;
+                                    }
+                                });
+                            }
+                            self.fallback = None;
+                            self.playback_context = None;
+                            self.screen = match self.sources_screen.take() {
+                                Some(screen) => Screen::Sources(screen),
+                                None => Screen::Search(self.new_search_screen()),
+                            };
+                        }
+                    }
+                }
+            }
+            Screen::Calendar(screen) => {
+                if let Some(action) = screen.handle_key(key) {
+                    match action {
+                        CalendarAction::Select(entry) => {
+                            self.pending = PendingOperation::SelectCalendarEntry(entry);
+                            self.screen = Screen::Loading(Spinner::new("Fetching sources..."));
+                        }
+                        CalendarAction::ToggleWatched(entry) => {
+                            if let Some(history) = &self.history {
+                                let _ = if entry.watched {
+                                    history.mark_unwatched(
+                                        &MediaSource::Tmdb { id: entry.tmdb_id },
+                                        MediaType::TvShow,
+                                        entry.season,
+                                        entry.episode,
+                                    )
+                                } else {
+                                    history.mark_watched(
+                                        &MediaSource::Tmdb { id: entry.tmdb_id },
+                                        MediaType::TvShow,
+                                        &entry.show_title,
+                                        entry.season,
+                                        entry.episode,
+                                        Some(&entry.episode_title),
+                                        None,
+                                    )
+                                };
+                            }
+                            screen.toggle_watched(&entry);
+                        }
+                        CalendarAction::Back => {
+                            self.screen = Screen::Search(self.new_search_screen());
+                        }
+                    }
+                }
+            }
             Screen::Download(screen) => {
                 if let Some(action) = screen.handle_key(key) {
                     match action {
@@ -676,6 +1311,14 @@ impl App {
                     }
                 }
             }
+            Screen::Debrid(screen) => {
+                if let Some(DebridAction::Cancel) = screen.handle_key(key) {
+                    self.stop_debrid_wait();
+                    self.fallback = None;
+                    self.playback_context = None;
+                    self.screen = Screen::Search(self.new_search_screen());
+                }
+            }
         }
 
         Ok(())
@@ -717,7 +1360,8 @@ impl App {
                 context,
                 show_uncached,
             } => {
-                self.handle_refetch_sources(context, show_uncached).await;
+                self.handle_refetch_sources(context, show_uncached, AutoSelect::Ask)
+                    .await;
             }
 
             PendingOperation::ResolveStream(stream) => {
@@ -728,10 +1372,39 @@ impl App {
                 self.handle_start_p2p_stream(stream).await;
             }
 
+            PendingOperation::StreamFiles(indices) => {
+                self.handle_stream_files(indices).await;
+            }
+
+            PendingOperation::StartP2PDownload(stream) => {
+                self.handle_start_p2p_download(stream).await;
+            }
+
             PendingOperation::SelectHistoryItem(item) => {
                 self.handle_select_history_item(item).await;
             }
 
+            PendingOperation::JumpToAbsoluteEpisode(media, absolute) => {
+                self.handle_jump_to_absolute_episode(media, absolute).await;
+            }
+            PendingOperation::OpenCollection(media) => {
+                self.handle_open_collection(media).await;
+            }
+            PendingOperation::LoadLibrary => {
+                self.handle_load_library().await;
+            }
+            PendingOperation::OpenLibraryTorrent(id) => {
+                self.handle_open_library_torrent(&id).await;
+            }
+            PendingOperation::PlayLibraryFile(file) => {
+                self.handle_play_library_file(file).await;
+            }
+            PendingOperation::LoadCalendar => {
+                self.handle_load_calendar().await;
+            }
+            PendingOperation::SelectCalendarEntry(entry) => {
+                self.handle_select_calendar_entry(entry).await;
+            }
             PendingOperation::SelectWatchlistItem(item) => {
                 self.handle_select_watchlist_item(item).await;
             }
@@ -740,10 +1413,10 @@ impl App {
 
     /// Handle selecting an item from watch history
     async fn handle_select_history_item(&mut self, item: WatchedItem) {
-        // Fetch media details from TMDB
-        let result = match item.media_type {
-            MediaType::Movie => self.tmdb.get_movie_details(item.tmdb_id).await,
-            MediaType::TvShow => self.tmdb.get_tv_show_details(item.tmdb_id).await,
+        // Fetch media details from the provider it came from
+        let result = match self.providers.for_source(&item.source) {
+            Ok(provider) => provider.details(&item.source, item.media_type).await,
+            Err(e) => Err(e),
         };
 
         match result {
@@ -761,9 +1434,10 @@ impl App {
                     }
                     MediaType::TvShow => {
                         // If we have season/episode from history, fetch that season's episodes
-                        if item.season > 0 {
+                        // (season 0 is a valid season here: TV specials)
+                        if item.episode > 0 {
                             // Fetch seasons to get the correct episode_count
-                            match self.tmdb.get_tv_details(media.tmdb_id()).await {
+                            match self.fetch_seasons(&media).await {
                                 Ok(seasons) => {
                                     if let Some(season) =
                                         seasons.into_iter().find(|s| s.number == item.season)
@@ -796,10 +1470,10 @@ impl App {
 
     /// Handle selecting an item from the watchlist
     async fn handle_select_watchlist_item(&mut self, item: WatchlistItem) {
-        // Fetch media details from TMDB (same logic as history item)
-        let result = match item.media_type {
-            MediaType::Movie => self.tmdb.get_movie_details(item.tmdb_id).await,
-            MediaType::TvShow => self.tmdb.get_tv_show_details(item.tmdb_id).await,
+        // Fetch media details (same logic as history item)
+        let result = match self.providers.for_source(&item.source) {
+            Ok(provider) => provider.details(&item.source, item.media_type).await,
+            Err(e) => Err(e),
         };
 
         match result {
@@ -825,31 +1499,45 @@ impl App {
         }
     }
 
-    /// Search TMDB for movies and TV shows
-    async fn handle_search(&mut self, query: &str) {
-        match self.tmdb.search_all(query).await {
-            Ok(mut results) => {
-                if results.is_empty() {
-                    self.screen = Screen::Error(ErrorScreen::new(
-                        "No results found. Try a different search term.".to_string(),
-                        true,
-                    ));
-                } else {
-                    // Sort results by score (descending)
-                    results.sort_by(|a, b| {
-                        let score_a = a.score.unwrap_or(0.0);
-                        let score_b = b.score.unwrap_or(0.0);
-                        score_b
-                            .partial_cmp(&score_a)
-                            .unwrap_or(std::cmp::Ordering::Equal)
-                    });
-
-                    let watchlist_ids = self.get_watchlist_ids();
-                    self.screen = Screen::Results(
-                        ResultsScreen::new(query.to_string(), results)
-                            .with_watchlist_ids(watchlist_ids),
-                    );
+    /// Open the collection a movie belongs to
+    async fn handle_open_collection(&mut self, media: Media) {
+        // Search results don't include the collection, so fetch movie details if needed
+        let tmdb_id = media.tmdb_id();
+        let known = match media.media_type {
+            MediaType::Movie => self.movie_collections.get(&media.source).cloned(),
+            _ => None,
+        };
+        let collection = match (media.collection, known, tmdb_id) {
+            (Some(collection), _, _) => Some(collection),
+            // Already looked up for search results
+            (None, Some(known), _) => known,
+            (None, None, Some(id)) => match self.tmdb.get_movie_details(id).await {
+                Ok(details) => details.collection,
+                Err(e) => {
+                    self.screen = Screen::Error(ErrorScreen::new(e.to_string(), true));
+                    return;
                 }
+            },
+            // Collections are a TMDB feature
+            (None, None, None) => None,
+        };
+        let Some(collection) = collection else {
+            self.screen = Screen::Error(ErrorScreen::new(
+                format!("{} is not part of a collection", media.title),
+                false,
+            ));
+            return;
+        };
+
+        match self.tmdb.get_collection(collection.id).await {
+            Ok(collection) => {
+                let watched = collection
+                    .parts
+                    .iter()
+                    .map(|part| part.source.clone())
+                    .filter(|source| self.is_episode_watched(source, MediaType::Movie, 0, 0))
+                    .collect();
+                self.screen = Screen::Collection(CollectionScreen::new(collection, watched));
             }
             Err(e) => {
                 self.screen = Screen::Error(ErrorScreen::new(e.to_string(), true));
@@ -857,13 +1545,234 @@ impl App {
         }
     }
 
-    /// Handle media selection based on type
-    async fn handle_select_media(&mut self, media: Media) {
-        match media.media_type {
-            MediaType::Movie => {
-                // Movies go directly to sources (no episode selection)
-                self.pending = PendingOperation::FetchSources {
-                    media,
+    /// Fetch the calendar for followed shows and show it
+    async fn handle_load_calendar(&mut self) {
+        let shows = self
+            .history
+            .as_ref()
+            .map(|h| h.get_followed_shows(MAX_CALENDAR_SHOWS))
+            .unwrap_or_default();
+        let window = CalendarWindow::from_config(&self.config.calendar);
+
+        match fetch_calendar(&self.tmdb, shows, window).await {
+            Ok(entries) => self.calendar_entries = Some(entries),
+            Err(e) => {
+                self.screen = Screen::Error(ErrorScreen::new(e.to_string(), true));
+                return;
+            }
+        }
+        self.calendar_updates = None;
+
+        let entries = self.calendar_entries().unwrap_or_default();
+        self.screen = Screen::Calendar(CalendarScreen::new(entries, window));
+    }
+
+    /// List the torrents and downloads in the Real-Debrid account
+    async fn handle_load_library(&mut self) {
+        let Some(client) = &self.real_debrid else {
+            return;
+        };
+
+        let (torrents, downloads, account) = tokio::join!(
+            client.torrents(LIBRARY_LIMIT),
+            client.downloads(LIBRARY_LIMIT),
+            client.account()
+        );
+        if let Ok(account) = account {
+            self.account = Some(account);
+        }
+        match torrents.and_then(|torrents| Ok((torrents, downloads?))) {
+            Ok((torrents, downloads)) => {
+                let mut screen = LibraryScreen::new(torrents, downloads);
+                if let Some(account) = &self.account {
+                    screen.set_account(account.clone());
+                }
+                self.screen = Screen::Library(Box::new(screen));
+            }
+            Err(e) => {
+                self.screen = Screen::Error(ErrorScreen::new(
+                    format!("Failed to load Real-Debrid library: {}", e),
+                    true,
+                ));
+            }
+        }
+    }
+
+    /// List the files of a downloaded torrent, or play it right away if it has one
+    async fn handle_open_library_torrent(&mut self, id: &str) {
+        let Some(client) = &self.real_debrid else {
+            return;
+        };
+
+        let info = client.torrent_info(id).await;
+        let Screen::Library(screen) = &mut self.screen else {
+            return;
+        };
+        let info = match info {
+            Ok(info) => info,
+            Err(e) => {
+                screen.set_message(format!("Failed to open torrent: {}", e));
+                return;
+            }
+        };
+
+        let mut files: Vec<LibraryFile> = info
+            .selected_links()
+            .into_iter()
+            .map(|(file, link)| LibraryFile {
+                name: file.path.clone(),
+                bytes: file.bytes,
+                link: link.to_string(),
+                direct: false,
+            })
+            .collect();
+        if files.len() == 1 {
+            self.screen = Screen::Loading(Spinner::new("Opening file..."));
+            self.handle_play_library_file(files.remove(0)).await;
+        } else {
+            screen.show_files(info.filename, files);
+        }
+    }
+
+    /// Play or download a file from the Real-Debrid account, matching it to a title
+    /// so it's recorded in the watch history
+    async fn handle_play_library_file(&mut self, file: LibraryFile) {
+        let Some(client) = &self.real_debrid else {
+            return;
+        };
+
+        let url = if file.direct {
+            file.link.clone()
+        } else {
+            match client.unrestrict_link(&file.link).await {
+                Ok(link) => link.download,
+                Err(e) => {
+                    self.screen = Screen::Error(ErrorScreen::new(
+                        format!("Failed to unrestrict link: {}", e),
+                        false,
+                    ));
+                    return;
+                }
+            }
+        };
+
+        self.fallback = None;
+        self.playback_context = self.match_library_file(&file.name).await;
+        match self.mode {
+            AppMode::Playback => self.play_url(&url),
+            AppMode::Download => self.download_url(url),
+        }
+    }
+
+    /// Find the movie or episode a file name belongs to
+    async fn match_library_file(&self, path: &str) -> Option<PlaybackContext> {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        let (title, year) = release_name::parse_title(name);
+        if title.is_empty() {
+            return None;
+        }
+        let release = release_name::parse(name);
+        let media_type = if release.episodes.is_some() {
+            MediaType::TvShow
+        } else {
+            MediaType::Movie
+        };
+
+        let results = self.providers.primary().search(&title).await.ok()?;
+        // Release years can be a year off from the listed one
+        let media = results.into_iter().find(|media| {
+            media.media_type == media_type
+                && year.is_none_or(|year| media.year.is_some_and(|y| (y - year).abs() <= 1))
+        })?;
+        tracing::info!("Matched {} to {}", name, media.display_title());
+
+        let (season, episode) = match media_type {
+            MediaType::TvShow => (
+                release.seasons.map_or(1, |seasons| *seasons.start()),
+                release.episodes.map_or(1, |episodes| *episodes.start()),
+            ),
+            MediaType::Movie => (0, 0),
+        };
+        Some(PlaybackContext {
+            media,
+            season,
+            episode,
+            episode_title: None,
+            stream: None,
+        })
+    }
+
+    /// Open sources for an episode picked from the calendar
+    async fn handle_select_calendar_entry(&mut self, entry: CalendarEntry) {
+        match self.tmdb.get_tv_show_details(entry.tmdb_id).await {
+            Ok(media) => {
+                self.pending = PendingOperation::FetchSources {
+                    media,
+                    season: entry.season,
+                    episode: entry.episode,
+                    show_uncached: false,
+                };
+            }
+            Err(e) => {
+                self.screen = Screen::Error(ErrorScreen::new(
+                    format!("Failed to load media details: {}", e),
+                    true,
+                ));
+            }
+        }
+    }
+
+    /// Search TMDB for movies and TV shows
+    async fn handle_search(&mut self, query: &str) {
+        // IDs and URLs open the title directly, skipping the results list
+        if let Some(external_id) = ExternalId::parse(query) {
+            match external_id.resolve(&self.tmdb, &self.http).await {
+                Ok(media) => self.handle_select_media(media).await,
+                Err(e) => {
+                    self.screen = Screen::Error(ErrorScreen::new(e.to_string(), true));
+                }
+            }
+            return;
+        }
+
+        match self.providers.primary().search(query).await {
+            Ok(mut results) => {
+                if results.is_empty() {
+                    self.screen = Screen::Error(ErrorScreen::new(
+                        "No results found. Try a different search term.".to_string(),
+                        true,
+                    ));
+                } else {
+                    // Sort results by score (descending)
+                    results.sort_by(|a, b| {
+                        let score_a = a.score.unwrap_or(0.0);
+                        let score_b = b.score.unwrap_or(0.0);
+                        score_b
+                            .partial_cmp(&score_a)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    });
+
+                    self.start_collection_lookups(&mut results);
+                    let watchlist_ids = self.get_watchlist_ids();
+                    self.screen = Screen::Results(
+                        ResultsScreen::new(query.to_string(), results)
+                            .with_watchlist_ids(watchlist_ids),
+                    );
+                }
+            }
+            Err(e) => {
+                self.screen = Screen::Error(ErrorScreen::new(e.to_string(), true));
+            }
+        }
+    }
+
+    /// Handle media selection based on type
+    async fn handle_select_media(&mut self, media: Media) {
+        match media.media_type {
+            MediaType::Movie => {
+                // Movies go directly to sources (no episode selection)
+                self.pending = PendingOperation::FetchSources {
+                    media,
                     season: 0,  // Not applicable for movies
                     episode: 0, // Not applicable for movies
                     show_uncached: false,
@@ -878,10 +1787,9 @@ impl App {
 
     /// Fetch seasons for TV shows
     async fn handle_fetch_seasons(&mut self, media: Media) {
-        let tmdb_id = media.tmdb_id();
-
-        match self.tmdb.get_tv_details(tmdb_id).await {
+        match self.fetch_seasons(&media).await {
             Ok(seasons) => {
+                self.seasons_cache = Some((media.source.clone(), seasons.clone()));
                 if seasons.is_empty() {
                     self.screen = Screen::Error(ErrorScreen::new(
                         "No seasons found for this show".to_string(),
@@ -891,31 +1799,28 @@ impl App {
                     // Only one season, skip to episodes
                     let season = seasons.into_iter().next().unwrap();
                     // Fetch detailed episode metadata
-                    match self
-                        .tmdb
-                        .get_season_episodes(media.tmdb_id(), season.number)
-                        .await
-                    {
+                    match self.fetch_episodes(&media, season.number).await {
                         Ok(episodes) => {
-                            let watched = self
-                                .get_watched_episodes_for_season(media.tmdb_id(), season.number);
+                            let watched =
+                                self.get_watched_episodes_for_season(&media.source, season.number);
                             let mut screen =
                                 EpisodesScreen::with_episodes(media, Some(season), episodes);
                             screen.set_watched_episodes(watched);
+                            self.set_absolute_numbering(&mut screen, &[]);
                             self.screen = Screen::Episodes(screen);
                         }
                         Err(_) => {
                             // Fallback to basic episode list
-                            let watched = self
-                                .get_watched_episodes_for_season(media.tmdb_id(), season.number);
+                            let watched =
+                                self.get_watched_episodes_for_season(&media.source, season.number);
                             let mut screen = EpisodesScreen::with_season(media, season);
                             screen.set_watched_episodes(watched);
+                            self.set_absolute_numbering(&mut screen, &[]);
                             self.screen = Screen::Episodes(screen);
                         }
                     }
                 } else {
-                    let watched_counts =
-                        self.get_watched_counts_by_season(media.tmdb_id(), &seasons);
+                    let watched_counts = self.get_watched_counts_by_season(&media.source, &seasons);
                     let mut screen = SeasonsScreen::new(media, seasons);
                     screen.set_watched_counts(watched_counts);
                     self.screen = Screen::Seasons(screen);
@@ -929,30 +1834,137 @@ impl App {
 
     /// Fetch episodes for TV show season
     async fn handle_fetch_episodes(&mut self, media: Media, season: Option<Season>) {
-        let tmdb_id = media.tmdb_id();
         let season_number = season.as_ref().map(|s| s.number).unwrap_or(1);
 
-        // Try to fetch detailed episode metadata from TMDB
-        match self.tmdb.get_season_episodes(tmdb_id, season_number).await {
+        // Absolute numbering needs the episode counts of earlier seasons
+        let seasons = if media.is_anime() {
+            self.show_seasons(&media).await
+        } else {
+            Vec::new()
+        };
+
+        // Try to fetch detailed episode metadata
+        match self.fetch_episodes(&media, season_number).await {
             Ok(episodes) => {
-                let watched = self.get_watched_episodes_for_season(tmdb_id, season_number);
+                let watched = self.get_watched_episodes_for_season(&media.source, season_number);
                 let mut screen = EpisodesScreen::with_episodes(media, season, episodes);
                 screen.set_watched_episodes(watched);
+                self.set_absolute_numbering(&mut screen, &seasons);
                 self.screen = Screen::Episodes(screen);
             }
             Err(_) => {
                 // Fallback to basic episode list without metadata
-                let watched = self.get_watched_episodes_for_season(tmdb_id, season_number);
+                let watched = self.get_watched_episodes_for_season(&media.source, season_number);
                 let mut screen = match season {
                     Some(s) => EpisodesScreen::with_season(media, s),
                     None => EpisodesScreen::new(media),
                 };
                 screen.set_watched_episodes(watched);
+                self.set_absolute_numbering(&mut screen, &seasons);
                 self.screen = Screen::Episodes(screen);
             }
         }
     }
 
+    /// Open the season containing an absolute episode number and select that episode
+    async fn handle_jump_to_absolute_episode(&mut self, media: Media, absolute: u32) {
+        let seasons = self.show_seasons(&media).await;
+        let Some((season_number, episode)) = seasonal_episode(&seasons, absolute) else {
+            self.screen = Screen::Error(ErrorScreen::new(
+                format!("Episode {} not found", absolute),
+                false,
+            ));
+            return;
+        };
+
+        let season = seasons.into_iter().find(|s| s.number == season_number);
+        self.handle_fetch_episodes(media, season).await;
+        if let Screen::Episodes(screen) = &mut self.screen {
+            screen.set_show_absolute(true);
+            screen.select_episode(episode);
+        }
+    }
+
+    /// Enable absolute episode numbering on the episodes screen (anime only)
+    fn set_absolute_numbering(&self, screen: &mut EpisodesScreen, seasons: &[Season]) {
+        if !screen.media.is_anime() {
+            return;
+        }
+        if let Some(offset) = absolute_offset(seasons, screen.season_number()) {
+            screen.set_absolute_offset(offset, self.config.anime.absolute_numbering);
+        }
+    }
+
+    /// Fetch the seasons of a show from its metadata provider
+    async fn fetch_seasons(&self, media: &Media) -> std::result::Result<Vec<Season>, ApiError> {
+        self.providers
+            .for_source(&media.source)?
+            .seasons(media)
+            .await
+    }
+
+    /// Fetch the episodes of a season from the show's metadata provider
+    async fn fetch_episodes(
+        &self,
+        media: &Media,
+        season: u32,
+    ) -> std::result::Result<Vec<Episode>, ApiError> {
+        self.providers
+            .for_source(&media.source)?
+            .episodes(media, season)
+            .await
+    }
+
+    /// Get the seasons of a show, from the cache when possible
+    async fn show_seasons(&mut self, media: &Media) -> Vec<Season> {
+        if let Some((source, seasons)) = &self.seasons_cache {
+            if *source == media.source {
+                return seasons.clone();
+            }
+        }
+
+        match self.fetch_seasons(media).await {
+            Ok(seasons) => {
+                self.seasons_cache = Some((media.source.clone(), seasons.clone()));
+                seasons
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Resolve a TV episode to its Kitsu equivalent using the anime mapping
+    async fn resolve_kitsu_episode(
+        &mut self,
+        media: &Media,
+        season: u32,
+        episode: u32,
+    ) -> Option<KitsuEpisode> {
+        if media.media_type != MediaType::TvShow || !media.is_anime() {
+            return None;
+        }
+
+        if self.anime_mapping.is_none() {
+            let mapping = AnimeMapping::load(&self.config.anime, &self.http)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load anime mapping: {}", e);
+                    AnimeMapping::default()
+                });
+            self.anime_mapping = Some(mapping);
+        }
+
+        // The mapping is keyed by TMDB ID
+        let tmdb_id = media.tmdb_id()?;
+        if !self.anime_mapping.as_ref()?.contains(tmdb_id) {
+            return None;
+        }
+
+        let seasons = self.show_seasons(media).await;
+        self.anime_mapping
+            .as_ref()?
+            .resolve(tmdb_id, &seasons, season, episode)
+    }
+
     /// Fetch sources from Torrentio
     async fn handle_fetch_sources(
         &mut self,
@@ -961,9 +1973,13 @@ impl App {
         episode: u32,
         show_uncached: bool,
     ) {
+        // Mapped anime can be looked up through Kitsu even without an IMDb entry
+        let kitsu = self.resolve_kitsu_episode(&media, season, episode).await;
+
         // Get IMDB ID based on source
         let imdb_id = match self.get_imdb_id(&media).await {
             Ok(id) => id,
+            Err(_) if kitsu.is_some() => String::new(),
             Err(e) => {
                 self.screen = Screen::Error(ErrorScreen::new(e.to_string(), false));
                 return;
@@ -972,64 +1988,109 @@ impl App {
 
         // Create context for potential re-fetching
         let context = SourcesContext {
-            media: media.clone(),
+            media,
             season,
             episode,
-            imdb_id: imdb_id.clone(),
+            imdb_id,
+            kitsu,
         };
 
-        // Fetch streams based on media type
-        let streams_result = match media.media_type {
-            MediaType::Movie => {
-                self.torrentio
-                    .get_movie_streams(&imdb_id, show_uncached)
-                    .await
-            }
-            MediaType::TvShow => {
-                self.torrentio
-                    .get_streams(&imdb_id, season, episode, show_uncached)
-                    .await
-            }
-        };
+        self.handle_refetch_sources(context, show_uncached, self.auto_select)
+            .await;
+    }
 
-        match streams_result {
+    /// Fetch sources for an existing context (also used to toggle the uncached setting)
+    ///
+    /// With auto-select on, the best source is resolved right away; the sources screen
+    /// is only shown if no source qualifies.
+    async fn handle_refetch_sources(
+        &mut self,
+        context: SourcesContext,
+        show_uncached: bool,
+        auto_select: AutoSelect,
+    ) {
+        match self.fetch_streams(&context, show_uncached).await {
             Ok(mut streams) => {
-                // Detect anime from genres
-                let is_anime = media
-                    .genres
-                    .iter()
-                    .any(|g| g.eq_ignore_ascii_case("animation"));
+                // P2P-only sources may still be cached on a debrid service
+                check_availability(&self.debrid, &mut streams).await;
 
-                // Build scoring options from media context
-                let scoring_options = ScoringOptions {
-                    media_type: media.media_type,
-                    is_anime,
+                // Apply the filter rules; the screen ranks what's left
+                let filter = match ReleaseFilter::new(&self.config.filters) {
+                    Ok(filter) => filter,
+                    Err(e) => {
+                        self.screen = Screen::Error(ErrorScreen::new(e.to_string(), true));
+                        return;
+                    }
                 };
+                let mut streams = filter.apply(streams);
+                let previous_choice = self.history.as_ref().and_then(|history| {
+                    history.last_stream_choice(&context.media.source, context.media.media_type)
+                });
+                let failed_sources = self
+                    .history
+                    .as_ref()
+                    .map(|history| history.failed_sources())
+                    .unwrap_or_default();
+                let scoring = scoring_options(
+                    &self.config,
+                    self.scoring_profile.as_deref(),
+                    &context.media,
+                )
+                .with_previous_choice(previous_choice)
+                .with_failed_sources(failed_sources);
+
+                // Sources the playback device can't handle are penalized by the scoring
+                // unless configured to be hidden
+                let capabilities = &self.config.playback.capabilities;
+                if capabilities.unsupported == UnsupportedAction::Hide {
+                    streams.hide(|stream| {
+                        !unsupported_features(stream, capabilities, context.media.media_type)
+                            .is_empty()
+                    });
+                }
 
-                // Sort streams by score
-                sort_streams_by_score(&mut streams, &scoring_options);
-
-                // Get recommended source indices (top 2) and pin to top
-                let recommended = get_recommended_indices(&streams, &scoring_options, 2);
-                let recommended_count = recommended.len();
-                let streams = pin_recommended_to_top(streams, &recommended);
+                let auto_pick = match auto_select {
+                    AutoSelect::Best => auto_select_index(&streams.shown, &scoring, false),
+                    AutoSelect::BestCached => auto_select_index(&streams.shown, &scoring, true),
+                    AutoSelect::Ask => None,
+                };
+                if let Some(index) = auto_pick {
+                    let stream = streams.shown.swap_remove(index);
+                    if auto_select == AutoSelect::BestCached {
+                        streams.shown.retain(|stream| stream.is_cached);
+                    }
+                    let fallbacks = fallback_streams(
+                        &streams.shown,
+                        &scoring,
+                        &stream,
+                        self.config.playback.fallback_retries,
+                    );
+                    self.start_source(context, &stream, fallbacks);
+                    self.handle_resolve_stream(stream).await;
+                    return;
+                }
 
-                // Always show sources screen, even if empty
-                let title = media.display_title().to_string();
-                let ep_num = if media.media_type == MediaType::Movie {
+                // Otherwise show sources screen, even if empty
+                let title = context.media.display_title().to_string();
+                let ep_num = if context.media.media_type == MediaType::Movie {
                     0
                 } else {
-                    episode
+                    context.episode
                 };
-                self.screen = Screen::Sources(SourcesScreen::new(
+                let mut screen = SourcesScreen::new(
                     title,
                     ep_num,
                     streams,
                     context,
                     show_uncached,
-                    recommended_count,
+                    scoring,
                     self.mode.action_label(),
-                ));
+                );
+                if let Some(warning) = self.account_warning() {
+                    screen.set_account_warning(warning);
+                }
+                self.screen = Screen::Sources(Box::new(screen));
+                self.start_health_checks();
             }
             Err(e) => {
                 self.screen = Screen::Error(ErrorScreen::new(e.to_string(), true));
@@ -1037,10 +2098,40 @@ impl App {
         }
     }
 
-    /// Re-fetch sources with different uncached setting
-    async fn handle_refetch_sources(&mut self, context: SourcesContext, show_uncached: bool) {
-        // Fetch streams based on media type
-        let streams_result = match context.media.media_type {
+    /// Fetch streams from Torrentio for a sources context
+    ///
+    /// Mapped anime are looked up by Kitsu ID first, falling back to the IMDb ID
+    /// when Kitsu has no results.
+    async fn fetch_streams(
+        &mut self,
+        context: &SourcesContext,
+        show_uncached: bool,
+    ) -> std::result::Result<Vec<Stream>, crate::error::ApiError> {
+        // Torrentio resolves links with the access tokens, so it needs current ones
+        for service in &self.debrid {
+            if let Err(e) = service.ensure_token().await {
+                tracing::warn!("Failed to refresh {} token: {}", service.kind().name(), e);
+            }
+            self.torrentio
+                .set_api_key(service.kind(), service.api_key());
+        }
+
+        if let Some(kitsu) = context.kitsu {
+            let result = self
+                .torrentio
+                .get_kitsu_streams(kitsu.kitsu_id, kitsu.episode, show_uncached)
+                .await;
+            match result {
+                Ok(streams) if !streams.is_empty() || context.imdb_id.is_empty() => {
+                    return Ok(streams)
+                }
+                Err(e) if context.imdb_id.is_empty() => return Err(e),
+                Ok(_) => tracing::debug!("No Kitsu streams, falling back to IMDb ID"),
+                Err(e) => tracing::warn!("Kitsu lookup failed, falling back to IMDb ID: {}", e),
+            }
+        }
+
+        match context.media.media_type {
             MediaType::Movie => {
                 self.torrentio
                     .get_movie_streams(&context.imdb_id, show_uncached)
@@ -1056,102 +2147,243 @@ impl App {
                     )
                     .await
             }
+        }
+    }
+
+    /// Get IMDB ID for a media item
+    async fn get_imdb_id(&self, media: &Media) -> std::result::Result<String, ApiError> {
+        // If we already have IMDB ID, use it
+        if let Some(imdb_id) = &media.imdb_id {
+            return Ok(imdb_id.clone());
+        }
+
+        self.providers
+            .for_source(&media.source)?
+            .imdb_id(media)
+            .await
+    }
+
+    /// Resolve a stream picked on the sources screen, keeping the screen to go back to
+    fn choose_source(&mut self, stream: Stream, browse_files: bool) {
+        let previous = std::mem::replace(
+            &mut self.screen,
+            Screen::Loading(Spinner::new("Resolving stream...")),
+        );
+        let Screen::Sources(screen) = previous else {
+            self.screen = previous;
+            return;
         };
 
-        match streams_result {
-            Ok(mut streams) => {
-                // Detect anime from genres
-                let is_anime = context
-                    .media
-                    .genres
-                    .iter()
-                    .any(|g| g.eq_ignore_ascii_case("animation"));
+        let fallbacks = screen.fallbacks(&stream, self.config.playback.fallback_retries);
+        self.start_source(screen.context.clone(), &stream, fallbacks);
+        self.sources_screen = Some(screen);
+        self.browse_files = browse_files;
+        self.pending = PendingOperation::ResolveStream(stream);
+    }
 
-                // Build scoring options from media context
-                let scoring_options = ScoringOptions {
-                    media_type: context.media.media_type,
-                    is_anime,
-                };
+    /// Set up playback of a stream chosen for a sources context, with the sources
+    /// to fall back to if it fails to play
+    fn start_source(&mut self, context: SourcesContext, stream: &Stream, fallbacks: Vec<Stream>) {
+        self.sources_screen = None;
+        self.browse_files = false;
+        // Set playback context for history tracking
+        self.playback_context = Some(PlaybackContext::for_stream(&context, stream));
+        self.fallback = (self.mode == AppMode::Playback).then(|| FallbackChain {
+            context,
+            current_key: stream.source_key(),
+            total: fallbacks.len() + 1,
+            remaining: fallbacks.into(),
+            attempt: 1,
+        });
+    }
 
-                // Sort streams by score
-                sort_streams_by_score(&mut streams, &scoring_options);
+    /// Handle the current source failing to play: remember the failure (so the source
+    /// is demoted next time) and try the next fallback source, if any
+    fn source_failed(&mut self, message: String) {
+        if let (Some(history), Some(chain)) = (&self.history, &self.fallback) {
+            let _ = history.record_source_failure(&chain.current_key);
+        }
+        self.try_next_source(message);
+    }
 
-                // Get recommended source indices (top 2) and pin to top
-                let recommended = get_recommended_indices(&streams, &scoring_options, 2);
-                let recommended_count = recommended.len();
-                let streams = pin_recommended_to_top(streams, &recommended);
+    /// Move on to the next fallback source, or show the error if there is none left
+    fn try_next_source(&mut self, message: String) {
+        let Some(chain) = self.fallback.as_mut() else {
+            self.screen = Screen::Error(ErrorScreen::new(message, false));
+            return;
+        };
 
-                let title = context.media.display_title().to_string();
-                let ep_num = if context.media.media_type == MediaType::Movie {
-                    0
+        tracing::warn!(
+            "Source {}/{} failed: {}",
+            chain.attempt,
+            chain.total,
+            message
+        );
+
+        match chain.remaining.pop_front() {
+            Some(stream) => {
+                // The file browser was asked for the chosen source, not its fallbacks
+                self.browse_files = false;
+                chain.attempt += 1;
+                chain.current_key = stream.source_key();
+                self.playback_context = Some(PlaybackContext::for_stream(&chain.context, &stream));
+                self.pending = PendingOperation::ResolveStream(stream);
+                self.screen = Screen::Loading(self.source_spinner("Source failed"));
+            }
+            None => {
+                let message = if chain.total > 1 {
+                    format!(
+                        "All {} sources failed to play.\n\nLast error: {}",
+                        chain.total, message
+                    )
                 } else {
-                    context.episode
+                    message
                 };
-                self.screen = Screen::Sources(SourcesScreen::new(
-                    title,
-                    ep_num,
-                    streams,
-                    context,
-                    show_uncached,
-                    recommended_count,
-                    self.mode.action_label(),
-                ));
-            }
-            Err(e) => {
-                self.screen = Screen::Error(ErrorScreen::new(e.to_string(), true));
+                self.fallback = None;
+                self.screen = Screen::Error(ErrorScreen::new(message, false));
             }
         }
     }
 
-    /// Get IMDB ID for a media item
-    async fn get_imdb_id(
-        &self,
-        media: &Media,
-    ) -> std::result::Result<String, crate::error::ApiError> {
-        // If we already have IMDB ID, use it
-        if let Some(imdb_id) = &media.imdb_id {
-            return Ok(imdb_id.clone());
-        }
-
-        let id = media.tmdb_id();
-        match media.media_type {
-            MediaType::Movie => self.tmdb.get_movie_external_ids(id).await,
-            MediaType::TvShow => self.tmdb.get_tv_external_ids(id).await,
+    /// Loading spinner, saying which source is tried after a fallback
+    fn source_spinner(&self, message: &str) -> Spinner {
+        match &self.fallback {
+            Some(chain) if chain.attempt > 1 => Spinner::new(format!(
+                "{}, trying source {}/{}...",
+                message.trim_end_matches("..."),
+                chain.attempt,
+                chain.total
+            )),
+            _ => Spinner::new(message),
         }
     }
 
     /// Resolve selected stream for playback or download
     async fn handle_resolve_stream(&mut self, stream: Stream) {
         if self.mode == AppMode::Download {
-            self.start_download(stream);
+            if stream.url.is_none() && stream.info_hash.is_some() {
+                // P2P: open the torrent first to choose files
+                self.pending = PendingOperation::StartP2PDownload(stream);
+                self.screen = Screen::Loading(Spinner::new("Opening torrent..."));
+            } else if !self.start_debrid_torrent(&stream) {
+                self.start_download(stream);
+            }
             return;
         }
 
-        // Check if we have a direct URL (Real-Debrid) or need P2P streaming
+        // Don't launch the player on a source that definitely failed its health check
+        // (inconclusive checks don't count). Cached debrid links are quick to check, so
+        // they are checked now if not done yet.
+        let health = match self.stream_health.get(&stream.source_key()) {
+            Some(health) => Some(health.clone()),
+            None => match &stream.url {
+                Some(url) if stream.is_cached => Some(probe_url(&self.http, url).await),
+                _ => None,
+            },
+        };
+        if let Some(StreamHealth::Dead(reason)) = health {
+            // Not a playback failure, so not remembered as one
+            self.try_next_source(format!("Source failed its health check: {}", reason));
+            return;
+        }
+
+        // Uncached on Real-Debrid: have it download the torrent first
+        if self.start_debrid_torrent(&stream) {
+            return;
+        }
+
+        // Check if we have a direct URL (debrid service) or need P2P streaming
         if let Some(url) = &stream.url {
-            // Real-Debrid: we have a direct HTTP URL
+            // Debrid service: we have a direct HTTP URL
             self.play_url(url);
         } else if stream.info_hash.is_some() {
             // P2P streaming: need to use TorrentStreamer
             self.pending = PendingOperation::StartP2PStream(stream);
-            self.screen = Screen::Loading(Spinner::new("Starting P2P stream..."));
+            self.screen = Screen::Loading(self.source_spinner("Starting P2P stream..."));
         } else {
-            self.screen = Screen::Error(ErrorScreen::new(
-                "No URL or torrent hash available for this source".to_string(),
-                false,
-            ));
+            self.source_failed("No URL or torrent hash available for this source".to_string());
         }
     }
 
     fn start_download(&mut self, stream: Stream) {
+        if let Some(url) = stream.url {
+            self.download_url(url);
+            return;
+        }
+
+        let download_dir = default_download_dir();
+        let (tx, _) = self.show_download_screen(&download_dir);
+        let _ = tx.send(DownloadUpdate::Failed {
+            message: "No URL or torrent hash available for this source".to_string(),
+        });
+    }
+
+    /// Download a direct URL into the download directory
+    fn download_url(&mut self, url: String) {
         let download_dir = default_download_dir();
-        let save_dir = download_dir.display().to_string();
         let title = self.current_title_for_filename();
+        let (tx, cancel) = self.show_download_screen(&download_dir);
+        tokio::spawn(download_direct_url(
+            self.http.clone(),
+            url,
+            download_dir,
+            title,
+            tx,
+            cancel,
+        ));
+    }
+
+    /// Have Real-Debrid download an uncached torrent, showing its progress until the
+    /// file can be played or downloaded. Returns false if the source isn't an uncached
+    /// Real-Debrid torrent.
+    fn start_debrid_torrent(&mut self, stream: &Stream) -> bool {
+        let (Some(client), Some(magnet)) = (&self.real_debrid, stream.magnet_link()) else {
+            return false;
+        };
+        if stream.is_cached || stream.url.is_none() || stream.debrid != Some(DebridKind::RealDebrid)
+        {
+            return false;
+        }
+
+        let title = self
+            .current_title_for_filename()
+            .unwrap_or_else(|| stream.title.lines().next().unwrap_or_default().to_string());
+        self.screen = Screen::Debrid(DebridScreen::new(title, self.mode.action_label()));
 
-        let screen_title = title
-            .clone()
+        let (tx, rx) = mpsc::unbounded_channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        tokio::spawn(wait_for_debrid(
+            client.clone(),
+            magnet,
+            self.file_target(stream),
+            tx,
+            cancel.clone(),
+        ));
+        self.debrid_updates = Some(rx);
+        self.debrid_cancel = Some(cancel);
+        true
+    }
+
+    /// Stop following an uncached torrent (Real-Debrid keeps downloading it)
+    fn stop_debrid_wait(&mut self) {
+        if let Some(cancel) = self.debrid_cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        self.debrid_updates = None;
+    }
+
+    /// Switch to the download screen and set up its progress channel and cancel flag
+    fn show_download_screen(
+        &mut self,
+        download_dir: &Path,
+    ) -> (UnboundedSender<DownloadUpdate>, Arc<AtomicBool>) {
+        let screen_title = self
+            .current_title_for_filename()
             .unwrap_or_else(|| "Selected source".to_string());
-        self.screen = Screen::Download(DownloadScreen::new(screen_title, save_dir));
+        self.screen = Screen::Download(DownloadScreen::new(
+            screen_title,
+            download_dir.display().to_string(),
+        ));
 
         let (tx, rx) = mpsc::unbounded_channel();
         let cancel = Arc::new(AtomicBool::new(false));
@@ -1159,24 +2391,109 @@ impl App {
         self.download_updates = Some(rx);
         self.download_cancel = Some(cancel.clone());
 
-        if let Some(url) = stream.url.clone() {
-            tokio::spawn(download_direct_url(url, download_dir, title, tx, cancel));
+        (tx, cancel)
+    }
+
+    /// Open a torrent in the download directory, then choose files or start downloading
+    async fn handle_start_p2p_download(&mut self, stream: Stream) {
+        let Some(magnet) = stream.magnet_link() else {
+            self.screen = Screen::Error(ErrorScreen::new(
+                "No torrent hash available for this source".to_string(),
+                false,
+            ));
+            return;
+        };
+
+        let download_dir = default_download_dir();
+        if let Err(e) = ensure_download_dir(&download_dir) {
+            self.screen = Screen::Error(ErrorScreen::new(e, false));
             return;
         }
 
-        if stream.info_hash.is_some() {
-            tokio::spawn(download_p2p(stream, download_dir, tx, cancel));
+        let streamer = match TorrentStreamer::with_download_dir(download_dir, 3131).await {
+            Ok(s) => s,
+            Err(e) => {
+                self.screen = Screen::Error(ErrorScreen::new(
+                    format!("Failed to initialize P2P download: {}", e),
+                    false,
+                ));
+                return;
+            }
+        };
+
+        let files = match streamer.open_magnet(&magnet).await {
+            Ok(files) => files,
+            Err(e) => {
+                self.screen = Screen::Error(ErrorScreen::new(
+                    format!("Failed to start P2P download: {}", e),
+                    false,
+                ));
+                return;
+            }
+        };
+
+        let target = self.file_target(&stream);
+        let selected = select_video_file(&files, target);
+        if self.wants_file_browser(&files, selected) {
+            self.screen = Screen::Files(self.files_screen(files, target));
+            self.download_streamer = Some(streamer);
             return;
         }
 
-        let _ = tx.send(DownloadUpdate::Failed {
-            message: "No URL or torrent hash available for this source".to_string(),
-        });
+        match selected {
+            Some(index) => self.start_p2p_download(streamer, vec![index]),
+            None => {
+                streamer.cleanup().await;
+                self.screen = Screen::Error(ErrorScreen::new(
+                    "No video file for this title found in torrent".to_string(),
+                    false,
+                ));
+            }
+        }
+    }
+
+    /// Start downloading files of a torrent opened for downloading
+    fn start_p2p_download(&mut self, streamer: TorrentStreamer, indices: Vec<usize>) {
+        let download_dir = default_download_dir();
+        let (tx, cancel) = self.show_download_screen(&download_dir);
+        tokio::spawn(download_p2p(streamer, indices, download_dir, tx, cancel));
+    }
+
+    /// Whether to let the user choose a torrent's files: when asked to, or when there
+    /// are several to choose from but none is known to be the one to play
+    fn wants_file_browser(&mut self, files: &[TorrentFile], selected: Option<usize>) -> bool {
+        let asked = std::mem::take(&mut self.browse_files);
+        has_file_choice(files) && (asked || selected.is_none())
+    }
+
+    /// File browser for a torrent, with the file for the current episode selected
+    fn files_screen(&self, files: Vec<TorrentFile>, target: FileTarget) -> FilesScreen {
+        let title = self
+            .current_title_for_filename()
+            .unwrap_or_else(|| "Torrent files".to_string());
+        let preselect = select_video_file(&files, target);
+        match self.mode {
+            AppMode::Download => FilesScreen::new(title, files, preselect, false, "download"),
+            AppMode::Playback => FilesScreen::new(title, files, preselect, true, "play"),
+        }
+    }
+
+    /// Which file of a torrent to play or download for the current episode
+    fn file_target(&self, stream: &Stream) -> FileTarget {
+        let episode = self
+            .playback_context
+            .as_ref()
+            .filter(|ctx| ctx.media.media_type == MediaType::TvShow)
+            .map(|ctx| (ctx.season, ctx.episode));
+        FileTarget {
+            file_idx: stream.file_idx,
+            episode,
+        }
     }
 
     fn current_title_for_filename(&self) -> Option<String> {
         self.playback_context.as_ref().map(|ctx| {
-            if ctx.season > 0 {
+            if ctx.media.media_type == MediaType::TvShow {
                 format!(
                     "{} S{:02}E{:02}",
                     ctx.media.title.trim(),
@@ -1195,44 +2512,70 @@ impl App {
         let magnet = match stream.magnet_link() {
             Some(m) => m,
             None => {
-                self.screen = Screen::Error(ErrorScreen::new(
-                    "No torrent hash available for P2P streaming".to_string(),
-                    false,
-                ));
+                self.source_failed("No torrent hash available for P2P streaming".to_string());
                 return;
             }
         };
 
         // Ensure torrent streamer is initialized
-        {
-            let mut streamer_guard = self.torrent_streamer.write().await;
-            if streamer_guard.is_none() {
-                match TorrentStreamer::new().await {
-                    Ok(streamer) => {
-                        *streamer_guard = Some(streamer);
-                        tracing::info!("Torrent streamer initialized");
-                    }
-                    Err(e) => {
-                        self.screen = Screen::Error(ErrorScreen::new(
-                            format!("Failed to initialize torrent streaming: {}", e),
-                            false,
-                        ));
-                        return;
-                    }
-                }
+        if let Err(e) = ensure_streamer(&self.torrent_streamer).await {
+            self.screen = Screen::Error(ErrorScreen::new(
+                format!("Failed to initialize torrent streaming: {}", e),
+                false,
+            ));
+            return;
+        }
+
+        // Open the torrent and list its files
+        let files = {
+            let streamer_guard = self.torrent_streamer.read().await;
+            let streamer = streamer_guard.as_ref().unwrap();
+            streamer.open_magnet(&magnet).await
+        };
+        let files = match files {
+            Ok(files) => files,
+            Err(e) => {
+                self.source_failed(format!("Failed to start stream: {}", e));
+                return;
+            }
+        };
+
+        let target = self.file_target(&stream);
+        let selected = select_video_file(&files, target);
+        if self.wants_file_browser(&files, selected) {
+            self.screen = Screen::Files(self.files_screen(files, target));
+            return;
+        }
+
+        match selected {
+            Some(index) => self.handle_stream_files(vec![index]).await,
+            None => {
+                self.cleanup().await;
+                self.source_failed("No video file for this title found in torrent".to_string());
             }
         }
+    }
 
-        // Start streaming the torrent
+    /// Stream files of the opened torrent, playing them in order
+    async fn handle_stream_files(&mut self, indices: Vec<usize>) {
         let streamer_guard = self.torrent_streamer.read().await;
-        let streamer = streamer_guard.as_ref().unwrap();
+        let Some(streamer) = streamer_guard.as_ref() else {
+            drop(streamer_guard);
+            self.screen = Screen::Error(ErrorScreen::new(
+                "Torrent streaming is not initialized".to_string(),
+                false,
+            ));
+            return;
+        };
 
-        match streamer.stream_magnet(&magnet).await {
-            Ok(handle) => {
-                tracing::info!("Streaming: {} at {}", handle.file_name, handle.stream_url);
+        match streamer.start_files(&indices).await {
+            Ok(handles) => {
+                for handle in &handles {
+                    tracing::info!("Streaming: {} at {}", handle.file_name, handle.stream_url);
+                }
 
                 // Wait for buffering before starting playback
-                self.screen = Screen::Loading(Spinner::new("Buffering..."));
+                self.screen = Screen::Loading(self.source_spinner("Buffering..."));
 
                 // Poll for ready state
                 let mut ready = false;
@@ -1255,25 +2598,29 @@ impl App {
                 drop(streamer_guard);
 
                 if ready {
-                    self.play_url(&handle.stream_url);
+                    let urls: Vec<String> = handles.into_iter().map(|h| h.stream_url).collect();
+                    self.play_urls(&urls);
                 } else {
-                    self.screen = Screen::Error(ErrorScreen::new(
+                    self.cleanup().await;
+                    self.source_failed(
                         "Buffering timeout - not enough data to start playback.\n\nThis torrent may have few seeders.".to_string(),
-                        false,
-                    ));
+                    );
                 }
             }
             Err(e) => {
-                self.screen = Screen::Error(ErrorScreen::new(
-                    format!("Failed to start stream: {}", e),
-                    false,
-                ));
+                drop(streamer_guard);
+                self.source_failed(format!("Failed to start stream: {}", e));
             }
         }
     }
 
     /// Play a URL with the configured player
     fn play_url(&mut self, url: &str) {
+        self.play_urls(&[url.to_string()]);
+    }
+
+    /// Play URLs in order (a playlist) with the configured player
+    fn play_urls(&mut self, urls: &[String]) {
         // Record to watch history when playback starts
         self.record_playback();
 
@@ -1281,24 +2628,31 @@ impl App {
         disable_raw_mode().ok();
         execute!(io::stdout(), LeaveAlternateScreen).ok();
 
-        match self.player.play(url) {
+        let result = self.player.play(urls);
+
+        // Player finished, restore TUI (even on error)
+        enable_raw_mode().ok();
+        execute!(io::stdout(), EnterAlternateScreen).ok();
+
+        // Clear playback context
+        let context = self.playback_context.take();
+
+        match result {
             Ok(()) => {
-                // Player finished, restore TUI
-                enable_raw_mode().ok();
-                execute!(io::stdout(), EnterAlternateScreen).ok();
+                if let Some(ctx) = &context {
+                    self.record_stream_choice(ctx);
+                }
                 // Return to search screen with updated history and watchlist
+                self.fallback = None;
                 self.screen = Screen::Search(self.new_search_screen());
             }
+            // The player couldn't play the source (e.g. a dead link)
+            Err(e @ PlayerError::PlaybackFailed(_)) => self.source_failed(e.to_string()),
             Err(e) => {
-                // Restore TUI even on error
-                enable_raw_mode().ok();
-                execute!(io::stdout(), EnterAlternateScreen).ok();
+                self.fallback = None;
                 self.screen = Screen::Error(ErrorScreen::new(e.to_string(), false));
             }
         }
-
-        // Clear playback context
-        self.playback_context = None;
     }
 
     /// Cleanup torrent streamer on shutdown
@@ -1311,6 +2665,101 @@ impl App {
     }
 }
 
+/// Start the torrent streaming session unless it's running
+async fn ensure_streamer(
+    streamer: &RwLock<Option<TorrentStreamer>>,
+) -> std::result::Result<(), StreamingError> {
+    // Check with a read lock first, so running health checks don't hold this up
+    if streamer.read().await.is_some() {
+        return Ok(());
+    }
+
+    let mut streamer_guard = streamer.write().await;
+    if streamer_guard.is_none() {
+        *streamer_guard = Some(TorrentStreamer::new().await?);
+        tracing::info!("Torrent streamer initialized");
+    }
+    Ok(())
+}
+
+/// Check whether a source can be played: a ranged request for debrid links, the
+/// swarm for torrents
+async fn check_stream_health(
+    http: HttpClient,
+    streamer: Arc<RwLock<Option<TorrentStreamer>>>,
+    stream: Stream,
+) -> StreamHealth {
+    if let Some(url) = &stream.url {
+        return probe_url(&http, url).await;
+    }
+    let Some(magnet) = stream.magnet_link() else {
+        return StreamHealth::Dead("no link".to_string());
+    };
+
+    if let Err(e) = ensure_streamer(&streamer).await {
+        tracing::warn!("Failed to initialize torrent streaming: {}", e);
+        return StreamHealth::Unknown("no session".to_string());
+    }
+    let streamer_guard = streamer.read().await;
+    let Some(streamer) = streamer_guard.as_ref() else {
+        return StreamHealth::Unknown("no session".to_string());
+    };
+    match streamer.probe_magnet(&magnet, P2P_PROBE_TIMEOUT).await {
+        Ok(peers) => swarm_health(peers),
+        // A fresh DHT can take longer than this to find the swarm
+        Err(StreamingError::Timeout(_)) => StreamHealth::Unknown("no metadata yet".to_string()),
+        Err(e) => {
+            tracing::debug!("Torrent probe failed: {}", e);
+            StreamHealth::Unknown("torrent error".to_string())
+        }
+    }
+}
+
+/// Scoring options for a media item, using the profile chosen on the sources screen
+/// or else the configured one for its media type
+fn scoring_options(config: &Config, profile: Option<&str>, media: &Media) -> ScoringOptions {
+    ScoringOptions::new(&config.scoring, media.media_type, media.is_anime(), profile)
+        .with_languages(&config.player)
+        .with_capabilities(&config.playback.capabilities)
+}
+
+/// Build the metadata providers from the config
+///
+/// The local catalog is loaded when configured or present at its default path, so its
+/// titles in the watch history stay reachable when TMDB is the search provider.
+fn build_providers(config: &Config, tmdb: &TmdbClient) -> Result<Providers> {
+    let catalog_path = match &config.metadata.local_catalog {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(default_catalog_path()).filter(|path| path.exists()),
+    };
+    let catalog = catalog_path
+        .map(|path| LocalCatalog::load_file(&path))
+        .transpose()?
+        .map(|catalog| Arc::new(catalog) as Arc<dyn MetadataProvider>);
+    let tmdb: Arc<dyn MetadataProvider> = Arc::new(tmdb.clone());
+
+    let providers = match (config.metadata.provider.as_str(), catalog) {
+        ("tmdb", Some(catalog)) => Providers::new(tmdb).with(catalog),
+        ("tmdb", None) => Providers::new(tmdb),
+        ("local", Some(catalog)) => Providers::new(catalog).with(tmdb),
+        ("local", None) => {
+            return Err(ApiError::Metadata(format!(
+                "Local catalog not found at {}",
+                default_catalog_path().display()
+            ))
+            .into())
+        }
+        (other, _) => {
+            return Err(ApiError::Metadata(format!(
+                "Unknown metadata provider \"{}\" (expected \"tmdb\" or \"local\")",
+                other
+            ))
+            .into())
+        }
+    };
+    Ok(providers)
+}
+
 fn default_download_dir() -> PathBuf {
     if let Some(path) = dirs::download_dir() {
         return path.join("miru");
@@ -1381,7 +2830,88 @@ fn choose_file_path(download_dir: &Path, base_name: &str) -> PathBuf {
     download_dir.join(format!("{}-{}", stem, chrono::Utc::now().timestamp()))
 }
 
+/// Save refreshed Real-Debrid OAuth tokens to the config file
+fn save_real_debrid_login(login: &OAuthLogin) {
+    let result = load_config().and_then(|mut config| {
+        config.real_debrid.api_key = login.access_token.clone();
+        config.real_debrid.oauth = Some(login.oauth.clone());
+        save_config(&config)
+    });
+    if let Err(e) = result {
+        tracing::warn!("Failed to save refreshed Real-Debrid token: {}", e);
+    }
+}
+
+/// Copy the current Real-Debrid tokens into the config before saving it
+///
+/// Keeps a save from writing back a token that was refreshed since startup.
+fn sync_real_debrid_tokens(config: &mut Config, client: Option<&RealDebridClient>) {
+    let Some(client) = client else {
+        return;
+    };
+    if let Some(oauth) = client.oauth() {
+        config.real_debrid.api_key = client.access_token();
+        config.real_debrid.oauth = Some(oauth);
+    }
+}
+
+/// Add a torrent to Real-Debrid and poll it until its file can be streamed
+async fn wait_for_debrid(
+    client: RealDebridClient,
+    magnet: String,
+    target: FileTarget,
+    tx: UnboundedSender<DebridUpdate>,
+    cancel: Arc<AtomicBool>,
+) {
+    let torrent = match client.add_magnet(&magnet).await {
+        Ok(torrent) => torrent,
+        Err(e) => {
+            let _ = tx.send(DebridUpdate::Failed(format!(
+                "Failed to add torrent to Real-Debrid: {}",
+                e
+            )));
+            return;
+        }
+    };
+
+    // Real-Debrid lists every file of the torrent in order, so file IDs map to
+    // torrent file indices
+    let choose_file = |files: &[TorrentFileInfo]| {
+        let torrent_files: Vec<TorrentFile> = files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| {
+                TorrentFile::new(
+                    index,
+                    file.path.trim_start_matches('/').to_string(),
+                    file.bytes,
+                )
+            })
+            .collect();
+        select_video_file(&torrent_files, target).map(|index| files[index].id)
+    };
+
+    while !cancel.load(Ordering::Relaxed) {
+        match client.advance_torrent(&torrent.id, choose_file).await {
+            Ok(TorrentStep::Waiting(info)) => {
+                let _ = tx.send(DebridUpdate::Progress(info));
+            }
+            Ok(TorrentStep::Ready(link)) => {
+                tracing::info!("Real-Debrid finished downloading {}", link.filename);
+                let _ = tx.send(DebridUpdate::Ready(link.download));
+                return;
+            }
+            Err(e) => {
+                let _ = tx.send(DebridUpdate::Failed(e.to_string()));
+                return;
+            }
+        }
+        tokio::time::sleep(DEBRID_POLL_INTERVAL).await;
+    }
+}
+
 async fn download_direct_url(
+    http: HttpClient,
     url: String,
     download_dir: PathBuf,
     title: Option<String>,
@@ -1400,8 +2930,7 @@ async fn download_direct_url(
 
     let target_path = choose_file_path(&download_dir, &file_name);
 
-    let client = reqwest::Client::new();
-    let response = match client.get(&url).send().await {
+    let response = match http.send(http.get(&url)).await {
         Ok(resp) => resp,
         Err(e) => {
             let _ = tx.send(DownloadUpdate::Failed {
@@ -1494,39 +3023,16 @@ async fn download_direct_url(
 }
 
 async fn download_p2p(
-    stream: Stream,
+    streamer: TorrentStreamer,
+    indices: Vec<usize>,
     download_dir: PathBuf,
     tx: UnboundedSender<DownloadUpdate>,
     cancel: Arc<AtomicBool>,
 ) {
-    if let Err(e) = ensure_download_dir(&download_dir) {
-        let _ = tx.send(DownloadUpdate::Failed { message: e });
-        return;
-    }
-
-    let magnet = match stream.magnet_link() {
-        Some(m) => m,
-        None => {
-            let _ = tx.send(DownloadUpdate::Failed {
-                message: "No torrent hash available for this source".to_string(),
-            });
-            return;
-        }
-    };
-
-    let streamer = match TorrentStreamer::with_download_dir(download_dir.clone(), 3131).await {
-        Ok(s) => s,
-        Err(e) => {
-            let _ = tx.send(DownloadUpdate::Failed {
-                message: format!("Failed to initialize P2P download: {}", e),
-            });
-            return;
-        }
-    };
-
-    let handle = match streamer.stream_magnet(&magnet).await {
+    let handles = match streamer.start_files(&indices).await {
         Ok(h) => h,
         Err(e) => {
+            streamer.cleanup().await;
             let _ = tx.send(DownloadUpdate::Failed {
                 message: format!("Failed to start P2P download: {}", e),
             });
@@ -1534,7 +3040,15 @@ async fn download_p2p(
         }
     };
 
-    let target_path = download_dir.join(&handle.file_name);
+    // A single file, or the folder holding several
+    let target_path = match handles.as_slice() {
+        [handle] => download_dir.join(&handle.file_name),
+        [handle, ..] => download_dir
+            .join(&handle.file_name)
+            .parent()
+            .map_or(download_dir.clone(), Path::to_path_buf),
+        [] => download_dir.clone(),
+    };
     loop {
         if cancel.load(Ordering::Relaxed) {
             streamer.cleanup_with(false).await;
@@ -202,6 +202,14 @@ impl DetailCard {
             lines.push(Line::from(count_info));
         }
 
+        // Collection for movies that are part of a franchise
+        if let Some(ref collection) = media.collection {
+            lines.push(Line::from(Span::styled(
+                truncate_str(&format!("Part of {}", collection.name), width),
+                theme.info(),
+            )));
+        }
+
         // Genres
         if !media.genres.is_empty() {
             lines.push(Line::from("")); // Spacer
@@ -52,6 +52,14 @@ impl<T> SelectableList<T> {
         self.state.select(Some(self.selected));
     }
 
+    /// Select the item at an index (ignored if out of range)
+    pub fn select(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.selected = index;
+            self.state.select(Some(index));
+        }
+    }
+
     /// Get the currently selected item
     pub fn get_selected(&self) -> Option<&T> {
         self.items.get(self.selected)
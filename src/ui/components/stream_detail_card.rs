@@ -5,6 +5,8 @@ use ratatui::{
     Frame,
 };
 
+use crate::api::release_name::episode_label;
+use crate::api::source_scoring::ScoreBreakdown;
 use crate::api::Stream;
 use crate::ui::theme::Theme;
 
@@ -73,14 +75,16 @@ fn wrap_text(text: &str, width: usize) -> Vec<String> {
 impl StreamDetailCard {
     /// Render the detail card for a stream
     ///
-    /// `score` and `is_recommended` provide scoring information for display.
+    /// `score` and `is_recommended` provide scoring information for display. With
+    /// `raw_scores`, every factor is listed with its exact points, zeros included.
     pub fn render(
         frame: &mut Frame,
         area: Rect,
         stream: &Stream,
         theme: &Theme,
-        score: Option<f64>,
+        score: Option<&ScoreBreakdown>,
         is_recommended: bool,
+        raw_scores: bool,
     ) {
         let block = Block::default()
             .borders(Borders::ALL)
@@ -105,14 +109,48 @@ impl StreamDetailCard {
             header_spans.push(Span::styled("  ", theme.normal()));
             header_spans.push(Span::styled("[★ Recommended]", theme.success()));
         }
+        if stream.season_pack {
+            header_spans.push(Span::styled("  ", theme.normal()));
+            header_spans.push(Span::styled("[Season pack]", theme.info()));
+        }
         lines.push(Line::from(header_spans));
 
-        // Score display
+        // Score display, with the factors that make it up
         if let Some(score) = score {
+            if !score.unsupported.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::styled("Can't play: ", theme.error()),
+                    Span::styled(score.unsupported.join(", "), theme.error()),
+                ]));
+            }
+
             lines.push(Line::from(vec![
                 Span::styled("Score: ", theme.muted()),
-                Span::styled(format!("{:.0}", score), theme.normal()),
+                Span::styled(format!("{:.0}", score.total()), theme.normal()),
             ]));
+
+            if raw_scores {
+                for (factor, points) in score.factors() {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {:<10}", factor), theme.muted()),
+                        Span::styled(format!("{:+.2}", points), theme.normal()),
+                    ]));
+                }
+            } else {
+                let factors: Vec<String> = score
+                    .factors()
+                    .iter()
+                    .filter(|(_, points)| points.round() != 0.0)
+                    .map(|(factor, points)| format!("{} {:+.0}", factor, points))
+                    .collect();
+                let max_width = inner.width.saturating_sub(4) as usize;
+                for line in wrap_text(&factors.join(" · "), max_width) {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", line),
+                        theme.muted(),
+                    )));
+                }
+            }
         }
 
         // Torrent name (with wrapping)
@@ -140,19 +178,64 @@ impl StreamDetailCard {
             lines.push(Line::from(quality_spans));
         }
 
-        // Video codec row
-        if let Some(ref codec) = stream.video_codec {
+        // Release row: group, repack and edition
+        let release: Vec<&str> = [&stream.release_group, &stream.repack, &stream.edition]
+            .into_iter()
+            .filter_map(|part| part.as_deref())
+            .collect();
+        if !release.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Release: ", theme.muted()),
+                Span::styled(release.join(" · "), theme.normal()),
+            ]));
+        }
+
+        // Episodes row
+        if let Some(label) = episode_label(stream.seasons.as_ref(), stream.episodes.as_ref()) {
+            let heading = if stream.season_pack {
+                "Season pack: "
+            } else {
+                "Episodes: "
+            };
+            lines.push(Line::from(vec![
+                Span::styled(heading, theme.muted()),
+                Span::styled(label, theme.normal()),
+            ]));
+        }
+
+        // Video codec row (bit depth is only shown when the codec doesn't mention it)
+        let mut video: Vec<String> = stream.video_codec.iter().cloned().collect();
+        if let Some(depth) = stream.bit_depth {
+            if !video.iter().any(|v| v.contains("bit")) {
+                video.push(format!("{}-bit", depth));
+            }
+        }
+        if !video.is_empty() {
             lines.push(Line::from(vec![
                 Span::styled("Video: ", theme.muted()),
-                Span::styled(codec.clone(), theme.normal()),
+                Span::styled(video.join(" "), theme.normal()),
             ]));
         }
 
         // Audio row
-        if let Some(ref audio) = stream.audio {
+        let mut audio: Vec<String> = stream.audio.iter().cloned().collect();
+        if stream.dual_audio {
+            audio.push("Dual audio".to_string());
+        } else if stream.multi_audio {
+            audio.push("Multi audio".to_string());
+        }
+        if !audio.is_empty() {
             lines.push(Line::from(vec![
                 Span::styled("Audio: ", theme.muted()),
-                Span::styled(audio.clone(), theme.normal()),
+                Span::styled(audio.join(" · "), theme.normal()),
+            ]));
+        }
+
+        // Subtitles row
+        if let Some(ref subtitles) = stream.subtitles {
+            lines.push(Line::from(vec![
+                Span::styled("Subtitles: ", theme.muted()),
+                Span::styled(subtitles.clone(), theme.normal()),
             ]));
         }
 
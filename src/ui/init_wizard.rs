@@ -14,10 +14,13 @@ use ratatui::{
     widgets::{Block, Borders, Gauge, Paragraph},
     Frame, Terminal,
 };
+use tokio::task::JoinHandle;
 
-use crate::api::{RealDebridClient, TmdbClient};
-use crate::config::{config_path, save_config, Config};
-use crate::error::Result;
+use crate::api::{
+    debrid_service, DeviceCode, HttpClient, OAuthClient, OAuthLogin, RealDebridClient, TmdbClient,
+};
+use crate::config::{config_path, save_config, Config, DebridKind, HttpConfig, RealDebridOAuth};
+use crate::error::{ApiError, Result};
 use crate::ui::components::{Input, Spinner};
 use crate::ui::theme::Theme;
 
@@ -33,8 +36,12 @@ const ASCII_ART: &str = r#"
 #[derive(Clone, PartialEq)]
 enum Step {
     Welcome,
-    RealDebrid,
-    RealDebridValidating,
+    Debrid,
+    DebridValidating,
+    /// Requesting a device login code
+    RealDebridCode,
+    /// Waiting for the user to enter the code on real-debrid.com
+    RealDebridDevice,
     Tmdb,
     TmdbValidating,
     Complete,
@@ -44,7 +51,10 @@ impl Step {
     fn index(&self) -> usize {
         match self {
             Step::Welcome => 0,
-            Step::RealDebrid | Step::RealDebridValidating => 1,
+            Step::Debrid
+            | Step::DebridValidating
+            | Step::RealDebridCode
+            | Step::RealDebridDevice => 1,
             Step::Tmdb | Step::TmdbValidating => 2,
             Step::Complete => 3,
         }
@@ -57,7 +67,10 @@ impl Step {
     fn title(&self) -> &'static str {
         match self {
             Step::Welcome => "Welcome",
-            Step::RealDebrid | Step::RealDebridValidating => "Real-Debrid (Optional)",
+            Step::Debrid
+            | Step::DebridValidating
+            | Step::RealDebridCode
+            | Step::RealDebridDevice => "Debrid Service (Optional)",
             Step::Tmdb | Step::TmdbValidating => "TMDB (Required)",
             Step::Complete => "Setup Complete",
         }
@@ -81,11 +94,18 @@ pub struct InitWizard {
     // MPV detection
     mpv_installed: bool,
 
-    // Real-Debrid
-    rd_input: Input,
-    rd_validation: ValidationResult,
-    rd_api_key: String,
-    rd_username: Option<String>,
+    // Debrid service
+    debrid_kind: DebridKind,
+    debrid_input: Input,
+    debrid_validation: ValidationResult,
+    debrid_api_key: String,
+    debrid_username: Option<String>,
+    /// OAuth credentials when logged in with a device code
+    rd_oauth: Option<RealDebridOAuth>,
+    /// Code shown while waiting for a device login
+    rd_device: Option<DeviceCode>,
+    /// Background task polling for the device login
+    rd_login: Option<JoinHandle<std::result::Result<OAuthLogin, ApiError>>>,
 
     // TMDB
     tmdb_input: Input,
@@ -94,27 +114,39 @@ pub struct InitWizard {
 
     // Spinner for validation
     spinner: Option<Spinner>,
+
+    /// Network settings (e.g. a proxy), kept in the saved config
+    http_config: HttpConfig,
+    /// HTTP client for validating keys and logging in
+    http: HttpClient,
 }
 
 impl InitWizard {
-    pub fn new(_config_exists: bool) -> Self {
+    pub fn new(_config_exists: bool, http_config: HttpConfig) -> Result<Self> {
         let mpv_installed = which::which("mpv").is_ok();
+        let http = HttpClient::new(&http_config)?;
 
         // Theme::default() uses "auto" mode which will detect terminal background
-        Self {
+        Ok(Self {
             step: Step::Welcome,
             theme: Theme::default(),
             should_quit: false,
             mpv_installed,
-            rd_input: Input::new(),
-            rd_validation: ValidationResult::None,
-            rd_api_key: String::new(),
-            rd_username: None,
+            debrid_kind: DebridKind::RealDebrid,
+            debrid_input: Input::new(),
+            debrid_validation: ValidationResult::None,
+            debrid_api_key: String::new(),
+            debrid_username: None,
+            rd_oauth: None,
+            rd_device: None,
+            rd_login: None,
             tmdb_input: Input::new(),
             tmdb_validation: ValidationResult::None,
             tmdb_api_key: String::new(),
             spinner: None,
-        }
+            http_config,
+            http,
+        })
     }
 
     /// Run the wizard
@@ -153,11 +185,23 @@ impl InitWizard {
             terminal.draw(|f| self.render(f))?;
 
             // Handle validation in progress
-            if matches!(self.step, Step::RealDebridValidating | Step::TmdbValidating) {
+            if matches!(
+                self.step,
+                Step::DebridValidating | Step::RealDebridCode | Step::TmdbValidating
+            ) {
                 self.handle_validation().await;
                 continue;
             }
 
+            if self
+                .rd_login
+                .as_ref()
+                .is_some_and(|task| task.is_finished())
+            {
+                self.finish_device_login().await;
+                continue;
+            }
+
             if self.should_quit {
                 return Ok(false);
             }
@@ -187,39 +231,59 @@ impl InitWizard {
 
     async fn handle_validation(&mut self) {
         match &self.step {
-            Step::RealDebridValidating => {
-                let key = self.rd_input.get_value().to_string();
+            Step::DebridValidating => {
+                let key = self.debrid_input.get_value().to_string();
                 if key.is_empty() {
                     // Skip validation for empty key (user skipped)
-                    self.rd_api_key = String::new();
-                    self.rd_validation =
+                    self.debrid_api_key = String::new();
+                    self.debrid_validation =
                         ValidationResult::Success("Using direct P2P streaming".to_string());
                     self.step = Step::Tmdb;
                     self.spinner = None;
                 } else {
-                    let client = RealDebridClient::new(key.clone());
-                    match client.validate_key().await {
-                        Ok(user) => {
-                            self.rd_api_key = key;
-                            self.rd_username = Some(user.username.clone());
-                            self.rd_validation = ValidationResult::Success(format!(
-                                "Logged in as: {}",
-                                user.username
-                            ));
+                    let service = debrid_service(self.debrid_kind, key.clone(), self.http.clone());
+                    match service.username().await {
+                        Ok(username) => {
+                            self.debrid_api_key = key;
+                            self.rd_oauth = None;
+                            self.debrid_validation =
+                                ValidationResult::Success(format!("Logged in as: {}", username));
+                            self.debrid_username = Some(username);
                             self.step = Step::Tmdb;
                         }
                         Err(e) => {
-                            self.rd_validation =
+                            self.debrid_validation =
                                 ValidationResult::Error(format!("Validation failed: {}", e));
-                            self.step = Step::RealDebrid;
+                            self.step = Step::Debrid;
                         }
                     }
                     self.spinner = None;
                 }
             }
+            Step::RealDebridCode => {
+                let oauth = OAuthClient::new(self.http.clone());
+                match oauth.device_code().await {
+                    Ok(device) => {
+                        let code = device.clone();
+                        self.rd_login =
+                            Some(tokio::spawn(
+                                async move { oauth.wait_for_login(&code).await },
+                            ));
+                        self.rd_device = Some(device);
+                        self.spinner = Some(Spinner::new("Waiting for approval..."));
+                        self.step = Step::RealDebridDevice;
+                    }
+                    Err(e) => {
+                        self.debrid_validation =
+                            ValidationResult::Error(format!("Login failed: {}", e));
+                        self.spinner = None;
+                        self.step = Step::Debrid;
+                    }
+                }
+            }
             Step::TmdbValidating => {
                 let key = self.tmdb_input.get_value().to_string();
-                let client = TmdbClient::new(key.clone());
+                let client = TmdbClient::new(key.clone(), self.http.clone());
                 match client.search_all("test").await {
                     Ok(_) => {
                         self.tmdb_api_key = key;
@@ -227,8 +291,10 @@ impl InitWizard {
                             ValidationResult::Success("TMDB configured successfully".to_string());
 
                         // Save config
-                        let config =
-                            Config::new(self.rd_api_key.clone(), self.tmdb_api_key.clone());
+                        let mut config = Config::new(String::new(), self.tmdb_api_key.clone());
+                        config.set_debrid_api_key(self.debrid_kind, self.debrid_api_key.clone());
+                        config.real_debrid.oauth = self.rd_oauth.clone();
+                        config.http = self.http_config.clone();
                         if let Err(e) = save_config(&config) {
                             self.tmdb_validation =
                                 ValidationResult::Error(format!("Failed to save config: {}", e));
@@ -249,10 +315,62 @@ impl InitWizard {
         }
     }
 
+    /// Store the tokens from a finished device login
+    async fn finish_device_login(&mut self) {
+        let Some(task) = self.rd_login.take() else {
+            return;
+        };
+        self.rd_device = None;
+        self.spinner = None;
+
+        let login = match task.await {
+            Ok(Ok(login)) => login,
+            Ok(Err(e)) => {
+                self.debrid_validation = ValidationResult::Error(format!("Login failed: {}", e));
+                self.step = Step::Debrid;
+                return;
+            }
+            // Cancelled
+            Err(_) => return,
+        };
+
+        let client = RealDebridClient::new(login.access_token.clone(), self.http.clone());
+        match client.validate_key().await {
+            Ok(user) => {
+                self.debrid_api_key = login.access_token;
+                self.rd_oauth = Some(login.oauth);
+                self.debrid_username = Some(user.username.clone());
+                self.debrid_validation =
+                    ValidationResult::Success(format!("Logged in as: {}", user.username));
+                self.step = Step::Tmdb;
+            }
+            Err(e) => {
+                self.debrid_validation = ValidationResult::Error(format!("Login failed: {}", e));
+                self.step = Step::Debrid;
+            }
+        }
+    }
+
+    /// Stop waiting for a device login
+    fn cancel_device_login(&mut self) {
+        if let Some(task) = self.rd_login.take() {
+            task.abort();
+        }
+        self.rd_device = None;
+        self.spinner = None;
+        self.debrid_validation = ValidationResult::None;
+        self.step = Step::Debrid;
+    }
+
     fn handle_key(&mut self, key: KeyCode) {
         match &self.step {
             Step::Welcome => self.handle_welcome_key(key),
-            Step::RealDebrid => self.handle_rd_key(key),
+            Step::Debrid => self.handle_debrid_key(key),
+            Step::RealDebridDevice => {
+                if matches!(key, KeyCode::Esc | KeyCode::Char('q')) {
+                    self.cancel_device_login();
+                }
+            }
             Step::Tmdb => self.handle_tmdb_key(key),
             _ => {}
         }
@@ -261,7 +379,7 @@ impl InitWizard {
     fn handle_welcome_key(&mut self, key: KeyCode) {
         match key {
             KeyCode::Enter => {
-                self.step = Step::RealDebrid;
+                self.step = Step::Debrid;
             }
             KeyCode::Esc | KeyCode::Char('q') => {
                 self.should_quit = true;
@@ -270,40 +388,60 @@ impl InitWizard {
         }
     }
 
-    fn handle_rd_key(&mut self, key: KeyCode) {
+    fn handle_debrid_key(&mut self, key: KeyCode) {
         match key {
             KeyCode::Enter => {
                 self.spinner = Some(Spinner::new("Validating..."));
-                self.rd_validation = ValidationResult::Validating;
-                self.step = Step::RealDebridValidating;
+                self.debrid_validation = ValidationResult::Validating;
+                self.step = Step::DebridValidating;
+            }
+            KeyCode::Up | KeyCode::Down => {
+                let index = DebridKind::ALL
+                    .iter()
+                    .position(|kind| *kind == self.debrid_kind)
+                    .unwrap_or(0);
+                let count = DebridKind::ALL.len();
+                let next = if key == KeyCode::Up {
+                    (index + count - 1) % count
+                } else {
+                    (index + 1) % count
+                };
+                self.debrid_kind = DebridKind::ALL[next];
+                self.debrid_validation = ValidationResult::None;
+            }
+            // Only Real-Debrid supports logging in with a code
+            KeyCode::Tab if self.debrid_kind == DebridKind::RealDebrid => {
+                self.spinner = Some(Spinner::new("Requesting login code..."));
+                self.debrid_validation = ValidationResult::Validating;
+                self.step = Step::RealDebridCode;
             }
             KeyCode::Esc => {
                 self.step = Step::Welcome;
-                self.rd_validation = ValidationResult::None;
+                self.debrid_validation = ValidationResult::None;
             }
             KeyCode::Backspace => {
-                self.rd_input.backspace();
-                self.rd_validation = ValidationResult::None;
+                self.debrid_input.backspace();
+                self.debrid_validation = ValidationResult::None;
             }
             KeyCode::Delete => {
-                self.rd_input.delete();
-                self.rd_validation = ValidationResult::None;
+                self.debrid_input.delete();
+                self.debrid_validation = ValidationResult::None;
             }
             KeyCode::Left => {
-                self.rd_input.move_left();
+                self.debrid_input.move_left();
             }
             KeyCode::Right => {
-                self.rd_input.move_right();
+                self.debrid_input.move_right();
             }
             KeyCode::Home => {
-                self.rd_input.move_start();
+                self.debrid_input.move_start();
             }
             KeyCode::End => {
-                self.rd_input.move_end();
+                self.debrid_input.move_end();
             }
             KeyCode::Char(c) => {
-                self.rd_input.insert(c);
-                self.rd_validation = ValidationResult::None;
+                self.debrid_input.insert(c);
+                self.debrid_validation = ValidationResult::None;
             }
             _ => {}
         }
@@ -322,7 +460,7 @@ impl InitWizard {
                 }
             }
             KeyCode::Esc => {
-                self.step = Step::RealDebrid;
+                self.step = Step::Debrid;
                 self.tmdb_validation = ValidationResult::None;
             }
             KeyCode::Backspace => {
@@ -384,8 +522,11 @@ impl InitWizard {
 
         match &self.step {
             Step::Welcome => self.render_welcome(frame, chunks[2]),
-            Step::RealDebrid => self.render_real_debrid(frame, chunks[2]),
-            Step::RealDebridValidating => self.render_validating(frame, chunks[2]),
+            Step::Debrid => self.render_debrid(frame, chunks[2]),
+            Step::DebridValidating | Step::RealDebridCode => {
+                self.render_validating(frame, chunks[2])
+            }
+            Step::RealDebridDevice => self.render_device_login(frame, chunks[2]),
             Step::Tmdb => self.render_tmdb(frame, chunks[2]),
             Step::TmdbValidating => self.render_validating(frame, chunks[2]),
             Step::Complete => self.render_complete(frame, chunks[2]),
@@ -483,8 +624,11 @@ impl InitWizard {
 
         let rd_line = Line::from(vec![
             Span::styled("  [ ] ", self.theme.muted()),
-            Span::styled("Real-Debrid API key ", self.theme.normal()),
-            Span::styled("(optional, paid)", self.theme.muted()),
+            Span::styled("Debrid service API key ", self.theme.normal()),
+            Span::styled(
+                "(optional, paid: Real-Debrid, AllDebrid, Premiumize, TorBox or Debrid-Link)",
+                self.theme.muted(),
+            ),
         ]);
         let rd_link = Line::from(vec![
             Span::styled("      Sign up at: ", self.theme.muted()),
@@ -520,11 +664,13 @@ impl InitWizard {
         frame.render_widget(checklist, chunks[2]);
     }
 
-    fn render_real_debrid(&mut self, frame: &mut Frame, area: Rect) {
+    fn render_debrid(&mut self, frame: &mut Frame, area: Rect) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Description
+                Constraint::Length(2), // Description
+                Constraint::Length(1), // Spacer
+                Constraint::Length(1), // Service
                 Constraint::Length(1), // Spacer
                 Constraint::Length(2), // Link
                 Constraint::Length(1), // Spacer
@@ -536,28 +682,78 @@ impl InitWizard {
 
         // Description
         let desc = Paragraph::new(vec![
-            Line::from("Real-Debrid provides faster cached streaming for popular content."),
-            Line::from("Without it, miru uses direct P2P streaming (free, but may buffer)."),
+            Line::from("A debrid service provides faster cached streaming for popular content."),
+            Line::from("Without one, miru uses direct P2P streaming (free, but may buffer)."),
         ])
         .style(self.theme.normal())
         .alignment(Alignment::Center);
         frame.render_widget(desc, chunks[0]);
 
+        // Service
+        let service = Paragraph::new(Line::from(vec![
+            Span::styled("Service: ", self.theme.muted()),
+            Span::styled("◀ ", self.theme.muted()),
+            Span::styled(self.debrid_kind.name(), self.theme.title()),
+            Span::styled(" ▶", self.theme.muted()),
+        ]))
+        .alignment(Alignment::Center);
+        frame.render_widget(service, chunks[2]);
+
         // Link
-        let link = Paragraph::new(vec![Line::from(vec![
+        let mut link_lines = vec![Line::from(vec![
             Span::styled("Get your API key at: ", self.theme.muted()),
-            Span::styled("https://real-debrid.com/apitoken", self.theme.highlight()),
-        ])])
-        .alignment(Alignment::Center);
-        frame.render_widget(link, chunks[2]);
+            Span::styled(self.debrid_kind.api_key_url(), self.theme.highlight()),
+        ])];
+        if self.debrid_kind == DebridKind::RealDebrid {
+            link_lines.push(Line::from(Span::styled(
+                "or press Tab to log in with a code instead",
+                self.theme.muted(),
+            )));
+        }
+        let link = Paragraph::new(link_lines).alignment(Alignment::Center);
+        frame.render_widget(link, chunks[4]);
 
         // Input
-        let input_area = self.centered_rect(60, 100, chunks[4]);
-        self.rd_input
+        let input_area = self.centered_rect(60, 100, chunks[6]);
+        self.debrid_input
             .render(frame, input_area, " API Key (Enter to skip) ", &self.theme);
 
         // Validation message
-        self.render_validation_message(frame, chunks[5], &self.rd_validation);
+        self.render_validation_message(frame, chunks[7], &self.debrid_validation);
+    }
+
+    fn render_device_login(&self, frame: &mut Frame, area: Rect) {
+        let Some(device) = &self.rd_device else {
+            return;
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Link
+                Constraint::Length(1), // Spacer
+                Constraint::Length(2), // Code
+                Constraint::Length(1), // Spacer
+                Constraint::Length(3), // Spinner
+                Constraint::Min(0),    // Spacer
+            ])
+            .split(area);
+
+        let link = Paragraph::new(vec![Line::from(vec![
+            Span::styled("Open ", self.theme.muted()),
+            Span::styled(device.verification_url.clone(), self.theme.highlight()),
+            Span::styled(" and enter this code:", self.theme.muted()),
+        ])])
+        .alignment(Alignment::Center);
+        frame.render_widget(link, chunks[0]);
+
+        let code = Paragraph::new(Span::styled(device.user_code.clone(), self.theme.title()))
+            .alignment(Alignment::Center);
+        frame.render_widget(code, chunks[2]);
+
+        if let Some(spinner) = &self.spinner {
+            let centered = self.centered_rect(50, 100, chunks[4]);
+            spinner.render(frame, centered, &self.theme);
+        }
     }
 
     fn render_tmdb(&mut self, frame: &mut Frame, area: Rect) {
@@ -644,15 +840,18 @@ impl InitWizard {
         // Summary
         let mut summary_lines = vec![];
 
-        // Real-Debrid status
-        if let Some(username) = &self.rd_username {
+        // Debrid status
+        if let Some(username) = &self.debrid_username {
             summary_lines.push(Line::from(vec![
-                Span::styled("  Real-Debrid: ", self.theme.muted()),
+                Span::styled(
+                    format!("  {}: ", self.debrid_kind.name()),
+                    self.theme.muted(),
+                ),
                 Span::styled(format!("✓ Logged in as {}", username), self.theme.info()),
             ]));
         } else {
             summary_lines.push(Line::from(vec![
-                Span::styled("  Real-Debrid: ", self.theme.muted()),
+                Span::styled("  Debrid: ", self.theme.muted()),
                 Span::styled("Using direct P2P streaming", self.theme.normal()),
             ]));
         }
@@ -714,13 +913,35 @@ impl InitWizard {
                 Span::styled("Esc", self.theme.highlight()),
                 Span::styled(" quit", self.theme.muted()),
             ]),
-            Step::RealDebrid | Step::Tmdb => Line::from(vec![
+            Step::Debrid => {
+                let mut spans = vec![
+                    Span::styled("Enter", self.theme.highlight()),
+                    Span::styled(" submit • ", self.theme.muted()),
+                    Span::styled("↑/↓", self.theme.highlight()),
+                    Span::styled(" service • ", self.theme.muted()),
+                ];
+                if self.debrid_kind == DebridKind::RealDebrid {
+                    spans.push(Span::styled("Tab", self.theme.highlight()));
+                    spans.push(Span::styled(" log in with a code • ", self.theme.muted()));
+                }
+                spans.push(Span::styled("Esc", self.theme.highlight()));
+                spans.push(Span::styled(" back", self.theme.muted()));
+                Line::from(spans)
+            }
+            Step::Tmdb => Line::from(vec![
                 Span::styled("Enter", self.theme.highlight()),
                 Span::styled(" submit • ", self.theme.muted()),
                 Span::styled("Esc", self.theme.highlight()),
                 Span::styled(" back", self.theme.muted()),
             ]),
-            Step::RealDebridValidating | Step::TmdbValidating => {
+            Step::RealDebridDevice => Line::from(vec![
+                Span::styled("Esc", self.theme.highlight()),
+                Span::styled(" cancel", self.theme.muted()),
+            ]),
+            Step::RealDebridCode => {
+                Line::from(vec![Span::styled("Requesting code...", self.theme.muted())])
+            }
+            Step::DebridValidating | Step::TmdbValidating => {
                 Line::from(vec![Span::styled("Validating...", self.theme.muted())])
             }
             Step::Complete => Line::from(vec![
@@ -0,0 +1,181 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::calendar::{CalendarEntry, CalendarWindow};
+use crate::ui::components::SelectableList;
+use crate::ui::theme::Theme;
+
+/// Action from calendar screen
+pub enum CalendarAction {
+    /// Open sources for an aired episode
+    Select(CalendarEntry),
+    /// Toggle watched status for an episode
+    ToggleWatched(CalendarEntry),
+    Back,
+}
+
+/// Upcoming and recently aired episodes of followed shows
+pub struct CalendarScreen {
+    pub list: SelectableList<CalendarEntry>,
+    window: CalendarWindow,
+}
+
+impl CalendarScreen {
+    pub fn new(entries: Vec<CalendarEntry>, window: CalendarWindow) -> Self {
+        // Start at the first episode that hasn't aired yet, or the last aired one
+        let mut list = SelectableList::new(entries);
+        let upcoming = list
+            .items
+            .iter()
+            .position(|e| !e.is_aired(window.today))
+            .unwrap_or(list.len());
+        list.select(upcoming.saturating_sub(1));
+
+        Self { list, window }
+    }
+
+    /// Handle key input
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<CalendarAction> {
+        match key.code {
+            KeyCode::Enter => {
+                if let Some(entry) = self.list.get_selected() {
+                    if entry.is_aired(self.window.today) {
+                        return Some(CalendarAction::Select(entry.clone()));
+                    }
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.list.previous();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.list.next();
+            }
+            KeyCode::Char('w') => {
+                if let Some(entry) = self.list.get_selected() {
+                    return Some(CalendarAction::ToggleWatched(entry.clone()));
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                return Some(CalendarAction::Back);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Toggle the watched status of the selected entry locally
+    pub fn toggle_watched(&mut self, entry: &CalendarEntry) {
+        if let Some(item) = self.list.items.iter_mut().find(|e| {
+            e.tmdb_id == entry.tmdb_id && e.season == entry.season && e.episode == entry.episode
+        }) {
+            item.watched = !item.watched;
+        }
+    }
+
+    /// Render the calendar screen
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Title
+                Constraint::Min(5),    // Episode list
+                Constraint::Length(2), // Help text
+            ])
+            .margin(1)
+            .split(area);
+
+        // Title
+        let today = self.window.today;
+        let new_count = self.list.items.iter().filter(|e| e.is_new(today)).count();
+        let mut title_spans = vec![
+            Span::styled("Calendar", theme.title()),
+            Span::styled(
+                format!(
+                    " {} - {}",
+                    self.window.start.format("%b %-d"),
+                    self.window.end.format("%b %-d")
+                ),
+                theme.muted(),
+            ),
+        ];
+        if new_count > 0 {
+            title_spans.push(Span::styled(
+                format!(" ({} new)", new_count),
+                theme.warning(),
+            ));
+        }
+        frame.render_widget(Paragraph::new(Line::from(title_spans)), chunks[0]);
+
+        if self.list.is_empty() {
+            let empty = Paragraph::new(Line::from(vec![
+                Span::styled("No episodes in this window. ", theme.warning()),
+                Span::styled(
+                    "Shows from your watchlist and history appear here.",
+                    theme.muted(),
+                ),
+            ]));
+            frame.render_widget(empty, chunks[1]);
+        } else {
+            self.list.render(
+                frame,
+                chunks[1],
+                " Episodes ",
+                theme,
+                |entry, is_selected| {
+                    let style = if is_selected {
+                        theme.selected()
+                    } else if entry.watched {
+                        theme.muted()
+                    } else {
+                        theme.normal()
+                    };
+                    let muted = theme.muted();
+
+                    let mut spans = vec![];
+
+                    // Status indicator
+                    if entry.watched {
+                        spans.push(Span::styled("[x] ", theme.success()));
+                    } else if entry.is_aired(today) {
+                        spans.push(Span::styled("[new] ", theme.warning()));
+                    } else {
+                        spans.push(Span::styled("[ ] ", muted));
+                    }
+
+                    let date_label = if entry.air_date == today {
+                        "Today".to_string()
+                    } else {
+                        entry.air_date.format("%a %b %-d").to_string()
+                    };
+                    spans.push(Span::styled(format!("{:<11}", date_label), muted));
+                    spans.push(Span::styled(entry.show_title.clone(), style));
+                    spans.push(Span::styled(
+                        format!(" {} ", entry.episode_display()),
+                        theme.highlight(),
+                    ));
+                    spans.push(Span::styled(entry.episode_title.clone(), muted));
+
+                    spans
+                },
+            );
+        }
+
+        // Help text
+        let help = Line::from(vec![
+            Span::styled("↑/↓", theme.highlight()),
+            Span::styled(" navigate ", theme.muted()),
+            Span::styled("Enter", theme.highlight()),
+            Span::styled(" play aired ", theme.muted()),
+            Span::styled("w", theme.highlight()),
+            Span::styled(" toggle watched ", theme.muted()),
+            Span::styled("Esc", theme.highlight()),
+            Span::styled(" back", theme.muted()),
+        ]);
+        frame.render_widget(Paragraph::new(help), chunks[2]);
+    }
+}
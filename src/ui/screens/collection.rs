@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::api::{Collection, Media, MediaSource};
+use crate::ui::components::{DetailCard, SelectableList};
+use crate::ui::theme::{Theme, STAR};
+
+/// Minimum terminal width to show the detail card
+const MIN_WIDTH_FOR_DETAIL_CARD: u16 = 100;
+
+/// Action from collection screen
+pub enum CollectionAction {
+    Select(Box<Media>),
+    Back,
+}
+
+/// Movie collection screen listing the parts of a franchise in release order
+pub struct CollectionScreen {
+    pub name: String,
+    pub list: SelectableList<Media>,
+    collection: Collection,
+    /// Watched movies
+    watched: HashSet<MediaSource>,
+}
+
+impl CollectionScreen {
+    /// Create the screen with the next unwatched part selected
+    pub fn new(collection: Collection, watched: HashSet<MediaSource>) -> Self {
+        let mut list = SelectableList::new(collection.parts.clone());
+        if let Some(index) = collection.next_unwatched(&watched) {
+            list.select(index);
+        }
+        Self {
+            name: collection.name.clone(),
+            list,
+            collection,
+            watched,
+        }
+    }
+
+    /// Handle key input
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<CollectionAction> {
+        match key.code {
+            KeyCode::Enter => {
+                // Unreleased parts have no sources to play
+                if let Some(media) = self.list.get_selected().filter(|m| m.is_released()) {
+                    return Some(CollectionAction::Select(Box::new(media.clone())));
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.list.previous();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.list.next();
+            }
+            KeyCode::Char('n') => {
+                // Jump to the next unwatched part
+                if let Some(index) = self.collection.next_unwatched(&self.watched) {
+                    self.list.select(index);
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                return Some(CollectionAction::Back);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Render the collection screen
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let show_detail_card = area.width >= MIN_WIDTH_FOR_DETAIL_CARD && !self.list.is_empty();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Title
+                Constraint::Min(5),    // Parts list (and detail card)
+                Constraint::Length(2), // Help text
+            ])
+            .margin(1)
+            .split(area);
+
+        // Title
+        let total = self.list.len();
+        let watched_count = self
+            .list
+            .items
+            .iter()
+            .filter(|m| self.watched.contains(&m.source))
+            .count();
+        let mut title = vec![Line::from(vec![
+            Span::styled(self.name.clone(), theme.title()),
+            Span::styled(
+                format!(" ({}/{} watched)", watched_count, total),
+                theme.muted(),
+            ),
+        ])];
+        if let Some(ref overview) = self.collection.overview {
+            title.push(Line::from(Span::styled(overview.clone(), theme.muted())));
+        }
+        frame.render_widget(Paragraph::new(title), chunks[0]);
+
+        if self.list.is_empty() {
+            let empty = Paragraph::new(Line::from(Span::styled(
+                "This collection has no movies.",
+                theme.warning(),
+            )));
+            frame.render_widget(empty, chunks[1]);
+        } else if show_detail_card {
+            let content_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(55), // Parts list
+                    Constraint::Percentage(45), // Detail card
+                ])
+                .split(chunks[1]);
+
+            self.render_list(frame, content_chunks[0], theme);
+
+            if let Some(media) = self.list.get_selected() {
+                DetailCard::render(frame, content_chunks[1], media, theme);
+            }
+        } else {
+            self.render_list(frame, chunks[1], theme);
+        }
+
+        // Help text
+        let help = Line::from(vec![
+            Span::styled("↑/↓", theme.highlight()),
+            Span::styled(" navigate ", theme.muted()),
+            Span::styled("Enter", theme.highlight()),
+            Span::styled(" select ", theme.muted()),
+            Span::styled("n", theme.highlight()),
+            Span::styled(" next unwatched ", theme.muted()),
+            Span::styled("Esc", theme.highlight()),
+            Span::styled(" back", theme.muted()),
+        ]);
+        frame.render_widget(Paragraph::new(help), chunks[2]);
+    }
+
+    /// Render the parts list
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let watched = self.watched.clone();
+
+        self.list
+            .render(frame, area, " Movies ", theme, |media, is_selected| {
+                let is_watched = watched.contains(&media.source);
+                let is_released = media.is_released();
+
+                let style = if is_selected {
+                    theme.selected()
+                } else if is_watched || !is_released {
+                    theme.muted()
+                } else {
+                    theme.normal()
+                };
+                let muted = theme.muted();
+
+                let mut spans = vec![];
+
+                // Watched indicator
+                if is_watched {
+                    spans.push(Span::styled("[x] ", theme.success()));
+                } else {
+                    spans.push(Span::styled("[ ] ", muted));
+                }
+
+                match media.year {
+                    Some(year) => spans.push(Span::styled(format!("{} ", year), muted)),
+                    None => spans.push(Span::styled("TBA  ", muted)),
+                }
+
+                spans.push(Span::styled(media.display_title().to_string(), style));
+
+                if let Some(score) = media.score {
+                    if score > 0.0 {
+                        spans.push(Span::styled(format!("  {} {:.1}", STAR, score), muted));
+                    }
+                }
+
+                if let Some(status) = media.status.as_deref().filter(|_| !is_released) {
+                    spans.push(Span::styled(
+                        format!("  [{}]", status.to_lowercase()),
+                        theme.warning(),
+                    ));
+                }
+
+                spans
+            });
+    }
+}
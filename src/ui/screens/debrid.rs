@@ -0,0 +1,139 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Gauge, Paragraph},
+    Frame,
+};
+
+use super::download::format_bytes;
+use crate::api::TorrentInfo;
+use crate::ui::theme::Theme;
+
+/// Action from the Real-Debrid progress screen
+pub enum DebridAction {
+    /// Stop waiting (Real-Debrid keeps downloading the torrent)
+    Cancel,
+}
+
+/// Progress of an uncached torrent being downloaded by Real-Debrid
+pub struct DebridScreen {
+    title: String,
+    /// Latest torrent status, once known
+    info: Option<TorrentInfo>,
+    /// What happens once the file is ready (play/download)
+    action_label: String,
+}
+
+impl DebridScreen {
+    pub fn new(title: String, action_label: &str) -> Self {
+        Self {
+            title,
+            info: None,
+            action_label: action_label.to_string(),
+        }
+    }
+
+    /// Show the latest torrent status
+    pub fn set_info(&mut self, info: TorrentInfo) {
+        self.info = Some(info);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<DebridAction> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(DebridAction::Cancel),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Title
+                Constraint::Length(2), // Status
+                Constraint::Length(1), // Progress bar
+                Constraint::Length(1),
+                Constraint::Length(4), // Details
+                Constraint::Length(2), // Help text
+                Constraint::Min(0),
+            ])
+            .margin(1)
+            .split(area);
+
+        let title = Paragraph::new(Line::from(vec![Span::styled(
+            format!("Real-Debrid: {}", self.title),
+            theme.title(),
+        )]));
+        frame.render_widget(title, chunks[0]);
+
+        let status = match &self.info {
+            Some(info) => status_label(&info.status),
+            None => "Adding torrent".to_string(),
+        };
+        let status_line = Paragraph::new(Line::from(vec![
+            Span::styled("Status: ", theme.muted()),
+            Span::styled(status, theme.highlight()),
+        ]));
+        frame.render_widget(status_line, chunks[1]);
+
+        let progress = self.info.as_ref().map_or(0.0, |info| info.progress);
+        let gauge = Gauge::default()
+            .gauge_style(theme.success())
+            .ratio((progress / 100.0).clamp(0.0, 1.0))
+            .label(format!("{:.1}%", progress));
+        frame.render_widget(gauge, chunks[2]);
+
+        let mut details = vec![];
+        if let Some(info) = &self.info {
+            if info.bytes > 0 {
+                details.push(Line::from(Span::styled(
+                    format!("Size: {}", format_bytes(info.bytes)),
+                    theme.normal(),
+                )));
+            }
+            if let Some(speed) = info.speed.filter(|speed| *speed > 0) {
+                details.push(Line::from(Span::styled(
+                    format!("Speed: {}/s", format_bytes(speed)),
+                    theme.normal(),
+                )));
+            }
+            if let Some(seeders) = info.seeders {
+                details.push(Line::from(Span::styled(
+                    format!("Seeders: {}", seeders),
+                    theme.normal(),
+                )));
+            }
+        }
+        details.push(Line::from(Span::styled(
+            format!(
+                "This source isn't cached yet; it will {} once Real-Debrid has it.",
+                self.action_label
+            ),
+            theme.muted(),
+        )));
+        frame.render_widget(Paragraph::new(details), chunks[4]);
+
+        let help = Line::from(vec![
+            Span::styled("Esc", theme.highlight()),
+            Span::styled(
+                " stop waiting (Real-Debrid keeps downloading)",
+                theme.muted(),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(help), chunks[5]);
+    }
+}
+
+/// Readable torrent status ("waiting_files_selection" -> "Waiting for file selection")
+fn status_label(status: &str) -> String {
+    match status {
+        "magnet_conversion" => "Fetching torrent metadata".to_string(),
+        "waiting_files_selection" => "Selecting file".to_string(),
+        "queued" => "Queued".to_string(),
+        "downloading" => "Downloading".to_string(),
+        "compressing" | "uploading" => "Finishing".to_string(),
+        "downloaded" => "Ready".to_string(),
+        other => other.replace('_', " "),
+    }
+}
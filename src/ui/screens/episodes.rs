@@ -21,6 +21,8 @@ pub enum EpisodesAction {
     Back,
     /// Toggle watched status for an episode
     ToggleWatched(Episode),
+    /// Jump to an absolute episode number outside the current season
+    JumpToAbsolute(u32),
 }
 
 /// Episode selection screen
@@ -30,6 +32,12 @@ pub struct EpisodesScreen {
     pub list: SelectableList<Episode>,
     /// Set of watched episode numbers
     watched_episodes: HashSet<u32>,
+    /// Episodes aired before this season (set for anime to allow absolute numbering)
+    absolute_offset: Option<u32>,
+    /// Whether episode numbers are shown in absolute form
+    show_absolute: bool,
+    /// Episode number being typed for "go to episode"
+    jump_input: String,
 }
 
 impl EpisodesScreen {
@@ -41,6 +49,9 @@ impl EpisodesScreen {
             media,
             season: None,
             watched_episodes: HashSet::new(),
+            absolute_offset: None,
+            show_absolute: false,
+            jump_input: String::new(),
         }
     }
 
@@ -52,6 +63,9 @@ impl EpisodesScreen {
             media,
             season: Some(season),
             watched_episodes: HashSet::new(),
+            absolute_offset: None,
+            show_absolute: false,
+            jump_input: String::new(),
         }
     }
 
@@ -62,6 +76,9 @@ impl EpisodesScreen {
             media,
             season,
             watched_episodes: HashSet::new(),
+            absolute_offset: None,
+            show_absolute: false,
+            jump_input: String::new(),
         }
     }
 
@@ -70,6 +87,61 @@ impl EpisodesScreen {
         self.watched_episodes = watched;
     }
 
+    /// Enable switching between seasonal and absolute episode numbering
+    pub fn set_absolute_offset(&mut self, offset: u32, show_absolute: bool) {
+        self.absolute_offset = Some(offset);
+        self.show_absolute = show_absolute;
+    }
+
+    /// Show absolute episode numbers (only if absolute numbering is available)
+    pub fn set_show_absolute(&mut self, show_absolute: bool) {
+        self.show_absolute = show_absolute;
+    }
+
+    /// Select an episode by its seasonal number
+    pub fn select_episode(&mut self, episode_number: u32) -> bool {
+        match self
+            .list
+            .items
+            .iter()
+            .position(|e| e.number == episode_number)
+        {
+            Some(index) => {
+                self.list.select(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Go to the typed episode number, in the current numbering mode
+    fn jump_to(&mut self, number: u32) -> Option<EpisodesAction> {
+        let offset = self.absolute_offset.filter(|_| self.show_absolute);
+        match offset {
+            Some(offset) => {
+                let in_season = number
+                    .checked_sub(offset)
+                    .filter(|ep| *ep > 0)
+                    .is_some_and(|ep| self.select_episode(ep));
+                if !in_season {
+                    return Some(EpisodesAction::JumpToAbsolute(number));
+                }
+            }
+            None => {
+                self.select_episode(number);
+            }
+        }
+        None
+    }
+
+    /// Episode number as currently displayed (seasonal or absolute)
+    fn display_number(&self, episode_number: u32) -> u32 {
+        match self.absolute_offset {
+            Some(offset) if self.show_absolute => offset + episode_number,
+            _ => episode_number,
+        }
+    }
+
     /// Check if an episode is watched
     pub fn is_watched(&self, episode_number: u32) -> bool {
         self.watched_episodes.contains(&episode_number)
@@ -78,6 +150,21 @@ impl EpisodesScreen {
     /// Handle key input
     pub fn handle_key(&mut self, key: KeyEvent) -> Option<EpisodesAction> {
         match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() && self.jump_input.len() < 5 => {
+                self.jump_input.push(c);
+            }
+            KeyCode::Backspace if !self.jump_input.is_empty() => {
+                self.jump_input.pop();
+            }
+            KeyCode::Esc if !self.jump_input.is_empty() => {
+                self.jump_input.clear();
+            }
+            KeyCode::Enter if !self.jump_input.is_empty() => {
+                let input = std::mem::take(&mut self.jump_input);
+                if let Ok(number) = input.parse() {
+                    return self.jump_to(number);
+                }
+            }
             KeyCode::Enter => {
                 if let Some(episode) = self.list.get_selected() {
                     return Some(EpisodesAction::Select(episode.clone()));
@@ -95,6 +182,10 @@ impl EpisodesScreen {
                     return Some(EpisodesAction::ToggleWatched(episode.clone()));
                 }
             }
+            KeyCode::Char('n') if self.absolute_offset.is_some() => {
+                // Toggle seasonal/absolute numbering (anime only)
+                self.show_absolute = !self.show_absolute;
+            }
             KeyCode::Esc | KeyCode::Char('q') => {
                 return Some(EpisodesAction::Back);
             }
@@ -138,8 +229,14 @@ impl EpisodesScreen {
         let title = if let Some(season) = &self.season {
             let mut spans = vec![
                 Span::styled(self.media.display_title(), theme.title()),
-                Span::styled(format!(" - Season {}", season.number), theme.highlight()),
+                Span::styled(format!(" - {}", season.label()), theme.highlight()),
             ];
+            if let Some(offset) = self.absolute_offset.filter(|_| self.show_absolute) {
+                spans.push(Span::styled(
+                    format!(" (episodes {}-{})", offset + 1, offset + total_count as u32),
+                    theme.highlight(),
+                ));
+            }
             if watched_count > 0 {
                 spans.push(Span::styled(
                     format!(" ({}/{} watched)", watched_count, total_count),
@@ -167,6 +264,16 @@ impl EpisodesScreen {
             }
             Line::from(spans)
         };
+        let title = if self.jump_input.is_empty() {
+            title
+        } else {
+            let mut spans = title.spans;
+            spans.push(Span::styled(
+                format!("  Go to episode: {}_", self.jump_input),
+                theme.highlight(),
+            ));
+            Line::from(spans)
+        };
         let title_widget = Paragraph::new(title);
         frame.render_widget(title_widget, chunks[0]);
 
@@ -205,16 +312,28 @@ impl EpisodesScreen {
         }
 
         // Help text
-        let help = Line::from(vec![
+        let mut help_spans = vec![
             Span::styled("^/v", theme.highlight()),
             Span::styled(" navigate ", theme.muted()),
             Span::styled("Enter", theme.highlight()),
             Span::styled(" play ", theme.muted()),
             Span::styled("w", theme.highlight()),
             Span::styled(" toggle watched ", theme.muted()),
-            Span::styled("Esc", theme.highlight()),
-            Span::styled(" back", theme.muted()),
-        ]);
+            Span::styled("0-9", theme.highlight()),
+            Span::styled(" go to ", theme.muted()),
+        ];
+        if self.absolute_offset.is_some() {
+            let label = if self.show_absolute {
+                " seasonal numbering "
+            } else {
+                " absolute numbering "
+            };
+            help_spans.push(Span::styled("n", theme.highlight()));
+            help_spans.push(Span::styled(label, theme.muted()));
+        }
+        help_spans.push(Span::styled("Esc", theme.highlight()));
+        help_spans.push(Span::styled(" back", theme.muted()));
+        let help = Line::from(help_spans);
         let help_widget = Paragraph::new(help);
         frame.render_widget(help_widget, chunks[2]);
     }
@@ -223,6 +342,7 @@ impl EpisodesScreen {
     fn render_list(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         // Clone watched_episodes for the closure
         let watched = self.watched_episodes.clone();
+        let number_offset = self.display_number(0);
 
         self.list
             .render(frame, area, " Episodes ", theme, |episode, is_selected| {
@@ -246,7 +366,10 @@ impl EpisodesScreen {
                     spans.push(Span::styled("[ ] ", muted));
                 }
 
-                spans.push(Span::styled(format!("{}. ", episode.number), muted));
+                spans.push(Span::styled(
+                    format!("{}. ", number_offset + episode.number),
+                    muted,
+                ));
                 spans.push(Span::styled(episode.title.clone(), style));
 
                 spans
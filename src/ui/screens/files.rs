@@ -0,0 +1,232 @@
+use std::collections::BTreeSet;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::download::format_bytes;
+use crate::streaming::TorrentFile;
+use crate::ui::components::SelectableList;
+use crate::ui::theme::Theme;
+
+/// Action from files screen
+pub enum FilesAction {
+    /// Play or download these files (torrent file indices, in playlist order)
+    Confirm(Vec<usize>),
+    Back,
+}
+
+/// Torrent file browser, shown before streaming a torrent with several files
+pub struct FilesScreen {
+    pub title: String,
+    pub list: SelectableList<TorrentFile>,
+    /// Marked files (torrent file indices)
+    marked: BTreeSet<usize>,
+    /// Only video files can be chosen (playback)
+    videos_only: bool,
+    /// Action label for Enter key (play/download)
+    action_label: String,
+}
+
+impl FilesScreen {
+    /// Create the screen with the `preselect` file (torrent index) selected
+    pub fn new(
+        title: String,
+        mut files: Vec<TorrentFile>,
+        preselect: Option<usize>,
+        videos_only: bool,
+        action_label: &str,
+    ) -> Self {
+        files.sort_by_key(|file| file.name.to_lowercase());
+        let position = preselect.and_then(|index| files.iter().position(|f| f.index == index));
+
+        let mut list = SelectableList::new(files);
+        if let Some(position) = position {
+            list.select(position);
+        }
+
+        Self {
+            title,
+            list,
+            marked: BTreeSet::new(),
+            videos_only,
+            action_label: action_label.to_string(),
+        }
+    }
+
+    fn is_choosable(&self, file: &TorrentFile) -> bool {
+        !self.videos_only || file.is_video()
+    }
+
+    /// Files to play or download: the marked files in episode order, or the selected file
+    fn chosen(&self) -> Vec<usize> {
+        if self.marked.is_empty() {
+            return self
+                .list
+                .get_selected()
+                .filter(|file| self.is_choosable(file))
+                .map(|file| vec![file.index])
+                .unwrap_or_default();
+        }
+
+        let mut files: Vec<&TorrentFile> = self
+            .list
+            .items
+            .iter()
+            .filter(|file| self.marked.contains(&file.index))
+            .collect();
+        files.sort_by_key(|file| (file.episode, file.name.to_lowercase()));
+        files.iter().map(|file| file.index).collect()
+    }
+
+    /// Handle key input
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<FilesAction> {
+        match key.code {
+            KeyCode::Enter => {
+                let chosen = self.chosen();
+                if !chosen.is_empty() {
+                    return Some(FilesAction::Confirm(chosen));
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.list.previous();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.list.next();
+            }
+            KeyCode::Char(' ') => {
+                // Toggle the selected file and move on, to mark several quickly
+                if let Some(file) = self.list.get_selected() {
+                    if self.is_choosable(file) && !self.marked.remove(&file.index) {
+                        self.marked.insert(file.index);
+                    }
+                    self.list.next();
+                }
+            }
+            KeyCode::Char('a') => {
+                // Mark all video files, or clear the marks if they already are
+                let videos: BTreeSet<usize> = self
+                    .list
+                    .items
+                    .iter()
+                    .filter(|file| file.is_video())
+                    .map(|file| file.index)
+                    .collect();
+                self.marked = if self.marked == videos {
+                    BTreeSet::new()
+                } else {
+                    videos
+                };
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                return Some(FilesAction::Back);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Render the files screen
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Title
+                Constraint::Min(5),    // Files list
+                Constraint::Length(2), // Help text
+            ])
+            .margin(1)
+            .split(area);
+
+        // Title
+        let total_size: u64 = self.list.items.iter().map(|file| file.length).sum();
+        let mut title_spans = vec![
+            Span::styled(self.title.clone(), theme.title()),
+            Span::styled(
+                format!(" ({} files, {})", self.list.len(), format_bytes(total_size)),
+                theme.muted(),
+            ),
+        ];
+        if !self.marked.is_empty() {
+            title_spans.push(Span::styled(
+                format!(" [{} marked]", self.marked.len()),
+                theme.warning(),
+            ));
+        }
+        frame.render_widget(Paragraph::new(Line::from(title_spans)), chunks[0]);
+
+        self.render_list(frame, chunks[1], theme);
+
+        // Help text
+        let action = match self.marked.len() {
+            0 => self.action_label.clone(),
+            1 => format!("{} marked", self.action_label),
+            n if self.videos_only => format!("{} {} as playlist", self.action_label, n),
+            n => format!("{} {} files", self.action_label, n),
+        };
+        let help = Line::from(vec![
+            Span::styled("↑/↓", theme.highlight()),
+            Span::styled(" navigate • ", theme.muted()),
+            Span::styled("Space", theme.highlight()),
+            Span::styled(" mark • ", theme.muted()),
+            Span::styled("a", theme.highlight()),
+            Span::styled(" mark all videos • ", theme.muted()),
+            Span::styled("Enter", theme.highlight()),
+            Span::styled(format!(" {} • ", action), theme.muted()),
+            Span::styled("Esc", theme.highlight()),
+            Span::styled(" back", theme.muted()),
+        ]);
+        frame.render_widget(Paragraph::new(help), chunks[2]);
+    }
+
+    /// Render the files list
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let marked = self.marked.clone();
+        let videos_only = self.videos_only;
+
+        self.list
+            .render(frame, area, " Files ", theme, |file, is_selected| {
+                let choosable = !videos_only || file.is_video();
+                let style = if is_selected {
+                    theme.selected()
+                } else if choosable {
+                    theme.normal()
+                } else {
+                    theme.muted()
+                };
+                let muted = theme.muted();
+
+                let mut spans = vec![];
+
+                // Mark indicator
+                if marked.contains(&file.index) {
+                    spans.push(Span::styled("[x] ", theme.success()));
+                } else {
+                    spans.push(Span::styled("[ ] ", muted));
+                }
+
+                match file.episode {
+                    Some((season, episode)) => spans.push(Span::styled(
+                        format!("S{:02}E{:02} ", season, episode),
+                        theme.info(),
+                    )),
+                    None if file.is_subtitle() => {
+                        spans.push(Span::styled("sub    ", theme.accent()))
+                    }
+                    None => spans.push(Span::styled("       ", muted)),
+                }
+
+                spans.push(Span::styled(file.name.clone(), style));
+                spans.push(Span::styled(
+                    format!("  {}", format_bytes(file.length)),
+                    muted,
+                ));
+
+                spans
+            });
+    }
+}
@@ -0,0 +1,379 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::download::format_bytes;
+use crate::api::{AccountDownload, AccountStatus, AccountTorrent};
+use crate::ui::components::SelectableList;
+use crate::ui::theme::Theme;
+
+/// A file in the Real-Debrid account that can be played or downloaded
+#[derive(Debug, Clone)]
+pub struct LibraryFile {
+    /// File name (or path within its torrent)
+    pub name: String,
+    pub bytes: u64,
+    /// Hoster link of a torrent file, or a direct link from the download history
+    pub link: String,
+    /// Whether `link` is already a direct link
+    pub direct: bool,
+}
+
+impl From<AccountDownload> for LibraryFile {
+    fn from(download: AccountDownload) -> Self {
+        Self {
+            name: download.filename,
+            bytes: download.filesize,
+            link: download.download,
+            direct: true,
+        }
+    }
+}
+
+/// Action from the library screen
+pub enum LibraryAction {
+    /// List the files of a downloaded torrent (torrent ID)
+    OpenTorrent(String),
+    Play(LibraryFile),
+    Back,
+}
+
+/// Which list is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LibraryView {
+    Torrents,
+    Downloads,
+    /// Files of the opened torrent
+    Files,
+    /// Premium, points and traffic
+    Account,
+}
+
+/// Browser for the torrents and downloads in the Real-Debrid account
+pub struct LibraryScreen {
+    torrents: SelectableList<AccountTorrent>,
+    downloads: SelectableList<LibraryFile>,
+    files: SelectableList<LibraryFile>,
+    /// Name of the opened torrent
+    torrent_name: String,
+    view: LibraryView,
+    /// Status line (loading, torrent not ready yet, ...)
+    message: Option<String>,
+    /// Account status, if it could be fetched
+    account: Option<AccountStatus>,
+}
+
+impl LibraryScreen {
+    pub fn new(torrents: Vec<AccountTorrent>, downloads: Vec<AccountDownload>) -> Self {
+        Self {
+            torrents: SelectableList::new(torrents),
+            downloads: SelectableList::new(downloads.into_iter().map(LibraryFile::from).collect()),
+            files: SelectableList::new(Vec::new()),
+            torrent_name: String::new(),
+            view: LibraryView::Torrents,
+            message: None,
+            account: None,
+        }
+    }
+
+    /// Show the account status in its own tab
+    pub fn set_account(&mut self, account: AccountStatus) {
+        self.account = Some(account);
+    }
+
+    /// Show the files of an opened torrent
+    pub fn show_files(&mut self, torrent_name: String, files: Vec<LibraryFile>) {
+        self.torrent_name = torrent_name;
+        self.files = SelectableList::new(files);
+        self.view = LibraryView::Files;
+        self.message = None;
+    }
+
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = Some(message.into());
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<LibraryAction> {
+        self.message = None;
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => match self.view {
+                LibraryView::Torrents => self.torrents.previous(),
+                LibraryView::Downloads => self.downloads.previous(),
+                LibraryView::Files => self.files.previous(),
+                LibraryView::Account => {}
+            },
+            KeyCode::Down | KeyCode::Char('j') => match self.view {
+                LibraryView::Torrents => self.torrents.next(),
+                LibraryView::Downloads => self.downloads.next(),
+                LibraryView::Files => self.files.next(),
+                LibraryView::Account => {}
+            },
+            KeyCode::Tab => {
+                self.view = match self.view {
+                    LibraryView::Torrents => LibraryView::Downloads,
+                    LibraryView::Downloads if self.account.is_some() => LibraryView::Account,
+                    _ => LibraryView::Torrents,
+                };
+            }
+            KeyCode::Enter => return self.select(),
+            KeyCode::Esc | KeyCode::Char('q') => {
+                if self.view == LibraryView::Files {
+                    self.view = LibraryView::Torrents;
+                } else {
+                    return Some(LibraryAction::Back);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn select(&mut self) -> Option<LibraryAction> {
+        match self.view {
+            LibraryView::Torrents => {
+                let torrent = self.torrents.get_selected()?;
+                if torrent.status == "downloaded" {
+                    return Some(LibraryAction::OpenTorrent(torrent.id.clone()));
+                }
+                self.message = Some(format!(
+                    "Not ready yet: {} ({:.0}%)",
+                    torrent.status.replace('_', " "),
+                    torrent.progress
+                ));
+                None
+            }
+            LibraryView::Downloads => self
+                .downloads
+                .get_selected()
+                .cloned()
+                .map(LibraryAction::Play),
+            LibraryView::Files => self.files.get_selected().cloned().map(LibraryAction::Play),
+            LibraryView::Account => None,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Title
+                Constraint::Min(5),    // List
+                Constraint::Length(2), // Help text
+            ])
+            .margin(1)
+            .split(area);
+
+        let tab = |label: &str, view: LibraryView| {
+            let style = if self.view == view {
+                theme.highlight()
+            } else {
+                theme.muted()
+            };
+            Span::styled(label.to_string(), style)
+        };
+        let title = match self.view {
+            LibraryView::Files => Line::from(vec![
+                Span::styled("Real-Debrid: ", theme.title()),
+                Span::styled(self.torrent_name.clone(), theme.normal()),
+            ]),
+            _ => Line::from(vec![
+                Span::styled("Real-Debrid Library  ", theme.title()),
+                tab(
+                    &format!("Torrents ({})", self.torrents.len()),
+                    LibraryView::Torrents,
+                ),
+                Span::styled(" | ", theme.muted()),
+                tab(
+                    &format!("Downloads ({})", self.downloads.len()),
+                    LibraryView::Downloads,
+                ),
+                Span::styled(" | ", theme.muted()),
+                tab("Account", LibraryView::Account),
+            ]),
+        };
+        frame.render_widget(Paragraph::new(title), chunks[0]);
+
+        match self.view {
+            LibraryView::Torrents => {
+                if self.torrents.is_empty() {
+                    render_empty(frame, chunks[1], "No torrents in your account.", theme);
+                } else {
+                    self.torrents.render(
+                        frame,
+                        chunks[1],
+                        " Torrents ",
+                        theme,
+                        |torrent, is_selected| {
+                            let ready = torrent.status == "downloaded";
+                            let style = if is_selected {
+                                theme.selected()
+                            } else if ready {
+                                theme.normal()
+                            } else {
+                                theme.muted()
+                            };
+                            let status = if ready {
+                                Span::styled("[✓] ", theme.success())
+                            } else {
+                                Span::styled(
+                                    format!("[{:>3.0}%] ", torrent.progress),
+                                    theme.warning(),
+                                )
+                            };
+                            vec![
+                                status,
+                                Span::styled(torrent.filename.clone(), style),
+                                Span::styled(
+                                    format!("  {}", format_bytes(torrent.bytes)),
+                                    theme.muted(),
+                                ),
+                            ]
+                        },
+                    );
+                }
+            }
+            LibraryView::Downloads => {
+                if self.downloads.is_empty() {
+                    render_empty(frame, chunks[1], "No downloads in your account.", theme);
+                } else {
+                    self.downloads.render(
+                        frame,
+                        chunks[1],
+                        " Downloads ",
+                        theme,
+                        file_spans(theme),
+                    );
+                }
+            }
+            LibraryView::Files => {
+                if self.files.is_empty() {
+                    render_empty(frame, chunks[1], "This torrent has no files.", theme);
+                } else {
+                    self.files
+                        .render(frame, chunks[1], " Files ", theme, file_spans(theme));
+                }
+            }
+            LibraryView::Account => {
+                if let Some(account) = &self.account {
+                    render_account(frame, chunks[1], account, theme);
+                }
+            }
+        }
+
+        let help = match &self.message {
+            Some(message) => Line::from(Span::styled(message.clone(), theme.warning())),
+            None => Line::from(vec![
+                Span::styled("↑/↓", theme.highlight()),
+                Span::styled(" navigate ", theme.muted()),
+                Span::styled("Enter", theme.highlight()),
+                Span::styled(" select ", theme.muted()),
+                Span::styled("Tab", theme.highlight()),
+                Span::styled(" switch tab ", theme.muted()),
+                Span::styled("Esc", theme.highlight()),
+                Span::styled(" back", theme.muted()),
+            ]),
+        };
+        frame.render_widget(Paragraph::new(help), chunks[2]);
+    }
+}
+
+/// List row for a file: name and size
+fn file_spans(theme: &Theme) -> impl Fn(&LibraryFile, bool) -> Vec<Span<'static>> + '_ {
+    move |file, is_selected| {
+        let style = if is_selected {
+            theme.selected()
+        } else {
+            theme.normal()
+        };
+        vec![
+            Span::styled(file.name.trim_start_matches('/').to_string(), style),
+            Span::styled(format!("  {}", format_bytes(file.bytes)), theme.muted()),
+        ]
+    }
+}
+
+/// Premium status, points and the traffic left per hoster
+fn render_account(frame: &mut Frame, area: Rect, account: &AccountStatus, theme: &Theme) {
+    let user = &account.user;
+    let premium = if user.is_premium() {
+        Span::styled(
+            format!(
+                "{} days left{}",
+                user.premium_days(),
+                user.expiration
+                    .as_deref()
+                    .and_then(|date| date.get(..10))
+                    .map(|date| format!(" (until {})", date))
+                    .unwrap_or_default()
+            ),
+            theme.success(),
+        )
+    } else {
+        Span::styled("expired", theme.error())
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("User:     ", theme.muted()),
+            Span::styled(user.username.clone(), theme.normal()),
+        ]),
+        Line::from(vec![Span::styled("Premium:  ", theme.muted()), premium]),
+        Line::from(vec![
+            Span::styled("Points:   ", theme.muted()),
+            Span::styled(user.points.to_string(), theme.normal()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled("Traffic left", theme.title())),
+    ];
+    if account.traffic.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No hosters with traffic limits.",
+            theme.muted(),
+        )));
+    }
+    for (host, traffic) in &account.traffic {
+        let left = match traffic.kind.as_str() {
+            "bytes" => format!(
+                "{} of {}",
+                format_bytes(traffic.left.max(0) as u64),
+                format_bytes(traffic.limit.max(0) as u64)
+            ),
+            "gigabytes" => format!("{} of {} GB", traffic.left, traffic.limit),
+            _ => format!("{} of {} {}", traffic.left, traffic.limit, traffic.kind),
+        };
+        let style = if traffic.left <= 0 {
+            theme.error()
+        } else {
+            theme.normal()
+        };
+        let mut spans = vec![
+            Span::styled(format!("{:<24}", host), theme.normal()),
+            Span::styled(left, style),
+        ];
+        if !traffic.reset.is_empty() {
+            spans.push(Span::styled(
+                format!("  (resets {})", traffic.reset),
+                theme.muted(),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border())
+        .title(" Account ");
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_empty(frame: &mut Frame, area: Rect, message: &str, theme: &Theme) {
+    let empty = Paragraph::new(Line::from(Span::styled(
+        message.to_string(),
+        theme.warning(),
+    )));
+    frame.render_widget(empty, area);
+}
@@ -1,14 +1,24 @@
+mod calendar;
+mod collection;
+mod debrid;
 mod download;
 mod episodes;
 mod error;
+mod files;
+mod library;
 mod results;
 mod search;
 mod seasons;
 mod sources;
 
+pub use calendar::{CalendarAction, CalendarScreen};
+pub use collection::{CollectionAction, CollectionScreen};
+pub use debrid::{DebridAction, DebridScreen};
 pub use download::{DownloadAction, DownloadScreen};
 pub use episodes::{EpisodesAction, EpisodesScreen};
 pub use error::{ErrorAction, ErrorScreen};
+pub use files::{FilesAction, FilesScreen};
+pub use library::{LibraryAction, LibraryFile, LibraryScreen};
 pub use results::{ResultsAction, ResultsScreen};
 pub use search::{SearchAction, SearchScreen};
 pub use seasons::{SeasonsAction, SeasonsScreen};
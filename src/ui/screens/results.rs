@@ -8,7 +8,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::api::{Media, MediaType};
+use crate::api::{CollectionRef, Media, MediaSource, MediaType};
 use crate::ui::components::{DetailCard, SelectableList};
 use crate::ui::theme::{Theme, STAR};
 
@@ -22,6 +22,8 @@ pub enum ResultsAction {
     Search,
     /// Toggle watchlist status for a media item
     ToggleWatchlist(Media),
+    /// Open the collection a movie belongs to
+    OpenCollection(Media),
 }
 
 /// Search results screen for all media types
@@ -29,7 +31,7 @@ pub struct ResultsScreen {
     pub query: String,
     pub list: SelectableList<Media>,
     /// Set of (tmdb_id, media_type) pairs currently in the watchlist
-    watchlist_ids: HashSet<(i32, MediaType)>,
+    watchlist_ids: HashSet<(MediaSource, MediaType)>,
 }
 
 impl ResultsScreen {
@@ -41,14 +43,26 @@ impl ResultsScreen {
         }
     }
 
-    pub fn with_watchlist_ids(mut self, ids: HashSet<(i32, MediaType)>) -> Self {
+    pub fn with_watchlist_ids(mut self, ids: HashSet<(MediaSource, MediaType)>) -> Self {
         self.watchlist_ids = ids;
         self
     }
 
+    /// Show the collection a listed movie belongs to (looked up in the background)
+    pub fn set_collection(&mut self, source: &MediaSource, collection: CollectionRef) {
+        for media in self
+            .list
+            .items
+            .iter_mut()
+            .filter(|m| m.media_type == MediaType::Movie && m.source == *source)
+        {
+            media.collection = Some(collection.clone());
+        }
+    }
+
     /// Toggle the watchlist status of a media item locally
-    pub fn toggle_watchlist(&mut self, tmdb_id: i32, media_type: MediaType) {
-        let key = (tmdb_id, media_type);
+    pub fn toggle_watchlist(&mut self, source: &MediaSource, media_type: MediaType) {
+        let key = (source.clone(), media_type);
         if self.watchlist_ids.contains(&key) {
             self.watchlist_ids.remove(&key);
         } else {
@@ -57,8 +71,8 @@ impl ResultsScreen {
     }
 
     /// Check if a media item is in the watchlist
-    pub fn is_in_watchlist(&self, tmdb_id: i32, media_type: MediaType) -> bool {
-        self.watchlist_ids.contains(&(tmdb_id, media_type))
+    pub fn is_in_watchlist(&self, source: &MediaSource, media_type: MediaType) -> bool {
+        self.watchlist_ids.contains(&(source.clone(), media_type))
     }
 
     /// Handle key input
@@ -80,6 +94,13 @@ impl ResultsScreen {
                     return Some(ResultsAction::ToggleWatchlist(media.clone()));
                 }
             }
+            KeyCode::Char('c') => {
+                if let Some(media) = self.list.get_selected() {
+                    if media.media_type == MediaType::Movie {
+                        return Some(ResultsAction::OpenCollection(media.clone()));
+                    }
+                }
+            }
             KeyCode::Esc => {
                 return Some(ResultsAction::Back);
             }
@@ -151,6 +172,8 @@ impl ResultsScreen {
             Span::styled(" select ", theme.muted()),
             Span::styled("a", theme.highlight()),
             Span::styled(" watchlist ", theme.muted()),
+            Span::styled("c", theme.highlight()),
+            Span::styled(" collection ", theme.muted()),
             Span::styled("/", theme.highlight()),
             Span::styled(" search ", theme.muted()),
             Span::styled("Esc", theme.highlight()),
@@ -182,7 +205,8 @@ impl ResultsScreen {
                 let mut spans = vec![];
 
                 // Watchlist indicator
-                let in_watchlist = watchlist_ids.contains(&(media.tmdb_id(), media.media_type));
+                let in_watchlist =
+                    watchlist_ids.contains(&(media.source.clone(), media.media_type));
                 if in_watchlist {
                     spans.push(Span::styled("[+] ", theme.success()));
                 }
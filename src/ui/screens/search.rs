@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -6,6 +8,7 @@ use ratatui::{
     Frame,
 };
 
+use crate::api::{MediaSource, MediaType};
 use crate::history::{WatchedItem, WatchlistItem};
 use crate::ui::components::Input;
 use crate::ui::theme::Theme;
@@ -47,6 +50,10 @@ pub struct SearchScreen {
     watchlist_state: ListState,
     /// Current focus (search bar, history, or watchlist)
     focus: Focus,
+    /// TMDB IDs of shows with aired, unwatched episodes
+    new_episode_ids: HashSet<i32>,
+    /// Real-Debrid account summary and expiry warning
+    account: Option<(String, Option<String>)>,
 }
 
 impl SearchScreen {
@@ -60,6 +67,8 @@ impl SearchScreen {
             watchlist_selected: 0,
             watchlist_state: ListState::default(),
             focus: Focus::Search,
+            new_episode_ids: HashSet::new(),
+            account: None,
         }
     }
 
@@ -73,6 +82,8 @@ impl SearchScreen {
             watchlist_selected: 0,
             watchlist_state: ListState::default(),
             focus: Focus::Search,
+            new_episode_ids: HashSet::new(),
+            account: None,
         }
     }
 
@@ -90,6 +101,8 @@ impl SearchScreen {
             watchlist_selected: 0,
             watchlist_state: ListState::default(),
             focus: Focus::Search,
+            new_episode_ids: HashSet::new(),
+            account: None,
         }
     }
 
@@ -107,6 +120,8 @@ impl SearchScreen {
             watchlist_selected: 0,
             watchlist_state: ListState::default(),
             focus: Focus::Search,
+            new_episode_ids: HashSet::new(),
+            account: None,
         }
     }
 
@@ -121,6 +136,24 @@ impl SearchScreen {
         }
     }
 
+    /// Set the shows that get a "new episode" badge
+    pub fn set_new_episode_ids(&mut self, ids: HashSet<i32>) {
+        self.new_episode_ids = ids;
+    }
+
+    /// Show the Real-Debrid account summary, or a warning when premium is running out
+    pub fn set_account(&mut self, summary: String, warning: Option<String>) {
+        self.account = Some((summary, warning));
+    }
+
+    /// Check if an item is a show with aired, unwatched episodes
+    fn has_new_episode(&self, source: &MediaSource, media_type: MediaType) -> bool {
+        media_type == MediaType::TvShow
+            && source
+                .tmdb_id()
+                .is_some_and(|id| self.new_episode_ids.contains(&id))
+    }
+
     /// Set the watch history
     #[allow(dead_code)]
     pub fn set_history(&mut self, history: Vec<WatchedItem>) {
@@ -219,11 +252,11 @@ impl SearchScreen {
                     self.history_state.select(Some(self.history_selected));
                 }
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.history_selected < self.history.len().saturating_sub(1) {
-                    self.history_selected += 1;
-                    self.history_state.select(Some(self.history_selected));
-                }
+            KeyCode::Down | KeyCode::Char('j')
+                if self.history_selected < self.history.len().saturating_sub(1) =>
+            {
+                self.history_selected += 1;
+                self.history_state.select(Some(self.history_selected));
             }
             KeyCode::Right | KeyCode::Tab => {
                 // Move to watchlist if available
@@ -276,11 +309,11 @@ impl SearchScreen {
                     self.watchlist_state.select(Some(self.watchlist_selected));
                 }
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.watchlist_selected < self.watchlist.len().saturating_sub(1) {
-                    self.watchlist_selected += 1;
-                    self.watchlist_state.select(Some(self.watchlist_selected));
-                }
+            KeyCode::Down | KeyCode::Char('j')
+                if self.watchlist_selected < self.watchlist.len().saturating_sub(1) =>
+            {
+                self.watchlist_selected += 1;
+                self.watchlist_state.select(Some(self.watchlist_selected));
             }
             KeyCode::Left | KeyCode::Tab => {
                 // Move to history if available, otherwise search
@@ -361,8 +394,23 @@ impl SearchScreen {
             .margin(2)
             .split(area);
 
-        // Title
-        let title = Line::from(vec![Span::styled("miru", theme.title())]);
+        // Title, with the Real-Debrid account status below
+        let mut title = vec![Line::from(vec![Span::styled("miru", theme.title())])];
+        match &self.account {
+            Some((_, Some(warning))) => {
+                title.push(Line::from(Span::styled(
+                    format!("⚠ {}", warning),
+                    theme.warning(),
+                )));
+            }
+            Some((summary, None)) => {
+                title.push(Line::from(Span::styled(
+                    format!("Real-Debrid: {}", summary),
+                    theme.muted(),
+                )));
+            }
+            None => {}
+        }
         let title_widget = Paragraph::new(title);
         frame.render_widget(title_widget, chunks[0]);
 
@@ -393,6 +441,8 @@ impl SearchScreen {
                 spans.push(Span::styled("d", theme.highlight()));
                 spans.push(Span::styled(" remove ", theme.muted()));
             }
+            spans.push(Span::styled("^U", theme.highlight()));
+            spans.push(Span::styled(" calendar ", theme.muted()));
             spans.push(Span::styled("^T", theme.highlight()));
             spans.push(Span::styled(" theme ", theme.muted()));
             spans.push(Span::styled("Esc", theme.highlight()));
@@ -402,6 +452,8 @@ impl SearchScreen {
             Line::from(vec![
                 Span::styled("Enter", theme.highlight()),
                 Span::styled(" search ", theme.muted()),
+                Span::styled("^U", theme.highlight()),
+                Span::styled(" calendar ", theme.muted()),
                 Span::styled("^T", theme.highlight()),
                 Span::styled(" theme ", theme.muted()),
                 Span::styled("Esc", theme.highlight()),
@@ -463,6 +515,11 @@ impl SearchScreen {
                     spans.push(Span::styled(format!(" {}", ep_display), theme.muted()));
                 }
 
+                // New episode badge
+                if self.has_new_episode(&item.source, item.media_type) {
+                    spans.push(Span::styled(" [new]", theme.warning()));
+                }
+
                 // Media type badge
                 let type_badge = match item.media_type {
                     crate::api::MediaType::Movie => " [Movie]",
@@ -521,6 +578,11 @@ impl SearchScreen {
                 };
                 spans.push(Span::styled(&item.title, title_style));
 
+                // New episode badge
+                if self.has_new_episode(&item.source, item.media_type) {
+                    spans.push(Span::styled(" [new]", theme.warning()));
+                }
+
                 // Media type badge
                 let type_badge = match item.media_type {
                     crate::api::MediaType::Movie => " [Movie]",
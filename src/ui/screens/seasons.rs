@@ -74,11 +74,17 @@ impl SeasonsScreen {
             .margin(1)
             .split(area);
 
-        // Title
-        let title = Line::from(vec![
+        // Title (specials are listed separately and not counted as a season)
+        let regular_seasons = self.list.items.iter().filter(|s| !s.is_specials()).count();
+        let mut title_spans = vec![
             Span::styled(self.media.display_title(), theme.title()),
-            Span::styled(format!(" ({} seasons)", self.list.len()), theme.muted()),
-        ]);
+            Span::styled(format!(" ({} seasons", regular_seasons), theme.muted()),
+        ];
+        if regular_seasons < self.list.len() {
+            title_spans.push(Span::styled(" + specials", theme.muted()));
+        }
+        title_spans.push(Span::styled(")", theme.muted()));
+        let title = Line::from(title_spans);
         let title_widget = Paragraph::new(title);
         frame.render_widget(title_widget, chunks[0]);
 
@@ -105,7 +111,7 @@ impl SeasonsScreen {
                     };
                     let muted = theme.muted();
 
-                    let mut spans = vec![Span::styled(format!("Season {} ", season.number), style)];
+                    let mut spans = vec![Span::styled(format!("{} ", season.label()), style)];
 
                     // Show watched count if any episodes are watched
                     let watched = watched_counts.get(&season.number).copied().unwrap_or(0);
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -6,18 +8,31 @@ use ratatui::{
     Frame,
 };
 
-use crate::api::{calculate_source_score, Media, ScoringOptions, Stream};
+use crate::api::source_scoring::sort_streams_by_score;
+use crate::api::{
+    calculate_source_score, fallback_streams, rank_streams, unsupported_features, FilteredStreams,
+    KitsuEpisode, Media, ScoringOptions, Stream, StreamHealth,
+};
 use crate::ui::components::{SelectableList, StreamDetailCard};
 use crate::ui::theme::Theme;
 
 /// Minimum terminal width to show the detail card
 const MIN_WIDTH_FOR_DETAIL_CARD: u16 = 100;
 
+/// Number of recommended sources pinned to the top of the list
+const RECOMMENDED_LIMIT: usize = 2;
+
 /// Action from sources screen
 pub enum SourcesAction {
-    Select(Stream),
+    Select(Box<Stream>),
+    /// Choose the files to play from this torrent, even if the right one is known
+    BrowseFiles(Box<Stream>),
     Back,
     ToggleUncached,
+    /// Switch to the next scoring profile
+    NextProfile,
+    /// Hide this release group from now on
+    HideGroup(String),
 }
 
 /// Context needed to re-fetch sources
@@ -27,6 +42,8 @@ pub struct SourcesContext {
     pub season: u32,
     pub episode: u32,
     pub imdb_id: String,
+    /// Kitsu episode for anime with an ID mapping (looked up before the IMDb ID)
+    pub kitsu: Option<KitsuEpisode>,
 }
 
 /// Source/torrent selection screen
@@ -38,35 +55,108 @@ pub struct SourcesScreen {
     pub show_uncached: bool,
     /// Context for re-fetching sources when toggling
     pub context: SourcesContext,
+    /// Scoring options (and profile) the sources are ranked with
+    pub scoring: ScoringOptions,
     /// Number of recommended sources pinned to the top of the list
     pub recommended_count: usize,
+    /// Number of demoted sources at the bottom of the list
+    demoted_count: usize,
+    /// Number of sources hidden by filter rules
+    hidden_count: usize,
+    /// Results of the background health checks, by `Stream::source_key`
+    health: HashMap<String, StreamHealth>,
+    /// Show raw scores in the list and every factor in the detail card
+    raw_scores: bool,
     /// Action label for Enter key (play/download)
     action_label: String,
+    /// Why no cached sources can be found (e.g., expired Real-Debrid premium)
+    account_warning: Option<String>,
 }
 
 impl SourcesScreen {
+    /// Create the screen, ranking `sources` with the scoring options
     pub fn new(
         title: String,
         episode_number: u32,
-        sources: Vec<Stream>,
+        sources: FilteredStreams,
         context: SourcesContext,
         show_uncached: bool,
-        recommended_count: usize,
+        scoring: ScoringOptions,
         action_label: &str,
     ) -> Self {
-        Self {
+        let mut screen = Self {
             title,
             episode_number: if episode_number > 0 {
                 Some(episode_number)
             } else {
                 None
             },
-            list: SelectableList::new(sources),
+            list: SelectableList::new(Vec::new()),
             show_uncached,
             context,
-            recommended_count,
+            scoring,
+            recommended_count: 0,
+            demoted_count: 0,
+            hidden_count: sources.hidden,
+            health: HashMap::new(),
+            raw_scores: false,
             action_label: action_label.to_string(),
-        }
+            account_warning: None,
+        };
+        screen.rank(sources.shown, sources.demoted);
+        screen
+    }
+
+    /// Rank shown sources (pinning the best picks) above demoted ones, selecting the top
+    fn rank(&mut self, shown: Vec<Stream>, mut demoted: Vec<Stream>) {
+        let (mut sources, recommended_count) =
+            rank_streams(shown, &self.scoring, RECOMMENDED_LIMIT);
+        sort_streams_by_score(&mut demoted, &self.scoring);
+        self.recommended_count = recommended_count;
+        self.demoted_count = demoted.len();
+        sources.extend(demoted);
+        self.list = SelectableList::new(sources);
+    }
+
+    /// Take the listed sources, split into shown and demoted ones
+    fn take_sources(&mut self) -> (Vec<Stream>, Vec<Stream>) {
+        let mut shown = std::mem::take(&mut self.list.items);
+        let demoted = shown.split_off(shown.len() - self.demoted_count);
+        (shown, demoted)
+    }
+
+    /// Re-rank the sources with other scoring options, selecting the new best pick
+    pub fn set_scoring(&mut self, scoring: ScoringOptions) {
+        self.scoring = scoring;
+        let (shown, demoted) = self.take_sources();
+        self.rank(shown, demoted);
+    }
+
+    /// Sources to fall back to if `chosen` fails to play (never demoted ones)
+    pub fn fallbacks(&self, chosen: &Stream, limit: usize) -> Vec<Stream> {
+        let shown = &self.list.items[..self.list.items.len() - self.demoted_count];
+        fallback_streams(shown, &self.scoring, chosen, limit)
+    }
+
+    /// Show the result of a source's health check
+    pub fn set_health(&mut self, source_key: String, health: StreamHealth) {
+        self.health.insert(source_key, health);
+    }
+
+    /// Remove the sources of a release group (after a rule hiding it was added)
+    pub fn hide_release_group(&mut self, group: &str) {
+        let in_group = |stream: &Stream| {
+            stream
+                .release_group
+                .as_ref()
+                .is_some_and(|g| g.eq_ignore_ascii_case(group))
+        };
+        let (mut shown, mut demoted) = self.take_sources();
+        let before = shown.len() + demoted.len();
+        shown.retain(|stream| !in_group(stream));
+        demoted.retain(|stream| !in_group(stream));
+        self.hidden_count += before - shown.len() - demoted.len();
+        self.rank(shown, demoted);
     }
 
     /// Handle key input
@@ -74,7 +164,7 @@ impl SourcesScreen {
         match key.code {
             KeyCode::Enter => {
                 if let Some(source) = self.list.get_selected() {
-                    return Some(SourcesAction::Select(source.clone()));
+                    return Some(SourcesAction::Select(Box::new(source.clone())));
                 }
             }
             KeyCode::Up | KeyCode::Char('k') => {
@@ -86,6 +176,31 @@ impl SourcesScreen {
             KeyCode::Char('u') => {
                 return Some(SourcesAction::ToggleUncached);
             }
+            KeyCode::Char('p') => {
+                return Some(SourcesAction::NextProfile);
+            }
+            KeyCode::Char('d') => {
+                self.raw_scores = !self.raw_scores;
+            }
+            KeyCode::Char('f') => {
+                // Only P2P sources have a file list to browse
+                if let Some(source) = self
+                    .list
+                    .get_selected()
+                    .filter(|s| s.url.is_none() && s.info_hash.is_some())
+                {
+                    return Some(SourcesAction::BrowseFiles(Box::new(source.clone())));
+                }
+            }
+            KeyCode::Char('h') => {
+                if let Some(group) = self
+                    .list
+                    .get_selected()
+                    .and_then(|s| s.release_group.clone())
+                {
+                    return Some(SourcesAction::HideGroup(group));
+                }
+            }
             KeyCode::Esc | KeyCode::Char('q') => {
                 return Some(SourcesAction::Back);
             }
@@ -122,6 +237,18 @@ impl SourcesScreen {
             title_spans.push(Span::styled(" [showing uncached]", theme.warning()));
         }
 
+        if self.hidden_count > 0 {
+            title_spans.push(Span::styled(
+                format!(" ({} hidden)", self.hidden_count),
+                theme.muted(),
+            ));
+        }
+
+        title_spans.push(Span::styled(
+            format!(" [{}]", self.scoring.profile_name),
+            theme.info(),
+        ));
+
         let title = Line::from(title_spans);
         let title_widget = Paragraph::new(title);
         frame.render_widget(title_widget, chunks[0]);
@@ -144,25 +271,16 @@ impl SourcesScreen {
 
             // Render the detail card for the selected item
             if let Some(stream) = self.list.get_selected() {
-                let is_anime = self
-                    .context
-                    .media
-                    .genres
-                    .iter()
-                    .any(|g| g.eq_ignore_ascii_case("animation"));
-                let scoring_options = ScoringOptions {
-                    media_type: self.context.media.media_type,
-                    is_anime,
-                };
-                let score = calculate_source_score(stream, &scoring_options);
+                let score = calculate_source_score(stream, &self.scoring);
                 let is_recommended = self.list.selected < self.recommended_count;
                 StreamDetailCard::render(
                     frame,
                     content_chunks[1],
                     stream,
                     theme,
-                    Some(score),
+                    Some(&score),
                     is_recommended,
+                    self.raw_scores,
                 );
             }
         } else {
@@ -174,6 +292,11 @@ impl SourcesScreen {
         self.render_help(frame, chunks[2], theme);
     }
 
+    /// Explain an empty list of cached sources
+    pub fn set_account_warning(&mut self, warning: String) {
+        self.account_warning = Some(warning);
+    }
+
     /// Render the empty state message
     fn render_empty_state(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let mut lines = vec![];
@@ -185,6 +308,11 @@ impl SourcesScreen {
 
         lines.push(Line::from(""));
 
+        if let Some(warning) = &self.account_warning {
+            lines.push(Line::from(Span::styled(warning.clone(), theme.error())));
+            lines.push(Line::from(""));
+        }
+
         if !self.show_uncached {
             lines.push(Line::from(vec![
                 Span::styled("Press ", theme.muted()),
@@ -217,6 +345,14 @@ impl SourcesScreen {
             Span::styled(format!(" {} • ", self.action_label), theme.muted()),
             Span::styled("u", theme.highlight()),
             Span::styled(format!(" {} • ", uncached_text), theme.muted()),
+            Span::styled("p", theme.highlight()),
+            Span::styled(" profile • ", theme.muted()),
+            Span::styled("d", theme.highlight()),
+            Span::styled(" scores • ", theme.muted()),
+            Span::styled("h", theme.highlight()),
+            Span::styled(" hide group • ", theme.muted()),
+            Span::styled("f", theme.highlight()),
+            Span::styled(" files • ", theme.muted()),
             Span::styled("Esc", theme.highlight()),
             Span::styled(" back", theme.muted()),
         ]);
@@ -227,6 +363,11 @@ impl SourcesScreen {
     /// Render the sources list
     fn render_list(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let recommended_count = self.recommended_count;
+        let first_demoted = self.list.len() - self.demoted_count;
+        let scoring = self.raw_scores.then_some(&self.scoring);
+        let capabilities = &self.scoring.capabilities;
+        let media_type = self.scoring.media_type;
+        let health = &self.health;
         self.list.render_with_index(
             frame,
             area,
@@ -242,11 +383,47 @@ impl SourcesScreen {
 
                 let mut spans = vec![];
 
+                // Raw score in debug mode
+                if let Some(scoring) = scoring {
+                    let score = calculate_source_score(source, scoring).total();
+                    spans.push(Span::styled(format!("{:>6.0} ", score), theme.info()));
+                }
+
                 // Show recommended indicator for pinned sources
                 if index < recommended_count {
                     spans.push(Span::styled("[★] ", theme.success()));
                 }
 
+                // Health check result (checked in the background for the top sources)
+                match health.get(&source.source_key()) {
+                    Some(result) if result.is_healthy() => spans.push(Span::styled(
+                        format!("[✓ {}] ", result.label()),
+                        theme.success(),
+                    )),
+                    Some(result @ StreamHealth::Unknown(_)) => spans.push(Span::styled(
+                        format!("[? {}] ", result.label()),
+                        theme.warning(),
+                    )),
+                    Some(result) => spans.push(Span::styled(
+                        format!("[✗ {}] ", result.label()),
+                        theme.error(),
+                    )),
+                    None => {}
+                }
+
+                // Demoted sources are listed last, muted
+                let style = if index >= first_demoted && !is_selected {
+                    spans.push(Span::styled("[demoted] ", muted));
+                    muted
+                } else {
+                    style
+                };
+
+                // Sources the playback device can't handle
+                if !unsupported_features(source, capabilities, media_type).is_empty() {
+                    spans.push(Span::styled("[unsupported] ", theme.error()));
+                }
+
                 // Show uncached indicator
                 if !source.is_cached {
                     spans.push(Span::styled("[uncached] ", theme.error()));
@@ -265,6 +442,11 @@ impl SourcesScreen {
                     spans.push(Span::styled(format!(" {}", size), muted));
                 }
 
+                // Season packs hold the whole season; only the episode is streamed
+                if source.season_pack {
+                    spans.push(Span::styled(" [season pack]", theme.info()));
+                }
+
                 if let Some(seeders) = source.seeders {
                     spans.push(Span::styled(format!(" 👤{}", seeders), muted));
                 }
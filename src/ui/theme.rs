@@ -5,10 +5,11 @@ use tracing::warn;
 use crate::config::{ThemeColors, UiConfig};
 
 /// Theme variant for selecting color schemes
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ThemeVariant {
     /// Terminal default colors - uses ANSI colors that adapt to terminal's theme
+    #[default]
     Auto,
     /// Catppuccin Mocha - optimized for dark backgrounds
     Dark,
@@ -45,12 +46,6 @@ impl ThemeVariant {
     }
 }
 
-impl Default for ThemeVariant {
-    fn default() -> Self {
-        ThemeVariant::Auto
-    }
-}
-
 /// Catppuccin-inspired color theme with support for dark/light modes
 /// and custom color overrides.
 pub struct Theme {